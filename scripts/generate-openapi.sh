@@ -21,6 +21,8 @@
 # OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 # SOFTWARE.
 
+set -euo pipefail
+
 print!() {
     echo $@
 }
@@ -39,38 +41,45 @@ if ! command -v java &> /dev/null; then
     fatal! "Missing \`java\` on system."
 fi
 
-if ! command -v docker &> /dev/null; then
-    fatal! "Missing \`docker\` on system."
-fi
-
 if ! command -v make &> /dev/null; then
     fatal! "Missing \`make\` on system."
 fi
 
-print! "Now pulling OpenAPI tools image..."
-docker pull "openapitools/openapi-generator-cli:v6.3.0"
+if ! command -v cargo &> /dev/null; then
+    fatal! "Missing \`cargo\` on system."
+fi
+
+WORK_DIR=$(mktemp -d)
+trap 'rm -rf "$WORK_DIR"' EXIT
 
-mkdir ./_work
-print! "Cloning charted-server on main branch!"
-git clone https://github.com/charted-dev/charted -b main ./_work &> /dev/null
+# The document is generated by the version of charted-server that is pinned in ./openapi/VERSION, which
+# the contract tests check against.
+VERSION=$(cat ./openapi/VERSION)
+
+print! "Cloning charted-server v$VERSION!"
+git clone https://github.com/charted-dev/charted -b "$VERSION" --depth 1 "$WORK_DIR/charted" &> /dev/null || fatal! "Unable to clone charted-server v$VERSION"
 
 print! "Building project..."
-pushd ./_work
+pushd "$WORK_DIR/charted"
     make build
 popd
 
-print! "Generating OpenAPI from CLI..."
-OPENAPI_FILE=$(cd ./_work/cli/build/install/charted && ./bin/charted openapi --format=json --openapi-version=3.0)
+# The document is written somewhere else first, so that the placeholder in ./openapi/charted.json is
+# only replaced if the CLI generated a document.
+print! "Generating OpenAPI document from CLI..."
+(cd "$WORK_DIR/charted/cli/build/install/charted" && ./bin/charted openapi --format=json --openapi-version=3.0) > "$WORK_DIR/charted.json"
+
+if ! grep -q '"openapi"' "$WORK_DIR/charted.json"; then
+    fatal! "charted-server didn't generate an OpenAPI document"
+fi
 
-print! "Generating Rust client from OpenAPI..."
-echo $OPENAPI_FILE > ./_work/openapi.json
+if ! grep -q "\"version\": *\"$VERSION\"" "$WORK_DIR/charted.json"; then
+    fatal! "the OpenAPI document isn't for charted-server v$VERSION"
+fi
 
-docker run --rm --name charted-sdk-rust-openapi \
-    -v "$(pwd):/local" -v "$(pwd)/_work:/_work" openapitools/openapi-generator-cli:v6.3.0 generate \
-    -i /_work/openapi.json \
-    -g rust \
-    -o /local/out/rust \
-    --skip-validate-spec
+mv "$WORK_DIR/charted.json" ./openapi/charted.json
+print! "Generating models and endpoints from ./openapi/charted.json..."
+cargo xtask codegen
+cargo xtask codegen --check
 
-rm -rf ./_work
 print! "Done~!"
@@ -0,0 +1,69 @@
+#!/bin/bash
+
+# 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+# Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+#
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to deal
+# in the Software without restriction, including without limitation the rights
+# to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+# copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+#
+# The above copyright notice and this permission notice shall be included in all
+# copies or substantial portions of the Software.
+#
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+# OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+# SOFTWARE.
+
+print!() {
+    echo $@
+}
+
+fatal!() {
+    echo "[~+~ fatal ~+~]" $@
+    exit 1
+}
+
+# Regenerates the index.yaml fixtures in tests/fixtures/helm with Helm itself, see
+# tests/fixtures/helm/README.md for more information.
+if ! command -v helm &> /dev/null; then
+    fatal! "Missing \`helm\` on system."
+fi
+
+if ! command -v curl &> /dev/null; then
+    fatal! "Missing \`curl\` on system."
+fi
+
+if ! command -v yq &> /dev/null; then
+    fatal! "Missing \`yq\` (https://github.com/mikefarah/yq) on system."
+fi
+
+FIXTURES=./tests/fixtures/helm
+WORK=$(mktemp -d)
+trap "rm -rf $WORK" EXIT
+
+print! "Packaging the charts in $FIXTURES/charts..."
+mkdir -p $WORK/local
+for chart in $FIXTURES/charts/*/*/; do
+    helm package "$chart" --destination $WORK/local > /dev/null || fatal! "Unable to package $chart"
+done
+
+print! "Generating $FIXTURES/local.yaml with \`helm repo index\`..."
+helm repo index $WORK/local || fatal! "Unable to index $WORK/local"
+cp $WORK/local/index.yaml $FIXTURES/local.yaml
+
+print! "Downloading the Bitnami chart repository's index..."
+curl -fsSL https://charts.bitnami.com/bitnami/index.yaml -o $WORK/bitnami.yaml || fatal! "Unable to download the Bitnami index"
+
+# Only the newest three nginx versions and the newest postgresql version are kept, the entries
+# themselves aren't touched.
+yq '{"apiVersion": .apiVersion, "entries": {"nginx": .entries.nginx[0:3], "postgresql": .entries.postgresql[0:1]}, "generated": .generated}' \
+    $WORK/bitnami.yaml > $FIXTURES/bitnami.yaml || fatal! "Unable to take an excerpt of the Bitnami index"
+
+print! "Done~!"
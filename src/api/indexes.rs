@@ -0,0 +1,58 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use async_trait::async_trait;
+
+use crate::{
+    models::{ChartIndexSpec, ChartIndexYaml},
+    IndexesContainer, Result,
+};
+
+/// Represents the `/indexes` REST handler, which is implemented by the [`IndexesContainer`].
+///
+/// [`IndexesContainer`]: ../struct.IndexesContainer.html
+#[async_trait]
+pub trait IndexesApi: Send + Sync {
+    /// Gets a user or organization's `index.yaml` by their name, or `None` if it doesn't exist.
+    async fn get(&self, name: &str) -> Result<Option<ChartIndexYaml>>;
+
+    /// Gets a user or organization's `index.yaml` by their ID, or `None` if it doesn't exist.
+    async fn get_by_id(&self, id: u64) -> Result<Option<ChartIndexYaml>>;
+
+    /// Downloads the chart package of an entry from a user or organization's `index.yaml`, and verifies
+    /// its digest.
+    async fn download(&self, name: &str, spec: &ChartIndexSpec) -> Result<Vec<u8>>;
+}
+
+#[async_trait]
+impl IndexesApi for IndexesContainer {
+    async fn get(&self, name: &str) -> Result<Option<ChartIndexYaml>> {
+        IndexesContainer::get(self, name).await
+    }
+
+    async fn get_by_id(&self, id: u64) -> Result<Option<ChartIndexYaml>> {
+        IndexesContainer::get_by_id(self, id).await
+    }
+
+    async fn download(&self, name: &str, spec: &ChartIndexSpec) -> Result<Vec<u8>> {
+        IndexesContainer::download(self, name, spec).await
+    }
+}
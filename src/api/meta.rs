@@ -0,0 +1,65 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use async_trait::async_trait;
+
+use crate::{
+    models::{FeaturesResponse, InfoResponse, MainResponse},
+    APIClient, Result,
+};
+
+/// Represents the REST handlers that describe the API server itself, which is implemented by
+/// the [`APIClient`].
+///
+/// [`APIClient`]: ../struct.APIClient.html
+#[async_trait]
+pub trait MetaApi: Send + Sync {
+    /// Checks if the API server is healthy, with the `GET /health` REST handler.
+    async fn health(&self) -> Result<()>;
+
+    /// Returns the response of the `GET /` REST handler.
+    async fn main(&self) -> Result<MainResponse>;
+
+    /// Returns the response of the `GET /info` REST handler.
+    async fn info(&self) -> Result<InfoResponse>;
+
+    /// Returns the response of the `GET /features` REST handler.
+    async fn features(&self) -> Result<FeaturesResponse>;
+}
+
+#[async_trait]
+impl MetaApi for APIClient {
+    async fn health(&self) -> Result<()> {
+        APIClient::health(self).await
+    }
+
+    async fn main(&self) -> Result<MainResponse> {
+        APIClient::main(self).await
+    }
+
+    async fn info(&self) -> Result<InfoResponse> {
+        APIClient::info(self).await
+    }
+
+    async fn features(&self) -> Result<FeaturesResponse> {
+        APIClient::features(self).await
+    }
+}
@@ -0,0 +1,94 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Traits for each area of the API that the [`APIClient`] and its containers implement, so code that
+//! uses this crate can depend on trait objects or generics instead, and swap in its own implementations
+//! (i.e, mocks in tests) without an HTTP layer.
+//!
+//! Only the API areas that this crate has containers for have a trait: [`MetaApi`] for the `/`,
+//! `/health`, `/info` and `/features` REST handlers, `IndexesApi` (with the `indexes` feature),
+//! `RepositoriesApi` (with the `repositories` feature) and `SearchApi` (with the `search` feature). The traits use [`async_trait`], which is re-exported so
+//! implementations don't need to depend on it themselves.
+//!
+//! ## Example
+//! ```
+//! use charted::{
+//!     api::{async_trait, MetaApi},
+//!     models::{FeaturesResponse, InfoResponse, MainResponse},
+//!     APIClient, Result,
+//! };
+//!
+//! async fn is_up(api: &dyn MetaApi) -> bool {
+//!     api.health().await.is_ok()
+//! }
+//!
+//! struct AlwaysDown;
+//!
+//! #[async_trait]
+//! impl MetaApi for AlwaysDown {
+//!     async fn health(&self) -> Result<()> {
+//!         Err(charted::Error::String("server is down".into()))
+//!     }
+//!
+//!     async fn main(&self) -> Result<MainResponse> {
+//!         unimplemented!()
+//!     }
+//!
+//!     async fn info(&self) -> Result<InfoResponse> {
+//!         unimplemented!()
+//!     }
+//!
+//!     async fn features(&self) -> Result<FeaturesResponse> {
+//!         unimplemented!()
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! assert!(!is_up(&AlwaysDown).await);
+//!
+//! // the real client can be used in the same place
+//! let client = APIClient::default_with_url("http://localhost:3651");
+//! # if false {
+//! is_up(&client).await;
+//! # }
+//! # }
+//! ```
+//!
+//! [`APIClient`]: ../struct.APIClient.html
+//! [`async_trait`]: https://docs.rs/async-trait
+
+#[cfg(feature = "indexes")]
+mod indexes;
+mod meta;
+#[cfg(feature = "repositories")]
+mod repositories;
+#[cfg(feature = "search")]
+mod search;
+
+pub use async_trait::async_trait;
+#[cfg(feature = "indexes")]
+pub use indexes::*;
+pub use meta::*;
+#[cfg(feature = "repositories")]
+pub use repositories::*;
+#[cfg(feature = "search")]
+pub use search::*;
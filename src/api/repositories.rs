@@ -0,0 +1,47 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use async_trait::async_trait;
+
+use crate::{models::Repository, RepositoriesContainer, Result};
+
+/// Represents the `/repositories` REST handler, which is implemented by the [`RepositoriesContainer`].
+///
+/// [`RepositoriesContainer`]: ../struct.RepositoriesContainer.html
+#[async_trait]
+pub trait RepositoriesApi: Send + Sync {
+    /// Gets a repository by its ID, or `None` if it doesn't exist.
+    async fn get(&self, id: u64) -> Result<Option<Repository>>;
+
+    /// Lists the repositories that a user owns, by the user's ID or name.
+    async fn list_for_user(&self, user: &str) -> Result<Vec<Repository>>;
+}
+
+#[async_trait]
+impl RepositoriesApi for RepositoriesContainer {
+    async fn get(&self, id: u64) -> Result<Option<Repository>> {
+        RepositoriesContainer::get(self, id).await
+    }
+
+    async fn list_for_user(&self, user: &str) -> Result<Vec<Repository>> {
+        RepositoriesContainer::list_for_user(self, user).await
+    }
+}
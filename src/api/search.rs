@@ -0,0 +1,57 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use async_trait::async_trait;
+
+use crate::{
+    models::{Organization, Repository, SearchQuery, SearchResult, User},
+    Result, SearchContainer,
+};
+
+/// Represents the `/search` REST handler, which is implemented by the [`SearchContainer`].
+///
+/// [`SearchContainer`]: ../struct.SearchContainer.html
+#[async_trait]
+pub trait SearchApi: Send + Sync {
+    /// Searches all the repositories that match the given [`SearchQuery`].
+    async fn repositories(&self, query: &SearchQuery) -> Result<SearchResult<Repository>>;
+
+    /// Searches all the users that match the given [`SearchQuery`].
+    async fn users(&self, query: &SearchQuery) -> Result<SearchResult<User>>;
+
+    /// Searches all the organizations that match the given [`SearchQuery`].
+    async fn organizations(&self, query: &SearchQuery) -> Result<SearchResult<Organization>>;
+}
+
+#[async_trait]
+impl SearchApi for SearchContainer {
+    async fn repositories(&self, query: &SearchQuery) -> Result<SearchResult<Repository>> {
+        SearchContainer::repositories(self, query).await
+    }
+
+    async fn users(&self, query: &SearchQuery) -> Result<SearchResult<User>> {
+        SearchContainer::users(self, query).await
+    }
+
+    async fn organizations(&self, query: &SearchQuery) -> Result<SearchResult<Organization>> {
+        SearchContainer::organizations(self, query).await
+    }
+}
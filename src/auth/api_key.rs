@@ -27,6 +27,13 @@ use super::AuthStrategy;
 #[derive(Debug, Clone)]
 pub struct ApiKeyStrategy(String);
 
+impl ApiKeyStrategy {
+    /// Creates a new [`ApiKeyStrategy`] with the API key's token.
+    pub fn new<S: AsRef<str>>(token: S) -> ApiKeyStrategy {
+        ApiKeyStrategy(token.as_ref().to_owned())
+    }
+}
+
 impl AuthStrategy for ApiKeyStrategy {
     fn prefix(&self) -> String {
         "ApiKey".to_owned()
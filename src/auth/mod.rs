@@ -20,19 +20,21 @@
 // SOFTWARE.
 
 mod api_key;
+#[cfg(feature = "basic-auth")]
 mod basic;
 mod session_token;
 
 use std::fmt::{Debug, Formatter, Result};
 
 pub use api_key::*;
+#[cfg(feature = "basic-auth")]
 pub use basic::*;
 pub use session_token::*;
 
 /// The trait for implementing an authentication strategy. It is not recommended to build
 /// your own strategy unless you have forked [charted-server](https://github.com/charted-dev/charted)
 /// and you wish to use your own authentication strategy, then that's completely ok.
-pub trait AuthStrategy {
+pub trait AuthStrategy: Send + Sync {
     /// Represents the prefix to use when building the `Authorization` header.
     fn prefix(&self) -> String;
 
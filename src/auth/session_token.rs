@@ -36,8 +36,9 @@ use super::AuthStrategy;
 /// /// token was passed, then it will actually refresh it but only on the first time, since the
 /// /// refresh token used is exhausted.
 /// let client = APIClient::default_with_auth(SessionTokenStrategy::new("access token"));
-/// client.users("@me").await?;
-/// // => Ok(ApiResponse { success: true, data: Some(charted::models::User { ... }), errors: None })
+/// client.health().await?;
+/// // => Ok(())
+/// # Ok(())
 /// # }
 /// ```
 ///
@@ -0,0 +1,145 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+
+#[cfg(feature = "indexes")]
+use super::IndexesContainer;
+#[cfg(feature = "repositories")]
+use super::RepositoriesContainer;
+#[cfg(feature = "search")]
+use super::SearchContainer;
+use crate::{
+    auth::AuthStrategy,
+    models::{FeaturesResponse, InfoResponse, MainResponse},
+    Result,
+};
+
+/// Represents the blocking version of [`crate::APIClient`]. Use [`APIClient::from`] to create one from
+/// an async client that was built with [`crate::APIClientBuilder`].
+///
+/// ## Panics
+/// Every request is run with [`Runtime::block_on`] on the client's embedded runtime, so requests
+/// panic if they're made from within an async runtime. Dropping the last clone of a client (or a
+/// container created from it) in an async context panics too, since the embedded runtime can't be
+/// shut down there; use [`tokio::task::spawn_blocking`] if a blocking client has to be used
+/// from async code.
+#[derive(Debug, Clone)]
+pub struct APIClient {
+    inner: crate::APIClient,
+    runtime: Arc<Runtime>,
+}
+
+impl Default for APIClient {
+    /// Creates a blocking client from [`crate::APIClient::default`].
+    ///
+    /// ## Panics
+    /// See [`APIClient::from`].
+    fn default() -> Self {
+        APIClient::from(crate::APIClient::default())
+    }
+}
+
+impl From<crate::APIClient> for APIClient {
+    /// Wraps `inner` in a blocking client with its own single-threaded tokio runtime.
+    ///
+    /// ## Panics
+    /// This panics if the runtime couldn't be created, i.e. if the operating system refused to create
+    /// the I/O or timer drivers.
+    fn from(inner: crate::APIClient) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("unable to create the tokio runtime for the blocking client");
+
+        APIClient {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+impl APIClient {
+    /// Blocking version of [`crate::APIClient::default_with_url`].
+    ///
+    /// ## Panics
+    /// See [`APIClient::from`].
+    pub fn default_with_url<S: Into<String>>(base_url: S) -> APIClient {
+        APIClient::from(crate::APIClient::default_with_url(base_url))
+    }
+
+    /// Blocking version of [`crate::APIClient::default_with_auth`].
+    ///
+    /// ## Panics
+    /// See [`APIClient::from`].
+    pub fn default_with_auth<S: AuthStrategy + 'static>(auth: S) -> APIClient {
+        APIClient::from(crate::APIClient::default_with_auth(auth))
+    }
+
+    /// Blocking version of [`crate::APIClient::uncached`].
+    #[cfg(feature = "cache")]
+    pub fn uncached(&self) -> APIClient {
+        APIClient {
+            inner: self.inner.uncached(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Blocking version of [`crate::APIClient::health`].
+    pub fn health(&self) -> Result<()> {
+        self.runtime.block_on(self.inner.health())
+    }
+
+    /// Blocking version of [`crate::APIClient::main`].
+    pub fn main(&self) -> Result<MainResponse> {
+        self.runtime.block_on(self.inner.main())
+    }
+
+    /// Blocking version of [`crate::APIClient::info`].
+    pub fn info(&self) -> Result<InfoResponse> {
+        self.runtime.block_on(self.inner.info())
+    }
+
+    /// Blocking version of [`crate::APIClient::features`].
+    pub fn features(&self) -> Result<FeaturesResponse> {
+        self.runtime.block_on(self.inner.features())
+    }
+
+    /// Creates a container to request to the Indexes API.
+    #[cfg(feature = "indexes")]
+    pub fn indexes(self) -> IndexesContainer {
+        IndexesContainer::new(self.inner.indexes(), self.runtime)
+    }
+
+    /// Creates a container to request to the Repositories API.
+    #[cfg(feature = "repositories")]
+    pub fn repositories(self) -> RepositoriesContainer {
+        RepositoriesContainer::new(self.inner.repositories(), self.runtime)
+    }
+
+    /// Creates a container to request to the Search API.
+    #[cfg(feature = "search")]
+    pub fn search(self) -> SearchContainer {
+        SearchContainer::new(self.inner.search(), self.runtime)
+    }
+}
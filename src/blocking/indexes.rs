@@ -0,0 +1,83 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio::runtime::Runtime;
+
+use crate::{
+    models::{ChartIndexSpec, ChartIndexYaml},
+    Result,
+};
+
+/// Represents the blocking version of [`crate::IndexesContainer`].
+#[derive(Debug, Clone)]
+pub struct IndexesContainer {
+    inner: crate::IndexesContainer,
+    runtime: Arc<Runtime>,
+}
+
+impl IndexesContainer {
+    pub(crate) fn new(inner: crate::IndexesContainer, runtime: Arc<Runtime>) -> IndexesContainer {
+        IndexesContainer { inner, runtime }
+    }
+
+    /// Blocking version of [`crate::IndexesContainer::allow_missing_digests`].
+    pub fn allow_missing_digests(self, allow: bool) -> IndexesContainer {
+        IndexesContainer {
+            inner: self.inner.allow_missing_digests(allow),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Same as [`crate::IndexesContainer::repository_url`].
+    pub fn repository_url<S: AsRef<str>>(&self, name: S) -> String {
+        self.inner.repository_url(name)
+    }
+
+    /// Blocking version of [`crate::IndexesContainer::get_by_id`].
+    pub fn get_by_id(&self, id: u64) -> Result<Option<ChartIndexYaml>> {
+        self.runtime.block_on(self.inner.get_by_id(id))
+    }
+
+    /// Blocking version of [`crate::IndexesContainer::get`].
+    pub fn get<S: Into<String>>(&self, name: S) -> Result<Option<ChartIndexYaml>> {
+        self.runtime.block_on(self.inner.get(name))
+    }
+
+    /// Blocking version of [`crate::IndexesContainer::download`].
+    pub fn download<N: AsRef<str>>(&self, name: N, spec: &ChartIndexSpec) -> Result<Vec<u8>> {
+        self.runtime.block_on(self.inner.download(name, spec))
+    }
+
+    /// Blocking version of [`crate::IndexesContainer::download_to`].
+    pub fn download_to<N: AsRef<str>, P: AsRef<Path>>(
+        &self,
+        name: N,
+        spec: &ChartIndexSpec,
+        dir: P,
+    ) -> Result<PathBuf> {
+        self.runtime.block_on(self.inner.download_to(name, spec, dir))
+    }
+}
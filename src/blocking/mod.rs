@@ -0,0 +1,54 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! A synchronous facade over the [`APIClient`][crate::APIClient] and its containers, for applications that
+//! don't use an async runtime. This is only available with the `blocking` feature.
+//!
+//! Every client runs requests on its own embedded single-threaded tokio runtime and shares the models,
+//! errors and caches with the async API. Like reqwest's blocking client, these methods **must not** be
+//! called from within an async runtime, since that would block it.
+//!
+//! ## Example
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = charted::blocking::APIClient::default_with_url("http://localhost:3651");
+//! client.health()?;
+//!
+//! let index = client.indexes().get("noel")?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+#[cfg(feature = "indexes")]
+mod indexes;
+#[cfg(feature = "repositories")]
+mod repositories;
+#[cfg(feature = "search")]
+mod search;
+
+pub use client::*;
+#[cfg(feature = "indexes")]
+pub use indexes::*;
+#[cfg(feature = "repositories")]
+pub use repositories::*;
+#[cfg(feature = "search")]
+pub use search::*;
@@ -0,0 +1,49 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::{models::Repository, Result};
+
+/// Represents the blocking version of [`crate::RepositoriesContainer`].
+#[derive(Debug, Clone)]
+pub struct RepositoriesContainer {
+    inner: crate::RepositoriesContainer,
+    runtime: Arc<Runtime>,
+}
+
+impl RepositoriesContainer {
+    pub(crate) fn new(inner: crate::RepositoriesContainer, runtime: Arc<Runtime>) -> RepositoriesContainer {
+        RepositoriesContainer { inner, runtime }
+    }
+
+    /// Blocking version of [`crate::RepositoriesContainer::get`].
+    pub fn get(&self, id: u64) -> Result<Option<Repository>> {
+        self.runtime.block_on(self.inner.get(id))
+    }
+
+    /// Blocking version of [`crate::RepositoriesContainer::list_for_user`].
+    pub fn list_for_user<S: AsRef<str>>(&self, user: S) -> Result<Vec<Repository>> {
+        self.runtime.block_on(self.inner.list_for_user(user))
+    }
+}
@@ -20,9 +20,15 @@
 // SOFTWARE.
 
 use std::collections::HashMap;
+#[cfg(feature = "cache")]
+use std::sync::Arc;
 
 use reqwest::Client;
 
+#[cfg(feature = "cache")]
+use crate::cache::CacheStore;
+#[cfg(feature = "testing")]
+use crate::testing::Cassette;
 use crate::{auth::AuthStrategy, APIClient};
 
 /// Represents the builder for creating [api clients].
@@ -34,10 +40,11 @@ use crate::{auth::AuthStrategy, APIClient};
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = APIClientBuilder::default()
 ///   .base_url("http://localhost:3651")
-///   .build()?;
+///   .build();
 ///
 /// client.health().await?;
 /// // => Ok(())
+/// # Ok(())
 /// # }
 /// ```
 ///
@@ -48,6 +55,10 @@ pub struct APIClientBuilder {
     pub(crate) http_client: Client,
     pub(crate) base_url: Option<String>,
     pub(crate) headers: HashMap<String, String>,
+    #[cfg(feature = "cache")]
+    pub(crate) cache: Option<Arc<dyn CacheStore>>,
+    #[cfg(feature = "testing")]
+    pub(crate) cassette: Option<Cassette>,
 }
 
 impl Default for APIClientBuilder {
@@ -57,6 +68,10 @@ impl Default for APIClientBuilder {
             http_client: Client::new(),
             base_url: None,
             headers: HashMap::new(),
+            #[cfg(feature = "cache")]
+            cache: None,
+            #[cfg(feature = "testing")]
+            cassette: None,
         }
     }
 }
@@ -80,12 +95,36 @@ impl APIClientBuilder {
         self
     }
 
+    /// Sets the [`CacheStore`] that responses of `GET` requests (including chart indexes) are cached in. Responses
+    /// are only cached if the server allows it with a `Cache-Control: max-age` directive, or if they can be revalidated
+    /// with their `ETag` or `Last-Modified` header. Cached responses are returned without making a request until they
+    /// become stale, and are then revalidated with a conditional request. Requests with any other method invalidate the
+    /// cached response of the same URL.
+    #[cfg(feature = "cache")]
+    pub fn cache<S: CacheStore + 'static>(&mut self, store: S) -> &mut Self {
+        self.cache = Some(Arc::new(store));
+        self
+    }
+
+    /// Sets the [`Cassette`] that requests are recorded into or replayed from, instead of only
+    /// being sent to the API server.
+    #[cfg(feature = "testing")]
+    pub fn cassette(&mut self, cassette: Cassette) -> &mut Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
     pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut Self {
         self.base_url = Some(base_url.into());
         self
     }
 
-    pub fn build(self) -> APIClient {
-        APIClient::new_with_builder(self)
+    /// Builds the [`APIClient`] from this builder's settings.
+    ///
+    /// This takes `&mut self` rather than `self` so that it can be called at the end of a chain of
+    /// setters (which all return `&mut Self`), like `APIClient::builder().base_url(..).build()`. The
+    /// builder is reset to its defaults afterwards.
+    pub fn build(&mut self) -> APIClient {
+        APIClient::new_with_builder(std::mem::take(self))
     }
 }
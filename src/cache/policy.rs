@@ -0,0 +1,133 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, CACHE_CONTROL, ETAG, LAST_MODIFIED};
+
+/// Returns how long a response can be used without revalidating it, or `None` if it can't be
+/// cached at all. Only what the server allows is cached, nothing is cached heuristically:
+///
+/// - `no-store` responses aren't cached;
+/// - `max-age` responses are fresh for that long;
+/// - `no-cache` responses, and responses that only have an `ETag` or `Last-Modified` header, are
+///   cached but revalidated on every request;
+/// - responses without any of these headers aren't cached.
+pub(crate) fn freshness(headers: &HeaderMap) -> Option<Duration> {
+    let directives = headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| directive.trim().to_ascii_lowercase())
+        .collect::<Vec<_>>();
+
+    if directives.iter().any(|directive| directive == "no-store") {
+        return None;
+    }
+
+    // a response that has to be revalidated can only be cached if it can be revalidated
+    let revalidate = (headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED)).then_some(Duration::ZERO);
+    if directives.iter().any(|directive| directive == "no-cache") {
+        return revalidate;
+    }
+
+    directives
+        .iter()
+        .find_map(|directive| {
+            directive
+                .strip_prefix("max-age=")
+                .and_then(|secs| secs.trim_matches('"').parse::<u64>().ok())
+        })
+        .map(Duration::from_secs)
+        .or(revalidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn freshness_of(headers: &[(&'static str, &'static str)]) -> Option<Duration> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, value.parse().unwrap());
+        }
+
+        freshness(&map)
+    }
+
+    #[test]
+    fn nothing_is_cached_heuristically() {
+        assert_eq!(freshness_of(&[]), None);
+        assert_eq!(freshness_of(&[("content-type", "application/json")]), None);
+        assert_eq!(freshness_of(&[("cache-control", "public")]), None);
+        assert_eq!(freshness_of(&[("cache-control", "no-cache")]), None);
+    }
+
+    #[test]
+    fn max_age() {
+        assert_eq!(
+            freshness_of(&[("cache-control", "public, max-age=300")]),
+            Some(Duration::from_secs(300))
+        );
+
+        assert_eq!(
+            freshness_of(&[("cache-control", r#"Max-Age="60""#)]),
+            Some(Duration::from_secs(60))
+        );
+
+        assert_eq!(
+            freshness_of(&[("cache-control", "public"), ("cache-control", "max-age=5")]),
+            Some(Duration::from_secs(5))
+        );
+
+        // an invalid max-age is ignored
+        assert_eq!(freshness_of(&[("cache-control", "max-age=soon")]), None);
+    }
+
+    #[test]
+    fn validators_are_revalidated() {
+        assert_eq!(freshness_of(&[("etag", "\"abc\"")]), Some(Duration::ZERO));
+        assert_eq!(
+            freshness_of(&[("last-modified", "Tue, 14 Mar 2023 18:32:18 GMT")]),
+            Some(Duration::ZERO)
+        );
+
+        assert_eq!(
+            freshness_of(&[("cache-control", "no-cache, max-age=300"), ("etag", "\"abc\"")]),
+            Some(Duration::ZERO)
+        );
+
+        assert_eq!(
+            freshness_of(&[("cache-control", "max-age=300"), ("etag", "\"abc\"")]),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn no_store() {
+        assert_eq!(
+            freshness_of(&[("cache-control", "no-store"), ("etag", "\"abc\"")]),
+            None
+        );
+        assert_eq!(freshness_of(&[("cache-control", "max-age=300, no-store")]), None);
+    }
+}
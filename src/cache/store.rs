@@ -0,0 +1,234 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::{Error, Result};
+
+/// Represents a response that was cached by the [`APIClient`][crate::APIClient].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CachedResponse {
+    /// The URL that the response is from.
+    pub url: String,
+
+    /// The response's body.
+    pub body: String,
+
+    /// The `Content-Type` header of the response.
+    #[serde(rename = "contentType", default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    /// The `ETag` header of the response, which is sent back as `If-None-Match` when revalidating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+
+    /// The `Last-Modified` header of the response, which is sent back as `If-Modified-Since` when revalidating.
+    #[serde(rename = "lastModified", skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+
+    /// The date of when the response was stored (or last revalidated).
+    #[serde(rename = "storedAt")]
+    pub stored_at: DateTime<Utc>,
+
+    /// The date of when the response becomes stale and needs to be revalidated.
+    #[serde(rename = "expiresAt")]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CachedResponse {
+    /// Checks if the response can still be used without revalidating it with the server.
+    pub fn is_fresh(&self) -> bool {
+        Utc::now() < self.expires_at
+    }
+}
+
+/// The trait for implementing where the [`APIClient`][crate::APIClient] keeps cached responses. Keys
+/// are opaque strings that are derived from the request's URL and credentials.
+///
+/// The methods are called while a request is being sent, so implementations that do I/O shouldn't
+/// block the runtime (i.e, [`FileStore`] runs its file system calls on tokio's blocking thread pool).
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Returns the cached response for the given key, if there is one.
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Caches a response with the given key, replacing any previous one.
+    async fn put(&self, key: &str, response: &CachedResponse) -> Result<()>;
+
+    /// Removes the cached response for the given key.
+    async fn remove(&self, key: &str) -> Result<()>;
+
+    /// Removes every cached response.
+    async fn clear(&self) -> Result<()>;
+}
+
+impl Debug for dyn CacheStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "dyn CacheStore")?;
+        Ok(())
+    }
+}
+
+/// Represents a [`CacheStore`] that keeps cached responses in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty [`MemoryStore`].
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryStore {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, response: &CachedResponse) -> Result<()> {
+        self.entries.lock().unwrap().insert(key.to_owned(), response.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.entries.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+/// Represents a [`CacheStore`] that keeps cached responses as JSON files in a directory, so
+/// they can be shared between runs of a CLI. Files that can't be read are treated as a cache miss.
+/// The files are read and written on tokio's blocking thread pool.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Creates a [`FileStore`] in the given directory, which is created once the first response is cached.
+    pub fn new<P: AsRef<Path>>(dir: P) -> FileStore {
+        FileStore {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", hex::encode(Sha256::digest(key.as_bytes()))))
+    }
+
+    // Runs a file system operation on tokio's blocking thread pool, so it doesn't stall the runtime
+    // that is sending the request
+    async fn blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(FileStore) -> Result<T> + Send + 'static,
+    {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || f(store))
+            .await
+            .map_err(|e| Error::String(format!("blocking task failed: {e}")))?
+    }
+}
+
+#[async_trait]
+impl CacheStore for FileStore {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let key = key.to_owned();
+        self.blocking(move |store| {
+            Ok(fs::read_to_string(store.path(&key))
+                .ok()
+                .and_then(|contents| serde_json::from_str::<BTreeMap<String, CachedResponse>>(&contents).ok())
+                .and_then(|mut entry| entry.remove(&key)))
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    async fn put(&self, key: &str, response: &CachedResponse) -> Result<()> {
+        // the key is kept alongside the response so that a hash collision is a cache miss
+        let contents =
+            serde_json::to_string(&BTreeMap::from([(key, response)])).map_err(|e| Error::JsonSerialization {
+                error: e,
+                payload: String::new(),
+            })?;
+
+        let key = key.to_owned();
+        self.blocking(move |store| {
+            fs::create_dir_all(&store.dir)?;
+            let mut file = tempfile::NamedTempFile::new_in(&store.dir)?;
+            file.write_all(contents.as_bytes())?;
+            file.persist(store.path(&key)).map_err(|e| Error::Io(e.error))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        let key = key.to_owned();
+        self.blocking(move |store| match fs::remove_file(store.path(&key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        })
+        .await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.blocking(|store| {
+            let entries = match fs::read_dir(&store.dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+                Err(e) => return Err(e.into()),
+            };
+
+            for entry in entries {
+                let path = entry?.path();
+                if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+                    fs::remove_file(path)?;
+                }
+            }
+
+            Ok(())
+        })
+        .await
+    }
+}
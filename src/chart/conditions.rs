@@ -0,0 +1,314 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use log::*;
+use serde_yaml::Value;
+
+use crate::models::{ChartDependency, ChartYaml};
+
+/// Represents what decided whether if a dependency is enabled or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnabledBy {
+    /// The dependency's condition resolved to a boolean at the given path.
+    Condition(String),
+
+    /// The dependency's tags were found in the values' `tags` mapping.
+    Tags,
+
+    /// Nothing was resolvable, so the dependency is enabled by default.
+    Default,
+}
+
+/// Represents whether if a chart dependency is enabled for a given values document.
+#[derive(Debug, Clone)]
+pub struct DependencyStatus<'a> {
+    /// The dependency that was evaluated.
+    pub dependency: &'a ChartDependency,
+
+    /// Whether if the dependency is enabled.
+    pub enabled: bool,
+
+    /// What decided whether if the dependency is enabled.
+    pub enabled_by: EnabledBy,
+}
+
+/// Evaluates each dependency's `condition` and `tags` against a values document the same way Helm does:
+///
+/// - every dependency starts out enabled;
+/// - if any of its tags is `true` in the values' `tags` mapping, it's enabled, otherwise if any of
+///   them is `false`, it's disabled;
+/// - the first comma-separated condition path that resolves to a boolean overrides the tags.
+///
+/// Paths or tags that resolve to something other than a boolean are skipped with a warning.
+///
+/// `values` has to be the chart's coalesced values: Helm evaluates conditions after merging the
+/// user-supplied values onto the chart's `values.yaml`, so a condition that is only set in the
+/// chart's defaults won't be seen if the defaults weren't merged in first (i.e, with
+/// [`merge_values`][crate::chart::merge_values]).
+pub fn evaluate_dependencies<'a>(dependencies: &'a [ChartDependency], values: &Value) -> Vec<DependencyStatus<'a>> {
+    let tags = values.get("tags").and_then(Value::as_mapping);
+    dependencies
+        .iter()
+        .map(|dependency| {
+            let mut status = DependencyStatus {
+                dependency,
+                enabled: true,
+                enabled_by: EnabledBy::Default,
+            };
+
+            if let Some(tags) = tags {
+                let (mut has_true, mut has_false) = (false, false);
+                for tag in dependency.tags.iter() {
+                    match tags.get(tag.as_str()) {
+                        Some(Value::Bool(true)) => has_true = true,
+                        Some(Value::Bool(false)) => has_false = true,
+                        Some(_) => warn!("tag '{tag}' for dependency {} is not a boolean", dependency.name),
+                        None => {}
+                    }
+                }
+
+                if has_true || has_false {
+                    status.enabled = has_true;
+                    status.enabled_by = EnabledBy::Tags;
+                }
+            }
+
+            let condition = dependency.condition.as_deref().unwrap_or_default();
+            for path in condition.split(',').map(str::trim).filter(|path| !path.is_empty()) {
+                match path_value(values, path) {
+                    Some(Value::Bool(enabled)) => {
+                        status.enabled = *enabled;
+                        status.enabled_by = EnabledBy::Condition(path.to_owned());
+                        break;
+                    }
+
+                    Some(_) => warn!(
+                        "condition path '{path}' for dependency {} returned a non-boolean value",
+                        dependency.name
+                    ),
+
+                    None => {}
+                }
+            }
+
+            status
+        })
+        .collect()
+}
+
+/// Looks up a dot-separated path (i.e, `postgresql.enabled`) in a values document.
+fn path_value<'v>(values: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.')
+        .try_fold(values, |value, key| value.as_mapping().and_then(|map| map.get(key)))
+}
+
+impl ChartYaml {
+    /// Returns the dependencies that are enabled with the given values, see [`evaluate_dependencies`]
+    /// for how they're evaluated. The chart's default values have to be coalesced into `values` first.
+    ///
+    /// ```
+    /// # use charted::models::ChartYaml;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let chart = ChartYaml::from_yaml(r#"
+    /// apiVersion: v2
+    /// name: hello
+    /// version: 0.1.0
+    /// dependencies:
+    ///   - name: postgresql
+    ///     version: 12.x
+    ///     condition: postgresql.enabled,global.postgresql.enabled
+    ///   - name: redis
+    ///     version: 17.x
+    ///     tags: [cache]
+    /// "#)?;
+    ///
+    /// let values: serde_yaml::Value = serde_yaml::from_str(r#"
+    /// postgresql:
+    ///   enabled: false
+    /// tags:
+    ///   cache: true
+    /// "#)?;
+    ///
+    /// let active = chart.active_dependencies(&values);
+    /// assert_eq!(active.len(), 1);
+    /// assert_eq!(active[0].name, "redis");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn active_dependencies(&self, values: &Value) -> Vec<&ChartDependency> {
+        evaluate_dependencies(&self.dependencies, values)
+            .into_iter()
+            .filter(|status| status.enabled)
+            .map(|status| status.dependency)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, condition: Option<&str>, tags: &[&str]) -> ChartDependency {
+        ChartDependency {
+            name: name.into(),
+            condition: condition.map(String::from),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn evaluate(dependency: ChartDependency, values: &str) -> (bool, EnabledBy) {
+        let values = serde_yaml::from_str::<Value>(values).unwrap();
+        let dependencies = [dependency];
+        let status = evaluate_dependencies(&dependencies, &values).remove(0);
+
+        (status.enabled, status.enabled_by)
+    }
+
+    fn condition(path: &str) -> EnabledBy {
+        EnabledBy::Condition(path.into())
+    }
+
+    #[test]
+    fn enabled_by_default() {
+        let dependency = dependency("postgresql", Some("postgresql.enabled"), &["database"]);
+        assert_eq!(evaluate(dependency.clone(), "{}"), (true, EnabledBy::Default));
+        assert_eq!(evaluate(dependency.clone(), "null"), (true, EnabledBy::Default));
+        assert_eq!(
+            evaluate(dependency, "tags: { cache: false }"),
+            (true, EnabledBy::Default)
+        );
+    }
+
+    #[test]
+    fn nested_condition_paths() {
+        let dependency = dependency("postgresql", Some("global.database.postgresql.enabled"), &[]);
+        assert_eq!(
+            evaluate(
+                dependency.clone(),
+                "global: { database: { postgresql: { enabled: false } } }"
+            ),
+            (false, condition("global.database.postgresql.enabled"))
+        );
+
+        // intermediate keys that aren't mappings don't resolve
+        assert_eq!(
+            evaluate(dependency.clone(), "global: { database: [postgresql] }"),
+            (true, EnabledBy::Default)
+        );
+
+        // keys are only split on dots, not looked up as a whole
+        assert_eq!(
+            evaluate(dependency, "global.database.postgresql.enabled: false"),
+            (true, EnabledBy::Default)
+        );
+    }
+
+    #[test]
+    fn first_boolean_condition_wins() {
+        let dependency = dependency("postgresql", Some("postgresql.enabled, global.postgresql.enabled"), &[]);
+        let values = r#"
+postgresql: { enabled: "no" }
+global: { postgresql: { enabled: false } }
+"#;
+
+        // the first path isn't a boolean, so it's skipped
+        assert_eq!(
+            evaluate(dependency.clone(), values),
+            (false, condition("global.postgresql.enabled"))
+        );
+
+        let values = r#"
+postgresql: { enabled: true }
+global: { postgresql: { enabled: false } }
+"#;
+
+        assert_eq!(evaluate(dependency, values), (true, condition("postgresql.enabled")));
+    }
+
+    #[test]
+    fn any_true_tag_enables() {
+        let dependency = dependency("redis", None, &["cache", "session"]);
+        assert_eq!(
+            evaluate(dependency.clone(), "tags: { cache: false, session: true }"),
+            (true, EnabledBy::Tags)
+        );
+
+        assert_eq!(
+            evaluate(dependency.clone(), "tags: { cache: false }"),
+            (false, EnabledBy::Tags)
+        );
+
+        // non-boolean tags are skipped
+        assert_eq!(
+            evaluate(dependency, "tags: { cache: 1, session: false }"),
+            (false, EnabledBy::Tags)
+        );
+    }
+
+    #[test]
+    fn conditions_override_tags() {
+        let dependency = dependency("redis", Some("redis.enabled"), &["cache"]);
+        assert_eq!(
+            evaluate(
+                dependency.clone(),
+                "{ tags: { cache: false }, redis: { enabled: true } }"
+            ),
+            (true, condition("redis.enabled"))
+        );
+
+        assert_eq!(
+            evaluate(
+                dependency.clone(),
+                "{ tags: { cache: true }, redis: { enabled: false } }"
+            ),
+            (false, condition("redis.enabled"))
+        );
+
+        // tags apply when the condition doesn't resolve
+        assert_eq!(
+            evaluate(dependency, "{ tags: { cache: false }, redis: { enabled: null } }"),
+            (false, EnabledBy::Tags)
+        );
+    }
+
+    #[test]
+    fn active_dependencies() {
+        let chart = ChartYaml {
+            dependencies: vec![
+                dependency("postgresql", Some("postgresql.enabled"), &[]),
+                dependency("redis", None, &["cache"]),
+                dependency("common", None, &[]),
+            ],
+            ..Default::default()
+        };
+
+        let values = serde_yaml::from_str(r#"{ postgresql: { enabled: false }, tags: { cache: false } }"#).unwrap();
+        let names = chart
+            .active_dependencies(&values)
+            .into_iter()
+            .map(|d| d.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["common"]);
+    }
+}
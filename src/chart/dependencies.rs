@@ -0,0 +1,462 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+};
+
+use chrono::Utc;
+
+use super::LockDrift;
+use crate::{
+    index::{parse_version, VersionConstraint},
+    models::{ChartDependency, ChartIndexYaml, ChartLock, ChartYaml},
+    IndexesContainer, Result,
+};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Represents an error that occurred when resolving a chart's dependencies.
+#[derive(Debug, thiserror::Error)]
+pub enum DependencyError {
+    #[error("dependency cycle detected: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+
+    #[error("more than one dependency is named or aliased '{0}'")]
+    DuplicateName(String),
+
+    #[error("repository '{0}' is not a charted-server repository")]
+    UnsupportedRepository(String),
+
+    #[error("dependency '{0}' is a local chart, the chart's directory has to be set to resolve its version")]
+    MissingChartDir(String),
+
+    #[error("repository '{repository}' doesn't exist or doesn't contain '{name}'")]
+    NotFound { name: String, repository: String },
+
+    #[error("no version of '{name}' in '{repository}' satisfies '{constraint}'")]
+    Unsatisfiable {
+        name: String,
+        repository: String,
+        constraint: String,
+    },
+}
+
+/// Represents a dependency that was resolved to an exact version, along with its own dependencies.
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+    /// The name of the chart.
+    pub name: String,
+
+    /// The alias of the chart, if it has one.
+    pub alias: Option<String>,
+
+    /// The repository the chart was resolved from.
+    pub repository: String,
+
+    /// The version constraint that was requested.
+    pub constraint: String,
+
+    /// The exact version that was resolved.
+    pub version: String,
+
+    /// The dependencies of this chart, which Helm vendors into its package.
+    pub dependencies: Vec<ResolvedDependency>,
+}
+
+/// Represents a chart that was resolved to more than one version across the dependency graph. This
+/// isn't an error since every subchart vendors its own dependencies, but it usually means that the same
+/// chart will be deployed twice with different versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyConflict {
+    /// The name of the chart.
+    pub name: String,
+
+    /// The repository the chart was resolved from.
+    pub repository: String,
+
+    /// Every version that was resolved, sorted.
+    pub versions: Vec<String>,
+}
+
+/// Represents the result of [`DependencyResolver::resolve`].
+#[derive(Debug, Clone)]
+pub struct DependencyResolution {
+    /// The lock file for the chart's direct dependencies, equivalent to Helm's `Chart.lock`.
+    pub lock: ChartLock,
+
+    /// The full dependency graph, starting with the chart's direct dependencies.
+    pub graph: Vec<ResolvedDependency>,
+
+    /// Charts that were resolved to more than one version in the graph.
+    pub conflicts: Vec<DependencyConflict>,
+}
+
+/// Resolves a chart's dependencies against the chart repositories of a **charted-server** instance, which
+/// are the dependencies with a `repository` of `<base url>/indexes/<name>` (see [`IndexesContainer::repository_url`]).
+/// Like Helm, a dependency's `repository` can also be:
+///
+/// - `@name` or `alias:name`, which refers to a repository that was registered with
+///   [`DependencyResolver::repository`] (like `helm repo add` does);
+/// - `file://<path>`, a chart on disk relative to the chart's directory (see [`DependencyResolver::chart_dir`]). Its
+///   `Chart.yaml` has to satisfy the version constraint, and it's locked to that version. Without the chart's directory,
+///   only exact versions can be locked;
+/// - empty, for a chart that is vendored in the chart's `charts/` directory, which is locked to the version as-is.
+///
+/// Only a **charted-server** instance's repositories are resolved from an index, so dependencies from other chart
+/// repositories (including `oci://` registries) fail with [`DependencyError::UnsupportedRepository`]. The dependencies
+/// of local and vendored charts aren't walked, since Helm packages them as-is.
+///
+/// ```no_run
+/// # use charted::{APIClient, chart::DependencyResolver, models::ChartYaml};
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let chart = ChartYaml::from_yaml(std::fs::read_to_string("./Chart.yaml")?)?;
+/// let client = APIClient::default();
+/// let mut resolver = DependencyResolver::new(client.clone().indexes());
+/// resolver
+///     .chart_dir(".")
+///     .repository("noelware", client.indexes().repository_url("noelware"));
+///
+/// let resolution = resolver.resolve(&chart).await?;
+/// for conflict in resolution.conflicts.iter() {
+///     println!("{} is resolved to {:?}", conflict.name, conflict.versions);
+/// }
+///
+/// let lock = resolution.lock;
+/// // => charted::models::ChartLock { ... }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`IndexesContainer::repository_url`]: ../struct.IndexesContainer.html#method.repository_url
+#[derive(Debug)]
+pub struct DependencyResolver {
+    indexes: IndexesContainer,
+    cache: HashMap<String, Option<ChartIndexYaml>>,
+    repositories: HashMap<String, String>,
+    chart_dir: Option<PathBuf>,
+}
+
+impl DependencyResolver {
+    /// Creates a new [`DependencyResolver`] that fetches indexes with the given [`IndexesContainer`].
+    pub fn new(indexes: IndexesContainer) -> DependencyResolver {
+        DependencyResolver {
+            indexes,
+            cache: HashMap::new(),
+            repositories: HashMap::new(),
+            chart_dir: None,
+        }
+    }
+
+    /// Registers a repository under a name, so dependencies can refer to it as `@name` or `alias:name`.
+    pub fn repository<N: Into<String>, U: Into<String>>(&mut self, name: N, url: U) -> &mut Self {
+        self.repositories.insert(name.into(), url.into());
+        self
+    }
+
+    /// Sets the directory of the chart whose dependencies are resolved, which `file://` repositories are
+    /// relative to and which has the `charts/` directory of vendored dependencies.
+    pub fn chart_dir<P: Into<PathBuf>>(&mut self, dir: P) -> &mut Self {
+        self.chart_dir = Some(dir.into());
+        self
+    }
+
+    /// Resolves every dependency of the given chart, walking the dependencies of each resolved chart
+    /// to detect cycles and conflicts.
+    pub async fn resolve(&mut self, chart: &ChartYaml) -> Result<DependencyResolution> {
+        let mut names = HashSet::new();
+        for dependency in chart.dependencies.iter() {
+            let name = dependency.alias.as_ref().unwrap_or(&dependency.name);
+            if !names.insert(name) {
+                return Err(DependencyError::DuplicateName(name.clone()).into());
+            }
+        }
+
+        // the chart itself isn't from a repository, so it matches a dependency on a chart with
+        // the same name from any repository
+        let mut stack = vec![StackEntry {
+            repository: None,
+            name: chart.name.clone(),
+            version: chart.version.clone(),
+        }];
+
+        let mut seen = BTreeMap::new();
+        let mut graph = vec![];
+        for dependency in chart.dependencies.iter() {
+            graph.push(self.resolve_dependency(dependency, &mut stack, &mut seen).await?);
+        }
+
+        // like Helm, the lock file has the URL of `@name` and `alias:name` repositories, and its digest
+        // is computed from the requested dependencies with their repositories resolved too
+        let requested = self.requested(chart)?;
+        let locked = graph
+            .iter()
+            .map(|resolved| ChartDependency {
+                name: resolved.name.clone(),
+                version: Some(resolved.version.clone()),
+                repository: Some(resolved.repository.clone()),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let conflicts = seen
+            .into_iter()
+            .filter(|(_, versions)| versions.len() > 1)
+            .map(|((repository, name), versions)| DependencyConflict {
+                name,
+                repository,
+                versions: versions.into_iter().collect(),
+            })
+            .collect();
+
+        Ok(DependencyResolution {
+            lock: ChartLock {
+                digest: ChartLock::compute_digest(&requested, &locked),
+                dependencies: locked,
+                generated: Utc::now(),
+            },
+            graph,
+            conflicts,
+        })
+    }
+
+    /// Compares a lock file with the chart's dependencies like [`ChartLock::drift`] does, after replacing
+    /// `@name` and `alias:name` repositories with the URL they were registered with, which is how Helm
+    /// checks a lock file that was generated for a chart with aliased repositories.
+    pub fn drift(&self, lock: &ChartLock, chart: &ChartYaml) -> Result<LockDrift> {
+        let chart = ChartYaml {
+            dependencies: self.requested(chart)?,
+            ..chart.clone()
+        };
+
+        Ok(lock.drift(&chart))
+    }
+
+    /// Returns the chart's dependencies with their `@name` and `alias:name` repositories resolved.
+    fn requested(&self, chart: &ChartYaml) -> Result<Vec<ChartDependency>> {
+        chart
+            .dependencies
+            .iter()
+            .map(|dependency| {
+                let Some(repository) = &dependency.repository else {
+                    return Ok(dependency.clone());
+                };
+
+                Ok(ChartDependency {
+                    repository: Some(self.repository_url(repository)?),
+                    ..dependency.clone()
+                })
+            })
+            .collect()
+    }
+
+    fn resolve_dependency<'a>(
+        &'a mut self,
+        dependency: &'a ChartDependency,
+        stack: &'a mut Vec<StackEntry>,
+        seen: &'a mut BTreeMap<(String, String), BTreeSet<String>>,
+    ) -> BoxFuture<'a, Result<ResolvedDependency>> {
+        Box::pin(async move {
+            let written = dependency.repository.clone().unwrap_or_default();
+            let constraint = dependency.version.clone().unwrap_or_else(|| "*".into());
+            let repository = self.repository_url(&written)?;
+
+            let (version, children) = if let Some(path) = repository.strip_prefix("file://") {
+                (self.local_version(dependency, path, &constraint).await?, vec![])
+            } else if repository.is_empty() {
+                self.vendored(dependency).await?;
+                (constraint.clone(), vec![])
+            } else {
+                let index = self.index(&repository).await?;
+                let spec =
+                    index
+                        .resolve(&dependency.name, &constraint)?
+                        .ok_or_else(|| DependencyError::Unsatisfiable {
+                            name: dependency.name.clone(),
+                            repository: repository.clone(),
+                            constraint: constraint.clone(),
+                        })?;
+
+                (spec.version.clone(), spec.dependencies.clone())
+            };
+
+            // a chart is only in a cycle if it depends on itself, no matter which version
+            let entry = StackEntry {
+                repository: Some(repository.clone()),
+                name: dependency.name.clone(),
+                version: version.clone(),
+            };
+
+            if stack.iter().any(|existing| existing.is_same_chart(&entry)) {
+                let mut cycle = stack.iter().map(StackEntry::to_string).collect::<Vec<_>>();
+                cycle.push(entry.to_string());
+
+                return Err(DependencyError::Cycle(cycle).into());
+            }
+
+            seen.entry((repository.clone(), dependency.name.clone()))
+                .or_default()
+                .insert(version.clone());
+
+            stack.push(entry);
+            let mut dependencies = vec![];
+            for child in children.iter() {
+                dependencies.push(self.resolve_dependency(child, stack, seen).await?);
+            }
+
+            stack.pop();
+            Ok(ResolvedDependency {
+                name: dependency.name.clone(),
+                alias: dependency.alias.clone(),
+                repository,
+                constraint,
+                version,
+                dependencies,
+            })
+        })
+    }
+
+    /// Resolves `@name` and `alias:name` repositories to the URL they were registered with.
+    fn repository_url(&self, repository: &str) -> Result<String> {
+        let Some(name) = repository
+            .strip_prefix('@')
+            .or_else(|| repository.strip_prefix("alias:"))
+        else {
+            return Ok(repository.to_owned());
+        };
+
+        self.repositories
+            .get(name)
+            .cloned()
+            .ok_or_else(|| DependencyError::UnsupportedRepository(repository.to_owned()).into())
+    }
+
+    /// Returns the version of a `file://` dependency, which is read from its `Chart.yaml` if the chart's
+    /// directory is known, otherwise the constraint has to be an exact version.
+    async fn local_version(&self, dependency: &ChartDependency, path: &str, constraint: &str) -> Result<String> {
+        let unsatisfiable = || DependencyError::Unsatisfiable {
+            name: dependency.name.clone(),
+            repository: format!("file://{path}"),
+            constraint: constraint.to_owned(),
+        };
+
+        let Some(dir) = &self.chart_dir else {
+            return match parse_version(constraint) {
+                Ok(_) => Ok(constraint.to_owned()),
+                Err(_) => Err(DependencyError::MissingChartDir(dependency.name.clone()).into()),
+            };
+        };
+
+        let chart_yaml = match tokio::fs::read_to_string(dir.join(path).join("Chart.yaml")).await {
+            Ok(contents) => ChartYaml::from_yaml(contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(DependencyError::NotFound {
+                    name: dependency.name.clone(),
+                    repository: format!("file://{path}"),
+                }
+                .into())
+            }
+
+            Err(e) => return Err(e.into()),
+        };
+
+        let version = parse_version(&chart_yaml.version)?;
+        if !constraint.parse::<VersionConstraint>()?.matches(&version) {
+            return Err(unsatisfiable().into());
+        }
+
+        Ok(chart_yaml.version)
+    }
+
+    /// Checks that a dependency without a repository is vendored in the chart's `charts/` directory, if
+    /// the chart's directory is known.
+    async fn vendored(&self, dependency: &ChartDependency) -> Result<()> {
+        let Some(dir) = &self.chart_dir else {
+            return Ok(());
+        };
+
+        match tokio::fs::metadata(dir.join("charts").join(&dependency.name)).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(DependencyError::NotFound {
+                name: dependency.name.clone(),
+                repository: String::new(),
+            }
+            .into()),
+
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn index(&mut self, repository: &str) -> Result<&ChartIndexYaml> {
+        let prefix = self.indexes.repository_url("");
+        let name = repository
+            .trim_end_matches('/')
+            .trim_end_matches("/index.yaml")
+            .strip_prefix(&prefix)
+            .filter(|name| !name.is_empty() && !name.contains('/'))
+            .ok_or_else(|| DependencyError::UnsupportedRepository(repository.to_owned()))?
+            .to_owned();
+
+        if !self.cache.contains_key(&name) {
+            let index = self.indexes.get(&name).await?;
+            self.cache.insert(name.clone(), index);
+        }
+
+        self.cache[&name].as_ref().ok_or_else(|| {
+            DependencyError::NotFound {
+                name,
+                repository: repository.to_owned(),
+            }
+            .into()
+        })
+    }
+}
+
+/// A chart that is being resolved, which is used to detect cycles.
+struct StackEntry {
+    repository: Option<String>,
+    name: String,
+    version: String,
+}
+
+impl StackEntry {
+    fn is_same_chart(&self, other: &StackEntry) -> bool {
+        self.name == other.name
+            && match (&self.repository, &other.repository) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+impl std::fmt::Display for StackEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.repository {
+            Some(repository) => write!(f, "{repository}/{}@{}", self.name, self.version),
+            None => write!(f, "{}@{}", self.name, self.version),
+        }
+    }
+}
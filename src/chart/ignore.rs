@@ -0,0 +1,324 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::Path;
+
+use globset::{Glob, GlobBuilder, GlobMatcher};
+
+use crate::{Error, Result};
+
+/// Represents the rules in a chart's `.helmignore` file, which are a port of Helm's `ignore.Rules`:
+///
+/// - blank lines and lines starting with `#` are skipped,
+/// - a trailing `/` only matches directories,
+/// - a leading `/` or a `/` anywhere else in the pattern matches against the path relative to the
+///   chart's root, otherwise the pattern is matched against the file's name,
+/// - patterns use Go's `filepath.Match` syntax, and `**` is not supported.
+///
+/// Rules are evaluated in order and the first rule that matches a path ignores it. A rule with a
+/// leading `!` is the opposite: it ignores every path that **doesn't** match it, and paths that do
+/// match it are passed on to the next rule. This means that `!pattern` isn't an exception to the
+/// rules above it like in a `.gitignore` file. Hidden files in `templates/` are always ignored, like
+/// Helm does.
+///
+/// ```
+/// # use charted::chart::HelmIgnore;
+/// #
+/// let ignore = HelmIgnore::parse(".git/\n*.tgz\nci/*.yaml\n").unwrap();
+/// assert!(ignore.is_ignored(".git", true));
+/// assert!(ignore.is_ignored("charts/old.tgz", false));
+/// assert!(ignore.is_ignored("ci/values.yaml", false));
+/// assert!(!ignore.is_ignored("values.yaml", false));
+///
+/// // only `*.yaml` files are kept, and every other file is ignored
+/// let ignore = HelmIgnore::parse("!*.yaml\n").unwrap();
+/// assert!(!ignore.is_ignored("values.yaml", false));
+/// assert!(ignore.is_ignored("README.md", false));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HelmIgnore {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    matcher: GlobMatcher,
+    negate: bool,
+    dir_only: bool,
+    match_path: bool,
+}
+
+impl Default for HelmIgnore {
+    fn default() -> Self {
+        let mut ignore = HelmIgnore { rules: vec![] };
+        ignore.add_defaults();
+
+        ignore
+    }
+}
+
+impl HelmIgnore {
+    /// Parses the contents of a `.helmignore` file.
+    pub fn parse<S: AsRef<str>>(contents: S) -> Result<HelmIgnore> {
+        let mut ignore = HelmIgnore { rules: vec![] };
+        for line in contents.as_ref().lines() {
+            ignore.add_rule(line)?;
+        }
+
+        // Helm adds the default rules after the ones in the .helmignore file
+        ignore.add_defaults();
+        Ok(ignore)
+    }
+
+    /// Loads the `.helmignore` file in the given chart directory, or returns the default rules
+    /// if there is no `.helmignore` file.
+    pub fn load<P: AsRef<Path>>(chart_dir: P) -> Result<HelmIgnore> {
+        let path = chart_dir.as_ref().join(".helmignore");
+        if !path.exists() {
+            return Ok(HelmIgnore::default());
+        }
+
+        HelmIgnore::parse(std::fs::read_to_string(path)?)
+    }
+
+    fn add_defaults(&mut self) {
+        self.add_rule("templates/.?*").expect("default rule to be valid");
+    }
+
+    fn add_rule(&mut self, line: &str) -> Result<()> {
+        let mut pattern = line.trim();
+        if pattern.is_empty() || pattern.starts_with('#') {
+            return Ok(());
+        }
+
+        if pattern.contains("**") {
+            return Err(Error::String(format!(
+                ".helmignore pattern '{pattern}' uses '**', which is not supported"
+            )));
+        }
+
+        let negate = pattern.starts_with('!');
+        if negate {
+            pattern = &pattern[1..];
+        }
+
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
+        let match_path = pattern.contains('/');
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+        let glob: Glob = GlobBuilder::new(&translate(pattern))
+            .literal_separator(true)
+            .backslash_escape(true)
+            .build()
+            .map_err(|e| Error::String(format!("invalid .helmignore pattern '{pattern}': {e}")))?;
+
+        self.rules.push(Rule {
+            matcher: glob.compile_matcher(),
+            negate,
+            dir_only,
+            match_path,
+        });
+
+        Ok(())
+    }
+
+    /// Checks if the given path (relative to the chart's root and separated with `/`) should
+    /// be ignored.
+    pub fn is_ignored<P: AsRef<str>>(&self, path: P, is_dir: bool) -> bool {
+        let path = path.as_ref();
+        if path.is_empty() || path == "." || path == "./" {
+            return false;
+        }
+
+        let trimmed = path.trim_end_matches('/');
+        let name = trimmed.rsplit('/').next().unwrap_or(trimmed);
+        for rule in self.rules.iter() {
+            let matched = if rule.match_path {
+                rule.matcher.is_match(path)
+            } else {
+                rule.matcher.is_match(name)
+            };
+
+            if rule.negate {
+                if (rule.dir_only && !is_dir) || !matched {
+                    return true;
+                }
+
+                continue;
+            }
+
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+
+            if matched {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Translates a pattern from Go's `filepath.Match` syntax into `globset`'s, which uses `[!...]`
+/// for negated character classes and has `{a,b}` alternations that Go doesn't.
+fn translate(pattern: &str) -> String {
+    let mut translated = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                translated.push(c);
+                if let Some(next) = chars.next() {
+                    translated.push(next);
+                }
+            }
+
+            '[' if !in_class => {
+                in_class = true;
+                translated.push(c);
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                    translated.push('!');
+                }
+            }
+
+            ']' if in_class => {
+                in_class = false;
+                translated.push(c);
+            }
+
+            '{' | '}' if !in_class => {
+                translated.push('[');
+                translated.push(c);
+                translated.push(']');
+            }
+
+            _ => translated.push(c),
+        }
+    }
+
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ported from Helm's `TestIgnore` in `pkg/ignore/rules_test.go`: `(pattern, path, is_dir, ignored)`.
+    /// The directories in Helm's testdata are `cargo/`, `mast/` and `.`.
+    const HELM_CASES: &[(&str, &str, bool, bool)] = &[
+        // glob tests
+        ("helm.txt", "helm.txt", false, true),
+        ("helm.*", "helm.txt", false, true),
+        ("helm.*", "rudder.txt", false, false),
+        ("*.txt", "tiller.txt", false, true),
+        ("*.txt", "cargo/a.txt", false, true),
+        ("cargo/*.txt", "cargo/a.txt", false, true),
+        ("cargo/*.*", "cargo/a.txt", false, true),
+        ("cargo/*.txt", "mast/a.txt", false, false),
+        ("ru[c-e]?er.txt", "rudder.txt", false, true),
+        ("templates/.?*", "templates/.dotfile", false, true),
+        // "." should never get ignored, https://github.com/helm/helm/issues/1776
+        (".*", ".", true, false),
+        (".*", "./", true, false),
+        (".*", ".joonix", false, true),
+        (".*", "helm.txt", false, false),
+        (".*", "", true, false),
+        // directory tests
+        ("cargo/", "cargo", true, true),
+        ("cargo/", "cargo/", true, true),
+        ("cargo/", "mast/", true, false),
+        ("helm.txt/", "helm.txt", false, false),
+        // negation tests
+        ("!helm.txt", "helm.txt", false, false),
+        ("!helm.txt", "tiller.txt", false, true),
+        ("!*.txt", "cargo", true, true),
+        ("!cargo/", "mast/", true, true),
+        // absolute path tests
+        ("/a.txt", "a.txt", false, true),
+        ("/a.txt", "cargo/a.txt", false, false),
+        ("/cargo/a.txt", "cargo/a.txt", false, true),
+    ];
+
+    #[test]
+    fn helm_rules() {
+        for (pattern, path, is_dir, expected) in HELM_CASES {
+            let ignore = HelmIgnore::parse(pattern).unwrap();
+            assert_eq!(
+                ignore.is_ignored(path, *is_dir),
+                *expected,
+                "expected {path:?} to be {expected} for pattern {pattern:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let ignore = HelmIgnore::parse("#ignore\n\n\t#ignore\nfoo\nbar/*\nbaz/bar/*.txt\n\none/more\n").unwrap();
+
+        // the four rules and the default one
+        assert_eq!(ignore.rules.len(), 5);
+        assert!(ignore.is_ignored("a/foo", false));
+        assert!(ignore.is_ignored("bar/a", false));
+        assert!(!ignore.is_ignored("a/bar/b", false));
+        assert!(ignore.is_ignored("baz/bar/a.txt", false));
+        assert!(ignore.is_ignored("one/more", false));
+        assert!(ignore.is_ignored("templates/.hidden", false));
+    }
+
+    #[test]
+    fn parse_failures() {
+        for pattern in ["foo/**/bar", "[z-"] {
+            assert!(HelmIgnore::parse(pattern).is_err(), "{pattern:?} should fail");
+        }
+    }
+
+    #[test]
+    fn negated_rules_ignore_everything_else() {
+        let ignore = HelmIgnore::parse("!*.yaml\n").unwrap();
+        assert!(!ignore.is_ignored("values.yaml", false));
+        assert!(ignore.is_ignored("templates", true));
+        assert!(ignore.is_ignored("README.md", false));
+
+        // a matching path is passed on to the next rule
+        let ignore = HelmIgnore::parse("!*.yaml\nci.yaml\n").unwrap();
+        assert!(ignore.is_ignored("ci.yaml", false));
+        assert!(!ignore.is_ignored("values.yaml", false));
+
+        // negated directory rules ignore every file
+        let ignore = HelmIgnore::parse("!templates/\n").unwrap();
+        assert!(!ignore.is_ignored("templates", true));
+        assert!(ignore.is_ignored("values.yaml", false));
+    }
+
+    #[test]
+    fn go_pattern_syntax() {
+        let ignore = HelmIgnore::parse("[^a]bc\n{x,y}.txt\n").unwrap();
+        assert!(ignore.is_ignored("xbc", false));
+        assert!(!ignore.is_ignored("abc", false));
+        assert!(ignore.is_ignored("{x,y}.txt", false));
+        assert!(!ignore.is_ignored("x.txt", false));
+    }
+}
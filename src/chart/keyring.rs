@@ -0,0 +1,304 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use tempfile::TempDir;
+
+use super::{
+    chart_digest,
+    provenance::{clearsigned_message, SIGNATURE_HEADER},
+    Provenance, ProvenanceError,
+};
+use crate::Result;
+
+/// The longest line that `gpg` can clearsign.
+const MAX_LINE_LENGTH: usize = 19_995;
+
+/// Represents the key that signed a provenance file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signer {
+    /// The fingerprint of the key that made the signature.
+    pub fingerprint: String,
+
+    /// The user ID of the key, i.e. `Noel <cutie@floofy.dev>`.
+    pub user_id: String,
+}
+
+/// Represents a provenance file that was verified with [`Keyring::verify_chart`].
+#[derive(Debug, Clone)]
+pub struct Verification {
+    /// The key that signed the provenance file.
+    pub signer: Signer,
+
+    /// The contents of the provenance file.
+    pub provenance: Provenance,
+
+    /// The SHA-256 digest of the chart package.
+    pub digest: String,
+}
+
+/// Represents a PGP keyring (either binary or ASCII-armored) that is used to sign or verify provenance files.
+///
+/// This uses the `gpg` binary (which must be on the `PATH`) with a temporary home directory that the keyring
+/// is imported into, so the user's own GnuPG configuration is never read or modified. [`Keyring::new`] fails
+/// if `gpg` can't be run, rather than the first signature or verification.
+///
+/// ```no_run
+/// # use charted::chart::{Keyring, Provenance};
+/// #
+/// # fn main() -> charted::Result<()> {
+/// let provenance = Provenance::for_package_file("./dist/hello-0.1.0.tgz")?;
+/// // exported with `gpg --export-secret-keys Noel > ./secring.gpg`
+/// let signed = Keyring::new("./secring.gpg")?.sign(&provenance, "Noel", None)?;
+/// std::fs::write("./dist/hello-0.1.0.tgz.prov", signed)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keyring {
+    path: PathBuf,
+}
+
+impl Keyring {
+    /// Creates a new [`Keyring`] from the keyring file at the given path. The path is used as-is, so
+    /// a leading `~` is not expanded to the home directory.
+    ///
+    /// Returns a [`ProvenanceError::GpgUnavailable`] if the `gpg` binary can't be run.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Keyring> {
+        let output = Command::new("gpg")
+            .arg("--version")
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| ProvenanceError::GpgUnavailable(format!("unable to run gpg: {e}")))?;
+
+        if !output.status.success() {
+            return Err(
+                ProvenanceError::GpgUnavailable(String::from_utf8_lossy(&output.stderr).trim().to_owned()).into(),
+            );
+        }
+
+        Ok(Keyring {
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Clearsigns the provenance document with the key matching the given name (a user ID, email or
+    /// key ID) from this keyring, which must contain the secret key. The passphrase is sent to `gpg`
+    /// over its standard input, so it never shows up in the process' arguments.
+    pub fn sign(&self, provenance: &Provenance, key: &str, passphrase: Option<&str>) -> Result<String> {
+        let home = self.import()?;
+
+        // gpg silently truncates longer lines (and still exits successfully)
+        let message = provenance.message()?;
+        if message.lines().any(|line| line.len() > MAX_LINE_LENGTH) {
+            return Err(
+                ProvenanceError::Malformed(format!("lines can't be longer than {MAX_LINE_LENGTH} characters")).into(),
+            );
+        }
+
+        // the message is read from a file since stdin is used for the passphrase
+        let path = home.path().join("provenance");
+        fs::write(&path, message)?;
+
+        let mut args = vec![
+            "--batch",
+            "--yes",
+            "--pinentry-mode",
+            "loopback",
+            "--digest-algo",
+            "SHA512",
+            "--local-user",
+            key,
+            "--output",
+            "-",
+        ];
+
+        let mut stdin = vec![];
+        if let Some(passphrase) = passphrase {
+            args.extend(["--passphrase-fd", "0"]);
+            stdin.extend_from_slice(passphrase.as_bytes());
+            stdin.push(b'\n');
+        }
+
+        let path = path.to_string_lossy();
+        args.extend(["--clearsign", path.as_ref()]);
+
+        let output = gpg(home.path(), &args, &stdin)?;
+        if !output.status.success() {
+            return Err(ProvenanceError::Gpg(String::from_utf8_lossy(&output.stderr).trim().to_owned()).into());
+        }
+
+        String::from_utf8(output.stdout).map_err(|e| ProvenanceError::Gpg(e.to_string()).into())
+    }
+
+    /// Verifies the signature of a clearsigned provenance file, returning the key that signed it.
+    pub fn verify<S: AsRef<str>>(&self, contents: S) -> Result<Signer> {
+        let contents = contents.as_ref();
+        if !contents.contains(SIGNATURE_HEADER) {
+            return Err(ProvenanceError::MissingSignature.into());
+        }
+
+        let home = self.import()?;
+        let output = gpg(
+            home.path(),
+            &["--batch", "--status-fd", "1", "--verify"],
+            contents.as_bytes(),
+        )?;
+        let status = String::from_utf8_lossy(&output.stdout);
+
+        let mut fingerprint = None;
+        let mut user_id = None;
+        for line in status.lines() {
+            let mut parts = line.trim_start_matches("[GNUPG:] ").splitn(3, ' ');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("GOODSIG"), Some(_), uid) => user_id = Some(uid.unwrap_or_default().to_owned()),
+                (Some("VALIDSIG"), Some(fpr), _) => fingerprint = Some(fpr.to_owned()),
+                (Some("BADSIG"), _, _) => {
+                    return Err(ProvenanceError::InvalidSignature("signature doesn't match the document".into()).into())
+                }
+
+                (Some("NO_PUBKEY"), Some(key), _) => {
+                    return Err(ProvenanceError::InvalidSignature(format!("key {key} is not in the keyring")).into())
+                }
+
+                (Some("EXPKEYSIG"), Some(key), _) => {
+                    return Err(ProvenanceError::InvalidSignature(format!("key {key} has expired")).into())
+                }
+
+                (Some("REVKEYSIG"), Some(key), _) => {
+                    return Err(ProvenanceError::InvalidSignature(format!("key {key} was revoked")).into())
+                }
+
+                _ => {}
+            }
+        }
+
+        match (output.status.success(), fingerprint, user_id) {
+            (true, Some(fingerprint), Some(user_id)) => Ok(Signer { fingerprint, user_id }),
+            _ => {
+                Err(ProvenanceError::InvalidSignature(String::from_utf8_lossy(&output.stderr).trim().to_owned()).into())
+            }
+        }
+    }
+
+    /// Verifies a chart package against its provenance file: the provenance file's signature must be
+    /// made by a key in this keyring, and the package's digest must match the one in the provenance file.
+    ///
+    /// The provenance file must be exactly one clearsigned block, and only the message that it signs
+    /// is parsed, so the digests that are checked are always the ones that the signature covers.
+    ///
+    /// ```no_run
+    /// # use charted::chart::Keyring;
+    /// #
+    /// # fn main() -> charted::Result<()> {
+    /// let package = std::fs::read("./hello-0.1.0.tgz")?;
+    /// let provenance = std::fs::read_to_string("./hello-0.1.0.tgz.prov")?;
+    ///
+    /// let verification = Keyring::new("./pubring.gpg")?.verify_chart("hello-0.1.0.tgz", package, provenance)?;
+    /// println!("signed by {}", verification.signer.user_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_chart<N: AsRef<str>, B: AsRef<[u8]>, S: AsRef<str>>(
+        &self,
+        file_name: N,
+        bytes: B,
+        provenance: S,
+    ) -> Result<Verification> {
+        let message = clearsigned_message(provenance.as_ref())?;
+        let signer = self.verify(provenance.as_ref())?;
+        let parsed = Provenance::parse(message)?;
+        parsed.verify_package(file_name.as_ref(), bytes.as_ref())?;
+
+        Ok(Verification {
+            signer,
+            provenance: parsed,
+            digest: chart_digest(bytes),
+        })
+    }
+
+    /// Creates a temporary GnuPG home directory and imports this keyring into it.
+    fn import(&self) -> Result<GnupgHome> {
+        let home = GnupgHome(tempfile::Builder::new().prefix("charted-gpg").tempdir()?);
+        let keyring = fs::read(&self.path)?;
+        let output = gpg(home.path(), &["--batch", "--import"], &keyring)?;
+        if !output.status.success() {
+            return Err(ProvenanceError::Gpg(String::from_utf8_lossy(&output.stderr).trim().to_owned()).into());
+        }
+
+        Ok(home)
+    }
+}
+
+/// A temporary GnuPG home directory, which stops any agent that was started for it when it is dropped.
+struct GnupgHome(TempDir);
+
+impl GnupgHome {
+    fn path(&self) -> &Path {
+        self.0.path()
+    }
+}
+
+impl Drop for GnupgHome {
+    fn drop(&mut self) {
+        let _ = Command::new("gpgconf")
+            .arg("--homedir")
+            .arg(self.path())
+            .args(["--kill", "all"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+fn gpg(home: &Path, args: &[&str], stdin: &[u8]) -> Result<std::process::Output> {
+    let mut child = Command::new("gpg")
+        .arg("--homedir")
+        .arg(home)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| ProvenanceError::Gpg(format!("unable to run gpg: {e}")))?;
+
+    // stdin is written from another thread while the output is read, otherwise both gpg and us
+    // could be blocked on a full pipe
+    let mut input = child.stdin.take().expect("stdin to be piped");
+    std::thread::scope(|scope| {
+        let writer = scope.spawn(move || input.write_all(stdin));
+        let output = child.wait_with_output()?;
+        let written = writer.join().expect("stdin writer to not panic");
+
+        // gpg can exit before reading all of stdin, in which case its own error is more useful
+        if output.status.success() {
+            written.map_err(|e| ProvenanceError::Gpg(e.to_string()))?;
+        }
+
+        Ok(output)
+    })
+}
@@ -0,0 +1,313 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{
+    index::{parse_version, VersionConstraint},
+    models::{ChartDependency, ChartLock, ChartYaml},
+};
+
+/// Represents a locked dependency whose version no longer satisfies the constraint in `Chart.yaml`.
+#[derive(Debug, Clone)]
+pub struct OutOfConstraint {
+    /// The dependency as it is requested in `Chart.yaml`.
+    pub requested: ChartDependency,
+
+    /// The version that is in `Chart.lock`.
+    pub locked_version: String,
+}
+
+/// Represents the differences between a chart's `Chart.yaml` and its `Chart.lock`, which is
+/// returned by [`ChartLock::drift`].
+#[derive(Debug, Clone, Default)]
+pub struct LockDrift {
+    /// Dependencies in `Chart.yaml` that aren't locked.
+    pub missing: Vec<ChartDependency>,
+
+    /// Dependencies in `Chart.lock` that aren't in `Chart.yaml` anymore.
+    pub extra: Vec<ChartDependency>,
+
+    /// Dependencies whose locked version doesn't satisfy the constraint in `Chart.yaml`.
+    pub out_of_constraint: Vec<OutOfConstraint>,
+
+    /// Whether if the lock file's digest matches the dependencies in `Chart.yaml`, which is what
+    /// Helm checks before running `helm dependency build`.
+    pub digest_matches: bool,
+}
+
+impl LockDrift {
+    /// Checks if the lock file needs to be regenerated with `helm dependency update`.
+    pub fn is_stale(&self) -> bool {
+        !self.digest_matches || !self.missing.is_empty() || !self.extra.is_empty() || !self.out_of_constraint.is_empty()
+    }
+}
+
+impl ChartLock {
+    /// Compares this lock file with the dependencies in the chart's `Chart.yaml`. Dependencies are matched by
+    /// their name and repository, so a dependency whose repository changed is reported as both missing and extra.
+    ///
+    /// Helm writes the URL of `@name` and `alias:name` repositories into the lock file, so use
+    /// [`DependencyResolver::drift`] for charts that have aliased repositories.
+    ///
+    /// ```
+    /// # use charted::models::{ChartLock, ChartYaml};
+    /// #
+    /// # fn main() -> charted::Result<()> {
+    /// let chart = ChartYaml::from_yaml(r#"
+    /// apiVersion: v2
+    /// name: hello
+    /// version: 0.1.0
+    /// dependencies:
+    ///   - name: postgresql
+    ///     version: ">=12, <13"
+    ///     repository: https://charts.noelware.org/api/indexes/noel
+    /// "#)?;
+    ///
+    /// let lock = ChartLock::from_yaml(r#"
+    /// dependencies:
+    ///   - name: postgresql
+    ///     version: 11.9.13
+    ///     repository: https://charts.noelware.org/api/indexes/noel
+    /// digest: sha256:0000000000000000000000000000000000000000000000000000000000000000
+    /// generated: "2023-03-14T18:32:18.493813472Z"
+    /// "#)?;
+    ///
+    /// let drift = lock.drift(&chart);
+    /// assert!(drift.is_stale());
+    /// assert!(!drift.digest_matches);
+    /// assert_eq!(drift.out_of_constraint[0].locked_version, "11.9.13");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`DependencyResolver::drift`]: crate::chart::DependencyResolver::drift
+    pub fn drift(&self, chart: &ChartYaml) -> LockDrift {
+        let mut drift = LockDrift {
+            digest_matches: ChartLock::compute_digest(&chart.dependencies, &self.dependencies) == self.digest,
+            ..Default::default()
+        };
+
+        let mut unmatched = self.dependencies.iter().collect::<Vec<_>>();
+        for requested in chart.dependencies.iter() {
+            let position = unmatched
+                .iter()
+                .position(|locked| locked.name == requested.name && locked.repository == requested.repository);
+
+            let Some(position) = position else {
+                drift.missing.push(requested.clone());
+                continue;
+            };
+
+            let locked = unmatched.remove(position);
+            let locked_version = locked.version.clone().unwrap_or_default();
+            let satisfied = match (&requested.version, parse_version(&locked_version)) {
+                (Some(constraint), Ok(version)) => constraint
+                    .parse::<VersionConstraint>()
+                    .map(|constraint| constraint.matches(&version))
+                    .unwrap_or(false),
+
+                (None, Ok(_)) => true,
+                (_, Err(_)) => false,
+            };
+
+            if !satisfied {
+                drift.out_of_constraint.push(OutOfConstraint {
+                    requested: requested.clone(),
+                    locked_version,
+                });
+            }
+        }
+
+        drift.extra = unmatched.into_iter().cloned().collect();
+        drift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChartDependency;
+
+    const NOEL: &str = "https://charts.noelware.org/api/indexes/noel";
+    const BITNAMI: &str = "https://charts.bitnami.com/bitnami";
+
+    fn dependency(name: &str, version: &str, repository: &str) -> ChartDependency {
+        ChartDependency::builder(name)
+            .version(version)
+            .repository(repository)
+            .build()
+            .unwrap()
+    }
+
+    fn chart(dependencies: Vec<ChartDependency>) -> ChartYaml {
+        ChartYaml {
+            name: "hello".into(),
+            version: "0.1.0".into(),
+            dependencies,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a lock file that is up to date with `chart`, where every dependency is
+    /// locked to the given versions.
+    fn lock(chart: &ChartYaml, versions: &[&str]) -> ChartLock {
+        let dependencies = chart
+            .dependencies
+            .iter()
+            .zip(versions)
+            .map(|(requested, version)| {
+                dependency(
+                    &requested.name,
+                    version,
+                    requested.repository.as_deref().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        ChartLock {
+            digest: ChartLock::compute_digest(&chart.dependencies, &dependencies),
+            dependencies,
+            ..Default::default()
+        }
+    }
+
+    fn names(dependencies: &[ChartDependency]) -> Vec<&str> {
+        dependencies.iter().map(|d| d.name.as_str()).collect()
+    }
+
+    #[test]
+    fn up_to_date() {
+        let chart = chart(vec![
+            dependency("postgresql", "12.x", NOEL),
+            dependency("redis", ">=17, <18", BITNAMI),
+        ]);
+
+        let drift = lock(&chart, &["12.1.6", "17.8.0"]).drift(&chart);
+        assert!(drift.digest_matches);
+        assert!(!drift.is_stale());
+    }
+
+    #[test]
+    fn changed_digest() {
+        let chart = chart(vec![dependency("postgresql", "12.x", NOEL)]);
+        let mut lock = lock(&chart, &["12.1.6"]);
+        lock.digest = format!("sha256:{}", "0".repeat(64));
+
+        let drift = lock.drift(&chart);
+        assert!(!drift.digest_matches);
+        assert!(drift.missing.is_empty() && drift.extra.is_empty() && drift.out_of_constraint.is_empty());
+        assert!(drift.is_stale());
+
+        // changing a constraint changes the digest, even if the locked version still satisfies it
+        let mut changed = chart.clone();
+        changed.dependencies[0].version = Some("^12.1".into());
+
+        let drift = lock.drift(&changed);
+        assert!(!drift.digest_matches);
+        assert!(drift.out_of_constraint.is_empty());
+
+        // so does changing a field that isn't used for matching dependencies
+        let mut changed = chart.clone();
+        changed.dependencies[0].condition = Some("postgresql.enabled".into());
+
+        let lock = self::lock(&chart, &["12.1.6"]);
+        assert!(lock.drift(&chart).digest_matches);
+        assert!(!lock.drift(&changed).digest_matches);
+    }
+
+    #[test]
+    fn added_dependency() {
+        let before = chart(vec![dependency("postgresql", "12.x", NOEL)]);
+        let lock = lock(&before, &["12.1.6"]);
+
+        let mut after = before.clone();
+        after.dependencies.push(dependency("redis", "17.x", BITNAMI));
+
+        let drift = lock.drift(&after);
+        assert!(!drift.digest_matches);
+        assert_eq!(names(&drift.missing), ["redis"]);
+        assert!(drift.extra.is_empty());
+        assert!(drift.is_stale());
+    }
+
+    #[test]
+    fn removed_dependency() {
+        let before = chart(vec![
+            dependency("postgresql", "12.x", NOEL),
+            dependency("redis", "17.x", BITNAMI),
+        ]);
+
+        let lock = lock(&before, &["12.1.6", "17.8.0"]);
+        let after = chart(vec![dependency("redis", "17.x", BITNAMI)]);
+
+        let drift = lock.drift(&after);
+        assert!(!drift.digest_matches);
+        assert!(drift.missing.is_empty());
+        assert_eq!(names(&drift.extra), ["postgresql"]);
+        assert_eq!(drift.extra[0].version.as_deref(), Some("12.1.6"));
+    }
+
+    #[test]
+    fn changed_repository() {
+        let before = chart(vec![dependency("redis", "17.x", BITNAMI)]);
+        let lock = lock(&before, &["17.8.0"]);
+        let after = chart(vec![dependency("redis", "17.x", NOEL)]);
+
+        let drift = lock.drift(&after);
+        assert_eq!(names(&drift.missing), ["redis"]);
+        assert_eq!(names(&drift.extra), ["redis"]);
+        assert_eq!(drift.extra[0].repository.as_deref(), Some(BITNAMI));
+    }
+
+    #[test]
+    fn out_of_constraint() {
+        let chart = chart(vec![
+            dependency("postgresql", "12.x", NOEL),
+            dependency("redis", "17.x", BITNAMI),
+            dependency("common", "2.x", BITNAMI),
+        ]);
+
+        // the digest matches, but the lock file was edited by hand
+        let drift = lock(&chart, &["11.9.13", "17.8.0", "not-a-version"]).drift(&chart);
+        assert!(drift.digest_matches);
+        assert!(drift.is_stale());
+
+        let out_of_constraint = drift
+            .out_of_constraint
+            .iter()
+            .map(|d| (d.requested.name.as_str(), d.locked_version.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            out_of_constraint,
+            [("postgresql", "11.9.13"), ("common", "not-a-version")]
+        );
+    }
+
+    #[test]
+    fn dependencies_without_constraints() {
+        let mut unconstrained = dependency("postgresql", "", NOEL);
+        unconstrained.version = None;
+
+        let chart = chart(vec![unconstrained]);
+        let drift = lock(&chart, &["12.1.6"]).drift(&chart);
+        assert!(!drift.is_stale());
+    }
+}
@@ -0,0 +1,54 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Utilities to work with Helm charts themselves (rather than a chart repository's index), like
+//! validating a chart's `Chart.yaml`, resolving its dependencies, previewing its values, packaging
+//! a chart or verifying its provenance file.
+
+mod conditions;
+#[cfg(feature = "indexes")]
+mod dependencies;
+#[cfg(feature = "chart-packaging")]
+mod ignore;
+#[cfg(feature = "gpg")]
+mod keyring;
+mod lock;
+#[cfg(feature = "chart-packaging")]
+mod package;
+#[cfg(feature = "chart-packaging")]
+mod provenance;
+mod validate;
+mod values;
+
+pub use conditions::*;
+#[cfg(feature = "indexes")]
+pub use dependencies::*;
+#[cfg(feature = "chart-packaging")]
+pub use ignore::*;
+#[cfg(feature = "gpg")]
+pub use keyring::*;
+pub use lock::*;
+#[cfg(feature = "chart-packaging")]
+pub use package::*;
+#[cfg(feature = "chart-packaging")]
+pub use provenance::*;
+pub use validate::*;
+pub use values::*;
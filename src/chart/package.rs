@@ -0,0 +1,304 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, Compression, GzBuilder};
+use serde_yaml::Value;
+use tar::{Archive, EntryType, Header};
+
+use super::{HelmIgnore, Severity};
+use crate::{models::ChartYaml, Error, Result};
+
+/// Packages a chart directory into a gzipped tarball, like `helm package` does. The tarball is
+/// deterministic: files are sorted, and every entry has the same modification time, owner and
+/// permissions, so packaging the same directory twice produces the same bytes (and digest).
+///
+/// ```no_run
+/// # use charted::chart::ChartPackager;
+/// #
+/// # fn main() -> charted::Result<()> {
+/// let package = ChartPackager::new("./charts/hello").package()?;
+/// let path = package.save("./dist")?;
+/// // => ./dist/hello-0.1.0.tgz
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChartPackager {
+    dir: PathBuf,
+    modified: DateTime<Utc>,
+}
+
+impl ChartPackager {
+    /// Creates a new [`ChartPackager`] for the given chart directory.
+    pub fn new<P: AsRef<Path>>(dir: P) -> ChartPackager {
+        ChartPackager {
+            dir: dir.as_ref().to_path_buf(),
+            modified: DateTime::<Utc>::default(),
+        }
+    }
+
+    /// Sets the modification time to use for every file in the tarball, which
+    /// is the Unix epoch by default.
+    pub fn modified(&mut self, modified: DateTime<Utc>) -> &mut Self {
+        self.modified = modified;
+        self
+    }
+
+    /// Validates the chart's `Chart.yaml` and packages the chart. If the `Chart.yaml` has any validation
+    /// errors, then [`Error::InvalidChart`] is returned.
+    ///
+    /// [`Error::InvalidChart`]: ../enum.Error.html#variant.InvalidChart
+    pub fn package(&self) -> Result<ChartPackage> {
+        let chart_yaml = fs::read_to_string(self.dir.join("Chart.yaml"))?;
+        let chart = ChartYaml::from_yaml(&chart_yaml)?;
+        let diagnostics = chart.validate();
+        if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+            return Err(Error::InvalidChart { diagnostics });
+        }
+
+        let ignore = HelmIgnore::load(&self.dir)?;
+        let mut files = vec![];
+        collect_files(&self.dir, "", &ignore, &mut vec![], &mut files)?;
+
+        // Helm always puts the Chart.yaml first, then everything else is sorted
+        files.retain(|file| file != "Chart.yaml");
+        files.sort();
+        files.insert(0, "Chart.yaml".to_owned());
+
+        let mut tarball = tar::Builder::new(GzBuilder::new().mtime(0).write(vec![], Compression::default()));
+        for file in files.iter() {
+            let contents = fs::read(self.dir.join(file))?;
+            let mut header = Header::new_gnu();
+            header.set_entry_type(EntryType::Regular);
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_uid(0);
+            header.set_gid(0);
+            header.set_mtime(self.modified.timestamp().max(0) as u64);
+
+            tarball.append_data(&mut header, format!("{}/{file}", chart.name), contents.as_slice())?;
+        }
+
+        let bytes = tarball.into_inner()?.finish()?;
+        Ok(ChartPackage { chart, files, bytes })
+    }
+}
+
+/// Collects every file that isn't ignored in `root/prefix`. Symbolic links are followed like Helm
+/// does, `ancestors` holds the canonical path of every directory that is being walked so a link
+/// back to one of them is reported as an error instead of being followed forever.
+fn collect_files(
+    root: &Path,
+    prefix: &str,
+    ignore: &HelmIgnore,
+    ancestors: &mut Vec<PathBuf>,
+    files: &mut Vec<String>,
+) -> Result<()> {
+    let dir = root.join(prefix);
+    let canonical = fs::canonicalize(&dir)?;
+    if ancestors.contains(&canonical) {
+        return Err(Error::String(format!(
+            "symbolic link '{prefix}' points to one of its parent directories"
+        )));
+    }
+
+    ancestors.push(canonical);
+
+    let mut entries = fs::read_dir(&dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+
+        let mut metadata = fs::symlink_metadata(entry.path())?;
+        if metadata.file_type().is_symlink() {
+            metadata = fs::metadata(entry.path())?;
+        }
+
+        if ignore.is_ignored(&path, metadata.is_dir()) {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            collect_files(root, &path, ignore, ancestors, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    ancestors.pop();
+    Ok(())
+}
+
+/// Represents a chart that was packaged by a [`ChartPackager`].
+#[derive(Debug, Clone)]
+pub struct ChartPackage {
+    /// The chart's `Chart.yaml`.
+    pub chart: ChartYaml,
+
+    /// Every file that was packaged, relative to the chart's root.
+    pub files: Vec<String>,
+    bytes: Vec<u8>,
+}
+
+impl ChartPackage {
+    /// Returns the file name that Helm uses for this chart package, i.e. `hello-0.1.0.tgz`.
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.tgz", self.chart.name, self.chart.version)
+    }
+
+    /// Returns the gzipped tarball.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the gzipped tarball, consuming this package.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Writes the tarball into the given directory with [`ChartPackage::file_name`] as its name, and
+    /// returns the path to it.
+    pub fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
+        fs::create_dir_all(dir.as_ref())?;
+
+        let path = dir.as_ref().join(self.file_name());
+        let mut file = fs::File::create(&path)?;
+        file.write_all(&self.bytes)?;
+
+        Ok(path)
+    }
+}
+
+/// Represents the contents of a chart package (`.tgz`) that was inspected without extracting it to disk.
+///
+/// ```no_run
+/// # use charted::chart::InspectedChart;
+/// #
+/// # fn main() -> charted::Result<()> {
+/// let inspected = InspectedChart::open("./dist/hello-0.1.0.tgz")?;
+/// println!("{} {}", inspected.chart.name, inspected.chart.version);
+/// for file in inspected.files.iter() {
+///     println!("  {file}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InspectedChart {
+    /// The chart's `Chart.yaml`.
+    pub chart: ChartYaml,
+
+    /// The chart's `values.yaml`, if it has one.
+    pub values: Option<Value>,
+
+    /// The chart's README, if it has one.
+    pub readme: Option<String>,
+
+    /// Every file in the package, relative to the chart's root.
+    pub files: Vec<String>,
+}
+
+impl InspectedChart {
+    /// Inspects the chart package at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<InspectedChart> {
+        InspectedChart::from_reader(fs::File::open(path)?)
+    }
+
+    /// Inspects a chart package from a gzipped tarball.
+    pub fn from_reader<R: Read>(reader: R) -> Result<InspectedChart> {
+        let mut archive = Archive::new(GzDecoder::new(reader));
+        let mut chart_yaml = None;
+        let mut values = None;
+        let mut readme = None;
+        let mut files = vec![];
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type() != EntryType::Regular {
+                continue;
+            }
+
+            // every file is in a directory named after the chart, i.e. `hello/Chart.yaml`
+            let path = entry.path()?.to_string_lossy().replace('\\', "/");
+            let Some((_, file)) = path.split_once('/') else {
+                continue;
+            };
+
+            let file = file.to_owned();
+            match file.as_str() {
+                "Chart.yaml" => {
+                    let mut contents = String::new();
+                    entry.read_to_string(&mut contents)?;
+                    chart_yaml = Some(ChartYaml::from_yaml(contents)?);
+                }
+
+                "values.yaml" => {
+                    let mut contents = String::new();
+                    entry.read_to_string(&mut contents)?;
+                    values = Some(serde_yaml::from_str(&contents).map_err(|e| Error::YamlSerialization {
+                        error: e,
+                        payload: contents,
+                    })?);
+                }
+
+                name if is_readme(name) => {
+                    let mut contents = String::new();
+                    entry.read_to_string(&mut contents)?;
+                    readme = Some(contents);
+                }
+
+                _ => {}
+            }
+
+            files.push(file);
+        }
+
+        let chart = chart_yaml.ok_or_else(|| Error::String("chart package doesn't contain a Chart.yaml".into()))?;
+        files.sort();
+
+        Ok(InspectedChart {
+            chart,
+            values,
+            readme,
+            files,
+        })
+    }
+}
+
+fn is_readme(name: &str) -> bool {
+    matches!(
+        name.to_lowercase().as_str(),
+        "readme" | "readme.md" | "readme.txt" | "readme.markdown"
+    )
+}
@@ -0,0 +1,263 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{collections::BTreeMap, fs, io::Read, path::Path};
+
+use sha2::{Digest, Sha256};
+
+use super::InspectedChart;
+use crate::{models::ChartYaml, Error, Result};
+
+/// Represents an error that occurred when generating or verifying a provenance (`.prov`) file.
+#[derive(Debug, thiserror::Error)]
+pub enum ProvenanceError {
+    #[error("provenance file is malformed: {0}")]
+    Malformed(String),
+
+    #[error("provenance file is not signed")]
+    MissingSignature,
+
+    #[error("signature verification failed: {0}")]
+    InvalidSignature(String),
+
+    #[error("provenance file doesn't list a digest for '{0}'")]
+    FileNotListed(String),
+
+    #[error("digest of '{file}' doesn't match the provenance file: expected {expected}, got {actual}")]
+    DigestMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("provenance file is for {expected}, but the chart package is {actual}")]
+    ChartMismatch { expected: String, actual: String },
+
+    #[error("gpg is not available: {0}")]
+    GpgUnavailable(String),
+
+    #[error("gpg failed: {0}")]
+    Gpg(String),
+}
+
+const SIGNED_MESSAGE_HEADER: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+pub(super) const SIGNATURE_HEADER: &str = "-----BEGIN PGP SIGNATURE-----";
+const SIGNATURE_FOOTER: &str = "-----END PGP SIGNATURE-----";
+
+/// Computes the SHA-256 digest of a chart package, encoded in hexadecimal. This is the same digest that
+/// Helm uses in provenance files and in an `index.yaml`'s `digest` field.
+pub fn chart_digest<B: AsRef<[u8]>>(bytes: B) -> String {
+    hex::encode(Sha256::digest(bytes.as_ref()))
+}
+
+/// Computes the SHA-256 digest of the chart package at the given path, see [`chart_digest`].
+pub fn chart_digest_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&buf[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Represents the contents of a Helm provenance (`.prov`) file: the chart's metadata and the digests of
+/// the chart packages it covers. Helm clearsigns this document with a PGP key, which is done by `Keyring::sign`
+/// with the `gpg` feature.
+///
+/// ```no_run
+/// # use charted::chart::Provenance;
+/// #
+/// # fn main() -> charted::Result<()> {
+/// let provenance = Provenance::for_package_file("./dist/hello-0.1.0.tgz")?;
+/// println!("{}", provenance.message()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Provenance {
+    /// The metadata of the chart this provenance file is for.
+    pub chart: ChartYaml,
+
+    /// The digests of the files this provenance file covers, keyed by the file's name. Digests are
+    /// prefixed with the algorithm, i.e. `sha256:...`.
+    pub files: BTreeMap<String, String>,
+}
+
+impl Provenance {
+    /// Creates a new [`Provenance`] for a chart package's bytes.
+    pub fn for_package<N: Into<String>, B: AsRef<[u8]>>(file_name: N, bytes: B) -> Result<Provenance> {
+        let inspected = InspectedChart::from_reader(bytes.as_ref())?;
+        let mut files = BTreeMap::new();
+        files.insert(file_name.into(), format!("sha256:{}", chart_digest(bytes)));
+
+        Ok(Provenance {
+            chart: inspected.chart,
+            files,
+        })
+    }
+
+    /// Creates a new [`Provenance`] for the chart package at the given path.
+    pub fn for_package_file<P: AsRef<Path>>(path: P) -> Result<Provenance> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| Error::String(format!("'{}' is not a file", path.display())))?;
+
+        Provenance::for_package(file_name, fs::read(path)?)
+    }
+
+    /// Returns the document that is signed, which is the chart's metadata and the file digests as
+    /// YAML, separated with the `...` document end marker.
+    pub fn message(&self) -> Result<String> {
+        let chart = self.chart.to_yaml()?;
+
+        #[derive(serde::Serialize)]
+        struct Sums<'a> {
+            files: &'a BTreeMap<String, String>,
+        }
+
+        let sums = serde_yaml::to_string(&Sums { files: &self.files }).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })?;
+
+        Ok(format!("{chart}\n...\n{sums}"))
+    }
+
+    /// Parses a provenance document, which can either be clearsigned (like a `.prov` file)
+    /// or just the message from [`Provenance::message`].
+    pub fn parse<S: AsRef<str>>(contents: S) -> Result<Provenance> {
+        let contents = contents.as_ref();
+        let message = if contents.trim_start().starts_with(SIGNED_MESSAGE_HEADER) {
+            signed_message(contents)?
+        } else {
+            contents.to_owned()
+        };
+
+        let (chart, sums) = message
+            .split_once("\n...\n")
+            .ok_or_else(|| ProvenanceError::Malformed("missing '...' separator".into()))?;
+
+        #[derive(serde::Deserialize)]
+        struct Sums {
+            #[serde(default)]
+            files: BTreeMap<String, String>,
+        }
+
+        let chart = ChartYaml::from_yaml(chart)?;
+        let sums: Sums = serde_yaml::from_str(sums).map_err(|e| ProvenanceError::Malformed(e.to_string()))?;
+
+        Ok(Provenance {
+            chart,
+            files: sums.files,
+        })
+    }
+
+    /// Checks that the given chart package's digest matches the one listed in this provenance
+    /// file, and that the package is the same chart that this file was created for.
+    pub fn verify_package<N: AsRef<str>, B: AsRef<[u8]>>(&self, file_name: N, bytes: B) -> Result<()> {
+        let file_name = file_name.as_ref();
+        let expected = self
+            .files
+            .get(file_name)
+            .ok_or_else(|| ProvenanceError::FileNotListed(file_name.to_owned()))?;
+
+        let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+        let actual = chart_digest(bytes.as_ref());
+        if !expected.eq_ignore_ascii_case(&actual) {
+            return Err(ProvenanceError::DigestMismatch {
+                file: file_name.to_owned(),
+                expected: expected.to_owned(),
+                actual,
+            }
+            .into());
+        }
+
+        let chart = InspectedChart::from_reader(bytes.as_ref())?.chart;
+        if chart.name != self.chart.name || chart.version != self.chart.version {
+            return Err(ProvenanceError::ChartMismatch {
+                expected: format!("{}-{}", self.chart.name, self.chart.version),
+                actual: format!("{}-{}", chart.name, chart.version),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts the signed message from a document that must be exactly one clearsigned block with only
+/// whitespace around it. gpg ignores text outside of the block, so anything else could put digests
+/// that weren't signed next to a valid signature.
+#[cfg(feature = "gpg")]
+pub(super) fn clearsigned_message(contents: &str) -> std::result::Result<String, ProvenanceError> {
+    let contents = contents.trim();
+    let count = |marker: &str| contents.lines().filter(|line| line.trim_end() == marker).count();
+    if count(SIGNATURE_HEADER) == 0 {
+        return Err(ProvenanceError::MissingSignature);
+    }
+
+    if !contents.starts_with(SIGNED_MESSAGE_HEADER) || !contents.ends_with(SIGNATURE_FOOTER) {
+        return Err(ProvenanceError::Malformed(
+            "only a single clearsigned block is allowed, with nothing around it".into(),
+        ));
+    }
+
+    if count(SIGNED_MESSAGE_HEADER) != 1 || count(SIGNATURE_HEADER) != 1 || count(SIGNATURE_FOOTER) != 1 {
+        return Err(ProvenanceError::Malformed(
+            "only a single clearsigned block is allowed".into(),
+        ));
+    }
+
+    signed_message(contents)
+}
+
+/// Extracts the signed message from a clearsigned document, undoing the dash-escaping.
+fn signed_message(contents: &str) -> std::result::Result<String, ProvenanceError> {
+    let contents = contents.replace("\r\n", "\n");
+    let (_, rest) = contents
+        .split_once(SIGNED_MESSAGE_HEADER)
+        .ok_or_else(|| ProvenanceError::Malformed("missing signed message header".into()))?;
+
+    // armor headers (i.e. `Hash: SHA512`) end with an empty line
+    let (_, rest) = rest
+        .split_once("\n\n")
+        .ok_or_else(|| ProvenanceError::Malformed("missing armor headers".into()))?;
+
+    let (message, _) = rest
+        .split_once(&format!("\n{SIGNATURE_HEADER}"))
+        .ok_or(ProvenanceError::MissingSignature)?;
+
+    let lines = message
+        .split('\n')
+        .map(|line| line.strip_prefix("- ").unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    Ok(lines.join("\n") + "\n")
+}
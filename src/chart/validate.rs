@@ -0,0 +1,556 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use reqwest::Url;
+
+use crate::{
+    index::{parse_version, VersionConstraint},
+    models::{ApiVersion, ChartMaintainer, ChartYaml, RepositoryType},
+};
+
+/// Represents how severe a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Severity {
+    /// The chart will be rejected by Helm.
+    #[serde(rename = "error")]
+    Error,
+
+    /// The chart will be accepted by Helm, but it should be fixed anyway.
+    #[serde(rename = "warning")]
+    Warning,
+
+    /// A suggestion to improve the chart, which doesn't need to be fixed.
+    #[serde(rename = "info")]
+    Info,
+}
+
+/// Represents a problem that was found when validating a `Chart.yaml` file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Diagnostic {
+    /// How severe this problem is.
+    pub severity: Severity,
+
+    /// The path to the field that has the problem, i.e. `maintainers[0].email`. This is
+    /// empty if the problem is about the whole file.
+    pub field: String,
+
+    /// A human-readable message about the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error<F: Into<String>, M: Into<String>>(field: F, message: M) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning<F: Into<String>, M: Into<String>>(field: F, message: M) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    fn info<F: Into<String>, M: Into<String>>(field: F, message: M) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Info,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "ERROR",
+            Severity::Warning => "WARNING",
+            Severity::Info => "INFO",
+        };
+
+        if self.field.is_empty() {
+            write!(f, "[{severity}] {}", self.message)
+        } else {
+            write!(f, "[{severity}] {}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Parses and validates the contents of a `Chart.yaml` file. If the file can't be parsed, the
+/// parse error is returned as the only [`Diagnostic`].
+///
+/// ```
+/// # use charted::chart::{validate_chart_yaml, Severity};
+/// #
+/// let diagnostics = validate_chart_yaml("apiVersion: v1\nname: hello\nversion: 0.1.0\ntype: library\n");
+/// let errors = diagnostics
+///     .iter()
+///     .filter(|d| d.severity == Severity::Error)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].field, "type");
+/// ```
+pub fn validate_chart_yaml<S: AsRef<str>>(contents: S) -> Vec<Diagnostic> {
+    match serde_yaml::from_str::<ChartYaml>(contents.as_ref()) {
+        Ok(chart) => chart.validate(),
+        Err(e) => vec![Diagnostic::error("", format!("unable to parse Chart.yaml: {e}"))],
+    }
+}
+
+impl ChartYaml {
+    /// Validates this `Chart.yaml` with the rules and severities that `helm lint` uses, returning every
+    /// problem that was found. A chart is valid if none of the diagnostics are a [`Severity::Error`].
+    ///
+    /// Like `helm lint`, invalid maintainer emails and URLs are errors, and a missing `icon` is only
+    /// a [`Severity::Info`]. The `home` URL is checked like the `sources`, although Helm ignores it.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        let api_version = self.api_version.clone();
+
+        match &api_version {
+            None => diagnostics.push(Diagnostic::error("apiVersion", "apiVersion is required")),
+            Some(ApiVersion::Other(other)) => diagnostics.push(Diagnostic::error(
+                "apiVersion",
+                format!("apiVersion '{other}' is not supported, it must be v1 or v2"),
+            )),
+
+            Some(_) => {}
+        }
+
+        if self.name.is_empty() {
+            diagnostics.push(Diagnostic::error("name", "name is required"));
+        } else if self.name.contains(['/', '\\']) || self.name == "." || self.name == ".." {
+            diagnostics.push(Diagnostic::error(
+                "name",
+                format!("chart name '{}' must not be a path", self.name),
+            ));
+        }
+
+        if self.version.is_empty() {
+            diagnostics.push(Diagnostic::error("version", "version is required"));
+        } else if let Err(e) = parse_version(&self.version) {
+            diagnostics.push(Diagnostic::error(
+                "version",
+                format!("version is not valid SemVer: {e}"),
+            ));
+        } else if semver::Version::parse(&self.version).is_err() {
+            diagnostics.push(Diagnostic::warning(
+                "version",
+                format!("version '{}' should be a full SemVer 2 version", self.version),
+            ));
+        }
+
+        if let Some(kube_version) = &self.kube_version {
+            if let Err(e) = kube_version.parse::<VersionConstraint>() {
+                diagnostics.push(Diagnostic::error("kubeVersion", e.to_string()));
+            }
+        }
+
+        match (&api_version, &self.chart_type) {
+            (Some(ApiVersion::V1), Some(_)) => diagnostics.push(Diagnostic::error(
+                "type",
+                "chart type is only valid for charts with apiVersion v2",
+            )),
+
+            (_, Some(RepositoryType::Other(other))) => diagnostics.push(Diagnostic::error(
+                "type",
+                format!("chart type '{other}' is not valid, it must be application, library or operator"),
+            )),
+
+            (_, Some(RepositoryType::Operator)) => diagnostics.push(Diagnostic::error(
+                "type",
+                "operator charts are specific to charted-server, Helm only supports application and library charts",
+            )),
+
+            _ => {}
+        }
+
+        if matches!(api_version, Some(ApiVersion::V1)) && !self.dependencies.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                "dependencies",
+                "dependencies are only valid in Chart.yaml for charts with apiVersion v2, use requirements.yaml instead",
+            ));
+        }
+
+        if matches!(api_version, Some(ApiVersion::V2)) {
+            if self.condition.is_some() {
+                diagnostics.push(Diagnostic::warning(
+                    "condition",
+                    "condition is only used by charts with apiVersion v1",
+                ));
+            }
+
+            if self.tags.is_some() {
+                diagnostics.push(Diagnostic::warning(
+                    "tags",
+                    "tags is only used by charts with apiVersion v1",
+                ));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for (idx, dependency) in self.dependencies.iter().enumerate() {
+            let field = format!("dependencies[{idx}]");
+            if dependency.name.is_empty() {
+                diagnostics.push(Diagnostic::error(
+                    format!("{field}.name"),
+                    "dependency name is required",
+                ));
+            }
+
+            if let Some(version) = &dependency.version {
+                if let Err(e) = version.parse::<VersionConstraint>() {
+                    diagnostics.push(Diagnostic::error(format!("{field}.version"), e.to_string()));
+                }
+            }
+
+            if let Some(alias) = &dependency.alias {
+                if alias.is_empty() || !alias.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                    diagnostics.push(Diagnostic::error(
+                        format!("{field}.alias"),
+                        format!("alias '{alias}' must only contain alphanumeric characters, '-' or '_'"),
+                    ));
+                }
+            }
+
+            let key = dependency.alias.as_ref().unwrap_or(&dependency.name);
+            if !key.is_empty() && !seen.insert(key) {
+                diagnostics.push(Diagnostic::error(
+                    field,
+                    format!("more than one dependency is named or aliased '{key}'"),
+                ));
+            }
+        }
+
+        for (idx, maintainer) in self.maintainers.iter().enumerate() {
+            validate_maintainer(&format!("maintainers[{idx}]"), maintainer, &mut diagnostics);
+        }
+
+        if let Some(home) = &self.home {
+            validate_url("home", home, &mut diagnostics);
+        }
+
+        for (idx, source) in self.sources.iter().enumerate() {
+            validate_url(&format!("sources[{idx}]"), source, &mut diagnostics);
+        }
+
+        match &self.icon {
+            Some(icon) => validate_url("icon", icon, &mut diagnostics),
+            None => diagnostics.push(Diagnostic::info("icon", "icon is recommended")),
+        }
+
+        diagnostics
+    }
+
+    /// Checks if [`ChartYaml::validate`] doesn't return any errors.
+    pub fn is_valid(&self) -> bool {
+        self.validate().iter().all(|d| d.severity != Severity::Error)
+    }
+}
+
+fn validate_maintainer(field: &str, maintainer: &ChartMaintainer, diagnostics: &mut Vec<Diagnostic>) {
+    if maintainer.name.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            format!("{field}.name"),
+            "maintainer name is required",
+        ));
+    }
+
+    if let Some(email) = &maintainer.email {
+        if !is_valid_email(email) {
+            diagnostics.push(Diagnostic::error(
+                format!("{field}.email"),
+                format!("invalid email '{email}'"),
+            ));
+        }
+    }
+
+    if let Some(url) = &maintainer.url {
+        validate_url(&format!("{field}.url"), url, diagnostics);
+    }
+}
+
+fn validate_url(field: &str, url: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some() => {}
+        Ok(_) => diagnostics.push(Diagnostic::error(
+            field,
+            format!("invalid URL '{url}': it must be an http:// or https:// URL"),
+        )),
+
+        Err(e) => diagnostics.push(Diagnostic::error(field, format!("invalid URL '{url}': {e}"))),
+    }
+}
+
+fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.contains('@')
+        && !email.contains(char::is_whitespace)
+        && domain.split('.').count() >= 2
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChartDependency;
+
+    fn chart() -> ChartYaml {
+        ChartYaml {
+            api_version: Some(ApiVersion::V2),
+            name: "hello".into(),
+            version: "0.1.0".into(),
+            icon: Some("https://charts.noelware.org/icon.png".into()),
+            ..Default::default()
+        }
+    }
+
+    fn diagnostics(chart: &ChartYaml) -> Vec<(Severity, String)> {
+        chart.validate().into_iter().map(|d| (d.severity, d.field)).collect()
+    }
+
+    fn error(field: &str) -> Vec<(Severity, String)> {
+        vec![(Severity::Error, field.into())]
+    }
+
+    fn warning(field: &str) -> Vec<(Severity, String)> {
+        vec![(Severity::Warning, field.into())]
+    }
+
+    #[test]
+    fn valid_chart() {
+        assert!(diagnostics(&chart()).is_empty());
+        assert!(chart().is_valid());
+    }
+
+    #[test]
+    fn unparsable_chart_yaml() {
+        let diagnostics = validate_chart_yaml("name: [");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].message.starts_with("unable to parse Chart.yaml"));
+    }
+
+    #[test]
+    fn api_version() {
+        let mut chart = chart();
+        chart.api_version = None;
+        assert_eq!(diagnostics(&chart), error("apiVersion"));
+
+        chart.api_version = Some(ApiVersion::Other("v3".into()));
+        assert_eq!(diagnostics(&chart), error("apiVersion"));
+
+        chart.api_version = Some(ApiVersion::V1);
+        assert!(diagnostics(&chart).is_empty());
+    }
+
+    #[test]
+    fn name() {
+        for name in ["", "a/b", "a\\b", ".", ".."] {
+            let mut chart = chart();
+            chart.name = name.into();
+            assert_eq!(diagnostics(&chart), error("name"), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn version() {
+        for (version, expected) in [
+            ("", error("version")),
+            ("latest", error("version")),
+            ("1.2", warning("version")),
+            ("v1.2.3", warning("version")),
+            ("1.2.3-rc.1+build", vec![]),
+        ] {
+            let mut chart = chart();
+            chart.version = version.into();
+            assert_eq!(diagnostics(&chart), expected, "{version:?}");
+        }
+    }
+
+    #[test]
+    fn kube_version() {
+        let mut chart = chart();
+        chart.kube_version = Some(">=1.20.0-0".into());
+        assert!(diagnostics(&chart).is_empty());
+
+        chart.kube_version = Some(">= one point twenty".into());
+        assert_eq!(diagnostics(&chart), error("kubeVersion"));
+    }
+
+    #[test]
+    fn chart_type() {
+        for (api_version, ty, expected) in [
+            (ApiVersion::V2, RepositoryType::Application, vec![]),
+            (ApiVersion::V2, RepositoryType::Library, vec![]),
+            (ApiVersion::V2, RepositoryType::Operator, error("type")),
+            (ApiVersion::V2, RepositoryType::Other("plugin".into()), error("type")),
+            (ApiVersion::V1, RepositoryType::Application, error("type")),
+        ] {
+            let mut chart = chart();
+            chart.api_version = Some(api_version);
+            chart.chart_type = Some(ty.clone());
+            assert_eq!(diagnostics(&chart), expected, "{ty}");
+        }
+    }
+
+    #[test]
+    fn v1_and_v2_only_fields() {
+        let mut chart = chart();
+        chart.condition = Some("hello.enabled".into());
+        chart.tags = Some("frontend".into());
+        assert_eq!(
+            diagnostics(&chart),
+            [
+                (Severity::Warning, "condition".into()),
+                (Severity::Warning, "tags".into())
+            ]
+        );
+
+        chart.api_version = Some(ApiVersion::V1);
+        assert!(diagnostics(&chart).is_empty());
+
+        chart
+            .dependencies
+            .push(ChartDependency::builder("common").version("2.x").build().unwrap());
+        assert_eq!(diagnostics(&chart), error("dependencies"));
+    }
+
+    #[test]
+    fn dependencies() {
+        let mut chart = chart();
+        chart.dependencies = vec![
+            ChartDependency::builder("common").version("2.x.x").build().unwrap(),
+            ChartDependency::builder("redis")
+                .version("not a version")
+                .build()
+                .unwrap(),
+            ChartDependency::builder("redis").alias("cache!").build().unwrap(),
+            ChartDependency::builder("postgresql").alias("common").build().unwrap(),
+        ];
+
+        assert_eq!(
+            diagnostics(&chart),
+            [
+                (Severity::Error, "dependencies[1].version".into()),
+                (Severity::Error, "dependencies[2].alias".into()),
+                (Severity::Error, "dependencies[3]".into()),
+            ]
+        );
+
+        chart.dependencies = vec![ChartDependency::default()];
+        assert_eq!(diagnostics(&chart), error("dependencies[0].name"));
+    }
+
+    #[test]
+    fn maintainers() {
+        let mut chart = chart();
+        chart.maintainers = vec![
+            ChartMaintainer::builder("Noel")
+                .email("cutie@floofy.dev")
+                .url("https://floofy.dev")
+                .build()
+                .unwrap(),
+            ChartMaintainer::default(),
+            ChartMaintainer::builder("Someone")
+                .email("someone@localhost")
+                .build()
+                .unwrap(),
+            ChartMaintainer::builder("Someone")
+                .url("mailto:someone@example.com")
+                .build()
+                .unwrap(),
+            ChartMaintainer::builder("Someone").url("not a url").build().unwrap(),
+        ];
+
+        assert_eq!(
+            diagnostics(&chart),
+            [
+                (Severity::Error, "maintainers[1].name".into()),
+                (Severity::Error, "maintainers[2].email".into()),
+                (Severity::Error, "maintainers[3].url".into()),
+                (Severity::Error, "maintainers[4].url".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn urls() {
+        let mut chart = chart();
+        chart.home = Some("git://example.com/hello".into());
+        chart.sources = vec!["https://github.com/noel/hello".into(), "/relative".into()];
+        chart.icon = Some("file:///icon.png".into());
+
+        assert_eq!(
+            diagnostics(&chart),
+            [
+                (Severity::Error, "home".into()),
+                (Severity::Error, "sources[1]".into()),
+                (Severity::Error, "icon".into()),
+            ]
+        );
+
+        chart.home = None;
+        chart.sources.clear();
+        chart.icon = None;
+        assert_eq!(diagnostics(&chart), [(Severity::Info, "icon".into())]);
+        assert!(chart.is_valid());
+    }
+
+    #[test]
+    fn emails() {
+        for email in ["noel@noelware.org", "a.b+c@sub.example.co"] {
+            assert!(is_valid_email(email), "{email}");
+        }
+
+        for email in [
+            "",
+            "noel",
+            "@noelware.org",
+            "noel@",
+            "noel@localhost",
+            "a@b@c.com",
+            "a b@c.com",
+            "a@-b.com",
+        ] {
+            assert!(!is_valid_email(email), "{email}");
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use log::*;
+use serde_yaml::{Mapping, Value};
+
+use crate::models::{ChartDependency, ChartYaml, ImportValue};
+
+/// Merges `src` into `dst` the same way Helm's `MergeTables` does: keys that are missing from `dst` are
+/// copied over, mappings that exist in both are merged recursively and every other value that is already
+/// in `dst` wins. If `dst` is `null`, it's replaced with `src`.
+pub fn merge_values(dst: &mut Value, src: &Value) {
+    match (dst, src) {
+        (Value::Mapping(dst), Value::Mapping(src)) => {
+            for (key, value) in src.iter() {
+                match dst.get_mut(key) {
+                    Some(existing) => {
+                        if existing.is_mapping() && value.is_mapping() {
+                            merge_values(existing, value);
+                        }
+                    }
+
+                    None => {
+                        dst.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        (dst @ Value::Null, src) => *dst = src.clone(),
+        _ => {}
+    }
+}
+
+/// Applies the `import-values` of the given dependencies onto a values document, and returns the
+/// resulting values. `values` is expected to be the coalesced values of the parent chart, where each
+/// subchart's values are under its alias (or name), which can be done with [`merge_values`]:
+///
+/// - `{ child, parent }` entries import the mapping at `<subchart>.<child>` into the `parent` path, where
+///   `.` is the root of the parent's values;
+/// - string entries are a shorthand for `{ child: exports.<string>, parent: . }`.
+///
+/// Like Helm, imported values have a lower priority than the parent's own values, and earlier entries
+/// win over later ones. Entries that don't point to a mapping are skipped with a warning.
+///
+/// ## Example
+/// ```
+/// # use charted::{chart::import_values, models::ChartDependency};
+/// #
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let dependencies: Vec<ChartDependency> = serde_yaml::from_str(r#"
+/// - name: postgresql
+///   import-values:
+///     - data
+///     - child: primary.service
+///       parent: database
+/// "#)?;
+///
+/// let values: serde_yaml::Value = serde_yaml::from_str(r#"
+/// database:
+///   port: 5433
+/// postgresql:
+///   exports:
+///     data:
+///       auth: { username: noel }
+///   primary:
+///     service: { host: postgresql, port: 5432 }
+/// "#)?;
+///
+/// let values = import_values(&values, &dependencies);
+/// assert_eq!(values["auth"]["username"], "noel");
+/// assert_eq!(values["database"]["host"], "postgresql");
+/// assert_eq!(values["database"]["port"], 5433);
+/// # Ok(())
+/// # }
+/// ```
+pub fn import_values(values: &Value, dependencies: &[ChartDependency]) -> Value {
+    let mut imported = Value::Mapping(Mapping::new());
+    for dependency in dependencies.iter() {
+        let name = dependency.alias.as_deref().unwrap_or(dependency.name.as_str());
+        for entry in dependency.import_values.iter() {
+            let ImportValue { child, parent } = entry.to_import_value();
+            match table(values, &format!("{name}.{child}")) {
+                Some(table) => merge_values(&mut imported, &path_to_map(&parent, table.clone())),
+                None => warn!("import-values for dependency {name} is missing table '{child}'"),
+            }
+        }
+    }
+
+    let mut values = values.clone();
+    merge_values(&mut values, &imported);
+    values
+}
+
+/// Looks up a dot-separated path in a values document, returning it only if it's a mapping, like
+/// Helm's `Values.Table`.
+fn table<'v>(values: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(values, |value, key| value.as_mapping().and_then(|map| map.get(key)))
+        .filter(|value| value.is_mapping())
+}
+
+/// Nests `data` under a dot-separated path, where `.` is the root.
+fn path_to_map(path: &str, data: Value) -> Value {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .rev()
+        .fold(data, |data, key| {
+            let mut map = Mapping::new();
+            map.insert(Value::String(key.to_owned()), data);
+
+            Value::Mapping(map)
+        })
+}
+
+impl ChartYaml {
+    /// Applies the `import-values` of every dependency that is enabled with the given values, which
+    /// is what Helm does before rendering a chart. See [`import_values`] for more details.
+    pub fn effective_values(&self, values: &Value) -> Value {
+        let dependencies = self
+            .active_dependencies(values)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        import_values(values, &dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(contents: &str) -> Value {
+        serde_yaml::from_str(contents).unwrap()
+    }
+
+    fn dependencies(contents: &str) -> Vec<ChartDependency> {
+        serde_yaml::from_str(contents).unwrap()
+    }
+
+    #[test]
+    fn merging() {
+        let mut dst = yaml("{ a: 1, b: { c: 2 }, d: [1], e: { f: 1 } }");
+        merge_values(&mut dst, &yaml("{ a: 2, b: { c: 3, g: 4 }, d: [2], e: 1, h: null }"));
+        assert_eq!(dst, yaml("{ a: 1, b: { c: 2, g: 4 }, d: [1], e: { f: 1 }, h: null }"));
+
+        let mut dst = Value::Null;
+        merge_values(&mut dst, &yaml("{ a: 1 }"));
+        assert_eq!(dst, yaml("{ a: 1 }"));
+    }
+
+    #[test]
+    fn imports_under_aliases() {
+        let dependencies = dependencies(
+            r#"
+- name: postgresql
+  alias: database
+  import-values:
+    - child: primary
+      parent: db.primary
+- name: postgresql
+  alias: replica
+  import-values: [data]
+"#,
+        );
+
+        // values are looked up under the alias, not the chart's name
+        let values = yaml(
+            r#"
+postgresql:
+  primary: { host: wrong }
+  exports:
+    data: { replicas: 0 }
+database:
+  primary: { host: database }
+replica:
+  exports:
+    data: { replicas: 3 }
+"#,
+        );
+
+        let imported = import_values(&values, &dependencies);
+        assert_eq!(imported["db"]["primary"]["host"], "database");
+        assert_eq!(imported["replicas"], 3);
+    }
+
+    #[test]
+    fn parent_values_win() {
+        let dependencies = dependencies(
+            r#"
+- name: redis
+  import-values:
+    - child: master
+      parent: cache
+"#,
+        );
+
+        let values = yaml(
+            r#"
+cache: { port: 6380 }
+redis:
+  master: { host: redis, port: 6379 }
+"#,
+        );
+
+        let imported = import_values(&values, &dependencies);
+        assert_eq!(imported["cache"], yaml("{ port: 6380, host: redis }"));
+    }
+
+    #[test]
+    fn earlier_entries_win() {
+        let dependencies = dependencies(
+            r#"
+- name: first
+  import-values: [defaults]
+- name: second
+  import-values:
+    - child: exports.defaults
+      parent: .
+"#,
+        );
+
+        let values = yaml(
+            r#"
+first:
+  exports:
+    defaults: { replicas: 1, from_first: true }
+second:
+  exports:
+    defaults: { replicas: 2, from_second: true }
+"#,
+        );
+
+        let imported = import_values(&values, &dependencies);
+        assert_eq!(imported["replicas"], 1);
+        assert_eq!(imported["from_first"], true);
+        assert_eq!(imported["from_second"], true);
+    }
+
+    #[test]
+    fn skips_missing_or_non_mapping_tables() {
+        let dependencies = dependencies(
+            r#"
+- name: redis
+  import-values:
+    - missing
+    - child: port
+      parent: cache
+    - child: master
+      parent: cache
+"#,
+        );
+
+        let values = yaml("redis: { port: 6379, master: { host: redis } }");
+        let imported = import_values(&values, &dependencies);
+        assert_eq!(imported["cache"], yaml("{ host: redis }"));
+        assert!(imported.get("missing").is_none());
+    }
+
+    #[test]
+    fn effective_values_skip_disabled_dependencies() {
+        let chart = ChartYaml {
+            dependencies: dependencies(
+                r#"
+- name: postgresql
+  condition: postgresql.enabled
+  import-values: [data]
+- name: redis
+  import-values: [data]
+"#,
+            ),
+            ..Default::default()
+        };
+
+        let values = yaml(
+            r#"
+postgresql:
+  enabled: false
+  exports:
+    data: { database: postgresql }
+redis:
+  exports:
+    data: { cache: redis }
+"#,
+        );
+
+        let effective = chart.effective_values(&values);
+        assert!(effective.get("database").is_none());
+        assert_eq!(effective["cache"], "redis");
+    }
+}
@@ -19,21 +19,27 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::{collections::HashMap, fmt::Debug, str::FromStr};
+use std::{collections::HashMap, fmt::Debug, str::FromStr, sync::Arc};
 
+#[cfg(feature = "cache")]
+use chrono::Utc;
 use log::*;
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
-    Body, Client, Method, Request,
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    Body, Client, Method, Request, Response, Url,
+};
+#[cfg(feature = "cache")]
+use reqwest::{
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
 };
 use serde::de::DeserializeOwned;
+#[cfg(feature = "cache")]
+use sha2::{Digest, Sha256};
 
-use crate::{
-    auth::AuthStrategy,
-    containers::IndexesContainer,
-    models::{APIResponse, FeaturesResponse, InfoResponse, MainResponse},
-    APIClientBuilder, Result,
-};
+#[cfg(feature = "cache")]
+use crate::cache::{policy, CacheStore, CachedResponse};
+use crate::{auth::AuthStrategy, APIClientBuilder, Result};
 
 /// Represents the client for creating API requests for [charted-server](https://charts.noelware.org/docs/server/latest). This
 /// is the entrypoint to faciliate API calls to charted-server.
@@ -44,8 +50,10 @@ use crate::{
 /// # #[tokio::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = APIClient::default();
-/// client.repositories("charted/server").get().await?;
-/// // => Vec<charted::models::Repository>
+/// # #[cfg(feature = "indexes")]
+/// client.indexes().get("noel").await?;
+/// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+/// # Ok(())
 /// # }
 /// ```
 ///
@@ -64,9 +72,11 @@ use crate::{
 ///
 /// # #[tokio::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// let client = APIClient::default_with_auth(("username", "password").into());
-/// client.users("@me").await?;
-/// // => Ok(ApiResponse { success: true, data: Some(charted::models::User { ... }), errors: None })
+/// let client = APIClient::default_with_auth(BasicAuthStrategy::from(("username", "password")));
+/// # #[cfg(feature = "indexes")]
+/// client.indexes().get("username").await?;
+/// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+/// # Ok(())
 /// # }
 /// ```
 ///
@@ -83,8 +93,10 @@ use crate::{
 /// # #[tokio::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = APIClient::default_with_auth(SessionTokenStrategy::new("access token"));
-/// client.users("@me").await?;
-/// // => Ok(ApiResponse { success: true, data: Some(charted::models::User { ... }), errors: None })
+/// # #[cfg(feature = "indexes")]
+/// client.indexes().get("noel").await?;
+/// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+/// # Ok(())
 /// # }
 /// ```
 ///
@@ -99,16 +111,22 @@ use crate::{
 /// # #[tokio::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = APIClient::default_with_auth(ApiKeyStrategy::new("access token"));
-/// client.users("@me").await?;
-/// // => [if the `users.view` permission is enabled]: Ok(ApiResponse { success: true, data: Some(charted::models::User { ... }), errors: None })
+/// # #[cfg(feature = "indexes")]
+/// client.indexes().get("noel").await?;
+/// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+/// # Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct APIClient {
-    auth_strategy: Option<Box<dyn AuthStrategy>>,
+    auth_strategy: Option<Arc<dyn AuthStrategy>>,
     http_client: Client,
     base_url: String,
     headers: Option<HashMap<String, String>>,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<dyn CacheStore>>,
+    #[cfg(feature = "testing")]
+    cassette: Option<crate::testing::Cassette>,
 }
 
 impl Default for APIClient {
@@ -127,6 +145,10 @@ impl Default for APIClient {
             http_client: client,
             base_url: "https://charts.noelware.org/api".into(),
             headers: None,
+            #[cfg(feature = "cache")]
+            cache: None,
+            #[cfg(feature = "testing")]
+            cassette: None,
         }
     }
 }
@@ -140,13 +162,24 @@ impl APIClient {
         };
 
         APIClient {
-            auth_strategy: builder.auth_strategy,
+            auth_strategy: builder.auth_strategy.map(Arc::from),
             http_client: builder.http_client,
             base_url,
             headers: Some(builder.headers),
+            #[cfg(feature = "cache")]
+            cache: builder.cache,
+            #[cfg(feature = "testing")]
+            cassette: builder.cassette,
         }
     }
 
+    /// Creates a new [`APIClientBuilder`] to customize every input of the [`APIClient`] struct.
+    ///
+    /// [`APIClientBuilder`]: struct.APIClientBuilder.html
+    pub fn builder() -> APIClientBuilder {
+        APIClientBuilder::default()
+    }
+
     /// Creates a new [`APIClient`] with a base URL to use. If you plan to use the official
     /// instance hosted by Noelware, please use [`APIClient::default`] instead.
     ///
@@ -158,6 +191,7 @@ impl APIClient {
     /// let client = APIClient::default_with_url("http://localhost:3651");
     /// client.health().await?;
     /// // => Ok(())
+    /// # Ok(())
     /// # }
     /// ```
     ///
@@ -181,24 +215,53 @@ impl APIClient {
     /// let auth: BasicAuthStrategy = ("someuser", "somepassword").into();
     /// let client = APIClient::default_with_auth(auth);
     ///
-    /// client.me().get().await?;
-    /// // => Ok(ApiResponse { success: true, data: Some(charted::models::User { ... }), errors: None })
+    /// client.health().await?;
+    /// // => Ok(())
+    /// # Ok(())
     /// # }
     /// ```
     ///
     /// [`APIClient::builder()`]: struct.APIClient.html#tymethod.builder
     pub fn default_with_auth<S: AuthStrategy + 'static>(auth: S) -> APIClient {
         APIClient {
-            auth_strategy: Some(Box::new(auth)),
+            auth_strategy: Some(Arc::new(auth)),
             ..Default::default()
         }
     }
 
+    /// Returns a copy of this [`APIClient`] that doesn't use its [`CacheStore`], which can be used to opt out of
+    /// the cache for a single request. Requests from the copy still invalidate cached responses.
+    ///
+    /// ```no_run
+    /// # use charted::{cache::MemoryStore, APIClient};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = APIClient::builder().cache(MemoryStore::new()).build();
+    /// client.info().await?; // => cached for the next call
+    /// client.uncached().info().await?; // => always requests the server
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cache")]
+    pub fn uncached(&self) -> APIClient {
+        APIClient {
+            cache: None,
+            ..self.clone()
+        }
+    }
+
     /// Sends the `GET /health` request to the API server. This method returns a Result<()>, where
     /// the `Result::Ok` variant is returned if the request was a success AND the data was "Ok", otherwise,
-    /// an `Result:Err` variant will occur.
+    /// an `Result:Err` variant will occur. This request never goes through the client's cache, so that
+    /// it always reflects the server's current health.
     pub async fn health(&self) -> Result<()> {
-        let resp = self
+        #[cfg(feature = "cache")]
+        let client = &self.uncached();
+        #[cfg(not(feature = "cache"))]
+        let client = self;
+
+        let resp = client
             .request_text::<Body, &str>("/health", Method::GET, None, None)
             .await?;
 
@@ -209,52 +272,32 @@ impl APIClient {
         }
     }
 
-    /// Sends the `GET /` request to the API server. Returns the [`MainResponse`] if the request was successful,
-    /// which will always will be unless a server error occurs.
-    pub async fn main(&self) -> Result<MainResponse> {
-        let resp = self
-            .request_json::<APIResponse<MainResponse>, Body, &str>("/", Method::GET, None, None)
-            .await?;
-
-        if !resp.success {
-            return Err(crate::Error::APIServer {
-                errors: resp.errors.unwrap(),
-            });
-        }
-
-        Ok(resp.data.unwrap())
-    }
-
-    /// Sends the `GET /info` request to the API server. Returns the [`InfoResponse`] if the request was successful,
-    /// which will always will be unless a server error occurs.
-    pub async fn info(&self) -> Result<InfoResponse> {
-        let resp = self
-            .request_json::<APIResponse<InfoResponse>, Body, &str>("/info", Method::GET, None, None)
-            .await?;
-
-        if !resp.success {
-            return Err(crate::Error::APIServer {
-                errors: resp.errors.unwrap(),
-            });
-        }
-
-        Ok(resp.data.unwrap())
-    }
+    /// Performs a REST request and returns the raw [`Response`], which is used when the body
+    /// needs to be streamed (i.e, downloading chart packages). The endpoint can also be an absolute URL.
+    pub(crate) async fn request<B: Into<Body>, E: AsRef<str>>(
+        &self,
+        endpoint: E,
+        method: Method,
+        body: Option<B>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<Response> {
+        let endpoint_to_use = self.url(endpoint.as_ref());
+        let req = self.create_request(endpoint, method.clone(), body, headers)?;
+        let res = self.execute(req).await?;
 
-    /// Sends the `GET /features` request to the API server. Returns the [`FeaturesResponse`] if the request was successful,
-    /// which will always will be unless a server error occurs.
-    pub async fn features(&self) -> Result<FeaturesResponse> {
-        let resp = self
-            .request_json::<APIResponse<FeaturesResponse>, Body, &str>("/features", Method::GET, None, None)
-            .await?;
+        trace!("[{} {}] -> {}", method, endpoint_to_use, res.status());
 
-        if !resp.success {
-            return Err(crate::Error::APIServer {
-                errors: resp.errors.unwrap(),
-            });
+        #[cfg(feature = "cache")]
+        if method != Method::GET && method != Method::HEAD {
+            // cached responses of this resource are stale now, even if the cache was opted out of
+            if let Some(cache) = self.cache.as_ref() {
+                if let Err(e) = cache.remove(&self.cache_key(&endpoint_to_use)).await {
+                    warn!("unable to invalidate cached response of {endpoint_to_use}: {e}");
+                }
+            }
         }
 
-        Ok(resp.data.unwrap())
+        Ok(res)
     }
 
     /// Performs a REST request where the response type returns just a String of the payload.
@@ -266,22 +309,123 @@ impl APIClient {
         body: Option<B>,
         headers: Option<HashMap<String, String>>,
     ) -> Result<String> {
-        let endpoint_to_use = format!("{}{}", self.base_url, endpoint.as_ref());
-        let req = self.create_request(endpoint, method.clone(), body, headers)?;
+        self.request_text_with_content_type(endpoint, method, body, headers)
+            .await
+            .map(|(body, _)| body)
+    }
 
-        match self.http_client.execute(req).await {
-            Ok(res) => {
-                let status = res.status();
-                let bytes: &[u8] = &res.bytes().await.map_err(|e| crate::Error::Unknown(Box::new(e)))?;
-                let slice = String::from_utf8_lossy(bytes).to_string();
+    /// Same as [`APIClient::request_text`], but also returns the `Content-Type` header of the response, for
+    /// endpoints that respond with something other than JSON when they succeed (like the indexes).
+    pub(crate) async fn request_text_with_content_type<B: Into<Body>, E: AsRef<str>>(
+        &self,
+        endpoint: E,
+        method: Method,
+        body: Option<B>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(String, Option<String>)> {
+        #[cfg(feature = "cache")]
+        if let Some(cache) = self.cache.as_ref().filter(|_| method == Method::GET) {
+            return self
+                .request_cached(cache.as_ref(), endpoint.as_ref(), body, headers)
+                .await;
+        }
+
+        let res = self.request(endpoint, method, body, headers).await?;
+        let content_type = content_type(res.headers());
+        let bytes: &[u8] = &res.bytes().await.map_err(|e| crate::Error::Unknown(Box::new(e)))?;
+        let body = String::from_utf8_lossy(bytes).to_string();
+
+        trace!("{}", body);
+        Ok((body, content_type))
+    }
+
+    // Internal method to perform a `GET` request through the cache store, which returns the cached response
+    // while it's fresh and revalidates it with its `ETag` or `Last-Modified` header afterwards
+    #[cfg(feature = "cache")]
+    async fn request_cached<B: Into<Body>>(
+        &self,
+        cache: &dyn CacheStore,
+        endpoint: &str,
+        body: Option<B>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(String, Option<String>)> {
+        let url = self.url(endpoint);
+        let key = self.cache_key(&url);
+        let cached = cache.get(&key).await;
+
+        let mut headers = headers.unwrap_or_default();
+        if let Some(cached) = &cached {
+            if cached.is_fresh() {
+                trace!("using cached response of {url}");
+                return Ok((cached.body.clone(), cached.content_type.clone()));
+            }
 
-                trace!("[{} {}] -> {}", method, endpoint_to_use, status);
-                trace!("{}", slice);
-                Ok(slice)
+            if let Some(etag) = &cached.etag {
+                headers.insert(IF_NONE_MATCH.to_string(), etag.clone());
             }
 
-            Err(e) => Err(crate::Error::Reqwest(e)),
+            if let Some(last_modified) = &cached.last_modified {
+                headers.insert(IF_MODIFIED_SINCE.to_string(), last_modified.clone());
+            }
+        }
+
+        // a `304 Not Modified` response doesn't have to repeat the validators
+        let (previous_etag, previous_last_modified) = cached
+            .as_ref()
+            .map(|cached| (cached.etag.clone(), cached.last_modified.clone()))
+            .unwrap_or_default();
+
+        let res = self.request(&url, Method::GET, body, Some(headers)).await?;
+        let status = res.status();
+        let response_headers = res.headers().clone();
+
+        let (body, content_type, revalidated) = match cached {
+            Some(cached) if status == StatusCode::NOT_MODIFIED => {
+                trace!("cached response of {url} wasn't modified");
+                (cached.body, cached.content_type, true)
+            }
+
+            _ => {
+                let content_type = content_type(&response_headers);
+                let bytes: &[u8] = &res.bytes().await.map_err(|e| crate::Error::Unknown(Box::new(e)))?;
+                (String::from_utf8_lossy(bytes).to_string(), content_type, false)
+            }
+        };
+
+        if revalidated || status.is_success() {
+            match policy::freshness(&response_headers) {
+                Some(ttl) => {
+                    let now = Utc::now();
+                    let header = |name| {
+                        response_headers
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                            .map(|value| value.to_owned())
+                    };
+
+                    let response = CachedResponse {
+                        url: url.clone(),
+                        body: body.clone(),
+                        content_type: content_type.clone(),
+                        etag: header(ETAG).or(previous_etag),
+                        last_modified: header(LAST_MODIFIED).or(previous_last_modified),
+                        stored_at: now,
+                        expires_at: now + chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::zero()),
+                    };
+
+                    if let Err(e) = cache.put(&key, &response).await {
+                        warn!("unable to cache response of {url}: {e}");
+                    }
+                }
+
+                None => {
+                    let _ = cache.remove(&key).await;
+                }
+            }
         }
+
+        trace!("{}", body);
+        Ok((body, content_type))
     }
 
     /// Performs a request where the response type will always be JSON, unless a [`Error::JsonSerialization`] error occurs. This
@@ -302,22 +446,51 @@ impl APIClient {
         })?
     }
 
-    /// Performs a request where the response type will be deserialized from a YAML-body encoding, unless a [`Error::YamlSerialization`] error
-    /// occurs. This method is used in user and organization Helm indexes and any specific repository templates, `Chart.yaml`, or `index.yaml`
-    /// files from the release.
-    pub(crate) async fn request_yaml<U: DeserializeOwned + Debug, B: Into<Body>, E: AsRef<str>>(
-        &self,
-        endpoint: E,
-        method: Method,
-        body: Option<B>,
-        headers: Option<HashMap<String, String>>,
-    ) -> Result<U> {
-        self.request_text(endpoint, method, body, headers).await.map(|data| {
-            serde_yaml::from_str(data.as_str()).map_err(|e| crate::Error::YamlSerialization {
-                error: e,
-                payload: data,
-            })
-        })?
+    /// Returns the base URL that every endpoint is resolved against.
+    #[cfg(feature = "indexes")]
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Internal method to send a request, which goes through the cassette if there is one
+    async fn execute(&self, req: Request) -> Result<Response> {
+        #[cfg(feature = "testing")]
+        if let Some(cassette) = &self.cassette {
+            return cassette.execute(&self.http_client, req).await;
+        }
+
+        self.http_client.execute(req).await.map_err(crate::Error::Reqwest)
+    }
+
+    // Internal method to compute the cache key of a URL, which is scoped to the credentials that are used so
+    // that a cache store can't leak responses between different users
+    #[cfg(feature = "cache")]
+    fn cache_key(&self, url: &str) -> String {
+        match &self.auth_strategy {
+            Some(auth) => {
+                let digest = Sha256::digest(format!("{} {}", auth.prefix(), auth.value()).as_bytes());
+                format!("{url}#{}", &hex::encode(digest)[..16])
+            }
+
+            None => url.to_owned(),
+        }
+    }
+
+    /// Resolves an endpoint against the base URL, unless it's already an absolute URL.
+    pub(crate) fn url(&self, endpoint: &str) -> String {
+        if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+            endpoint.to_owned()
+        } else {
+            format!("{}{}", self.base_url, endpoint)
+        }
+    }
+
+    // Internal method to check if a URL has the same scheme, host and port as the base URL
+    fn is_same_origin(&self, url: &str) -> bool {
+        match (Url::parse(&self.base_url), Url::parse(url)) {
+            (Ok(base), Ok(url)) => base.origin() == url.origin(),
+            _ => false,
+        }
     }
 
     // Internal method to create a Request to not repeat code
@@ -328,48 +501,108 @@ impl APIClient {
         body: Option<B>,
         headers: Option<HashMap<String, String>>,
     ) -> Result<Request> {
-        let endpoint_to_use = format!("{}{}", self.base_url, endpoint.as_ref());
+        let endpoint_to_use = self.url(endpoint.as_ref());
         trace!("creating request [{} {}]", method, endpoint_to_use);
 
-        let request = self.http_client.request(method, endpoint_to_use);
-        if let Some(auth) = &self.auth_strategy {
+        // credentials are only sent to the API server, not to other hosts that an endpoint can point
+        // to (i.e, chart packages that are hosted somewhere else)
+        let same_origin = self.is_same_origin(&endpoint_to_use);
+        let mut request = self.http_client.request(method, endpoint_to_use);
+        if let Some(auth) = self.auth_strategy.as_ref().filter(|_| same_origin) {
             let (prefix, value) = (auth.prefix(), auth.value());
-            let _ = request
-                .try_clone()
-                .map(|r| r.header(AUTHORIZATION, format!("{prefix} {value}")));
+            request = request.header(AUTHORIZATION, format!("{prefix} {value}"));
         }
 
+        // headers that were given to this request take precedence over the client's extra headers
         let mut headers_to_use = HeaderMap::new();
-        if let Some(h) = headers {
-            for (key, value) in h {
-                headers_to_use.insert(
-                    HeaderName::from_str(key.clone().as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
-                    HeaderValue::from_str(value.clone().as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
-                );
-            }
-        }
-
-        if let Some(h) = &self.headers {
+        for h in [self.headers.clone(), headers].into_iter().flatten() {
             for (key, value) in h {
                 headers_to_use.insert(
-                    HeaderName::from_str(key.clone().as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
-                    HeaderValue::from_str(value.clone().as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
+                    HeaderName::from_str(key.as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
+                    HeaderValue::from_str(value.as_str()).map_err(|e| crate::Error::Unknown(Box::new(e)))?,
                 );
             }
         }
 
-        let _ = request.try_clone().map(|f| f.headers(headers_to_use));
+        request = request.headers(headers_to_use);
         if let Some(b) = body {
-            let _ = request.try_clone().map(|f| f.body::<B>(b));
+            request = request.body::<B>(b);
         }
 
         request.build().map_err(crate::Error::Reqwest)
     }
 }
 
+// Returns the `Content-Type` header of a response, if it's valid UTF-8
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_owned())
+}
+
 impl APIClient {
     /// Creates a container to request to the Indexes API.
-    pub fn indexes(self) -> IndexesContainer {
-        IndexesContainer::new(self)
+    #[cfg(feature = "indexes")]
+    pub fn indexes(self) -> crate::IndexesContainer {
+        crate::IndexesContainer::new(self)
+    }
+
+    /// Creates a container to request to the Repositories API.
+    #[cfg(feature = "repositories")]
+    pub fn repositories(self) -> crate::RepositoriesContainer {
+        crate::RepositoriesContainer::new(self)
+    }
+
+    /// Creates a container to request to the Search API.
+    #[cfg(feature = "search")]
+    pub fn search(self) -> crate::SearchContainer {
+        crate::SearchContainer::new(self)
+    }
+}
+
+#[cfg(all(test, feature = "testing", feature = "cache"))]
+mod tests {
+    use reqwest::{Body, Method};
+
+    use crate::{cache::MemoryStore, testing::Cassette, APIClient};
+
+    #[tokio::test]
+    async fn requests_that_are_not_gets_invalidate_cached_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.yaml");
+        let body = r#"'{"success":true,"data":{"docs":"","message":"","tagline":""}}'"#;
+        std::fs::write(
+            &path,
+            format!(
+                "interactions:
+  - request: {{ method: GET, path: / }}
+    response: {{ status: 200, headers: {{ cache-control: max-age=60 }}, body: {body} }}
+  - request: {{ method: DELETE, path: / }}
+    response: {{ status: 404, body: '' }}
+  - request: {{ method: GET, path: / }}
+    response: {{ status: 200, headers: {{ cache-control: max-age=60 }}, body: {body} }}
+"
+            ),
+        )
+        .unwrap();
+
+        let cassette = Cassette::replay(&path).unwrap();
+        let client = APIClient::builder()
+            .base_url("http://127.0.0.1:9")
+            .cache(MemoryStore::new())
+            .cassette(cassette.clone())
+            .build();
+
+        client.main().await.unwrap();
+        client.main().await.unwrap();
+        assert_eq!(cassette.unused().len(), 2);
+
+        client
+            .request::<Body, &str>("/", Method::DELETE, None, None)
+            .await
+            .unwrap();
+        client.main().await.unwrap();
+        assert!(cassette.unused().is_empty());
     }
 }
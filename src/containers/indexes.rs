@@ -19,12 +19,18 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::sync::Arc;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use reqwest::{Body, Method};
+use log::*;
+use reqwest::{Body, Method, Url};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
-    models::{APIResponse, ChartIndexYaml, Empty},
+    models::{APIResponse, ChartIndexSpec, ChartIndexYaml, Empty},
     APIClient, Error, Result,
 };
 
@@ -32,6 +38,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct IndexesContainer {
     client: Arc<APIClient>,
+    allow_missing_digests: bool,
 }
 
 impl IndexesContainer {
@@ -41,12 +48,40 @@ impl IndexesContainer {
     pub(crate) fn new(client: APIClient) -> IndexesContainer {
         IndexesContainer {
             client: Arc::new(client),
+            allow_missing_digests: false,
         }
     }
 
+    /// Whether if chart versions without a `digest` in the index can be downloaded, which is `false`
+    /// by default since their integrity can't be verified. When they aren't allowed,
+    /// [`Error::MissingDigest`] is returned before anything is downloaded.
+    ///
+    /// [`Error::MissingDigest`]: ../enum.Error.html#variant.MissingDigest
+    pub fn allow_missing_digests(mut self, allow: bool) -> IndexesContainer {
+        self.allow_missing_digests = allow;
+        self
+    }
+
+    /// Returns the URL of a user or organization's chart repository, which is what is used as the `repository`
+    /// of a chart dependency or with `helm repo add`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use charted::APIClient;
+    /// #
+    /// let client = APIClient::default_with_url("http://localhost:3651");
+    /// assert_eq!(client.indexes().repository_url("noel"), "http://localhost:3651/indexes/noel");
+    /// ```
+    pub fn repository_url<S: AsRef<str>>(&self, name: S) -> String {
+        format!("{}/indexes/{}", self.client.base_url(), name.as_ref())
+    }
+
     /// Gets a organization or user's `index.yaml` with the specified snowflake. This can return
     /// the `Option::None` variant if the server doesn't use the Docker Registry feature.
     ///
+    /// With a cache store, the index is revalidated with its `ETag` instead of being downloaded again, but
+    /// it's still parsed on every call. Without one, the whole index is downloaded every time.
+    ///
     /// ## Example
     /// ```no_run
     /// # use charted::APIClient;
@@ -55,40 +90,20 @@ impl IndexesContainer {
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// #   let client = APIClient::default();
     /// client.indexes().get_by_id(123).await?;
-    /// // => Ok(charted::models::ChartIndexYaml { ... })
+    /// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+    /// # Ok(())
     /// # }
     /// ```
     pub async fn get_by_id(&self, id: u64) -> Result<Option<ChartIndexYaml>> {
-        match self
-            .client
-            .request_yaml::<ChartIndexYaml, Body, String>(format!("/indexes/{id}"), Method::GET, None, None)
-            .await
-        {
-            Ok(res) => Ok(Some(res)),
-            Err(e) => match e {
-                // This occurs if there is a database error or if the chart index was not found
-                Error::YamlSerialization { payload, .. } => {
-                    let serialized: APIResponse<Empty> =
-                        serde_json::from_str(&payload).map_err(|e| Error::JsonSerialization { error: e, payload })?;
-
-                    assert!(!serialized.success);
-                    let errors = serialized.errors.unwrap();
-                    if let Some(err) = errors.first() {
-                        if err.code.as_str() == "REST_HANDLER_NOT_FOUND" {
-                            return Ok(None);
-                        }
-                    }
-
-                    Err(Error::APIServer { errors })
-                }
-                _ => Err(e),
-            },
-        }
+        self.fetch(format!("/indexes/{id}")).await
     }
 
     /// Gets a organization or user's `index.yaml` with the user or organization's name. This can return
     /// the `Option::None` variant if the server doesn't use the Docker Registry feature.
     ///
+    /// With a cache store, the index is revalidated with its `ETag` instead of being downloaded again, but
+    /// it's still parsed on every call. Without one, the whole index is downloaded every time.
+    ///
     /// ## Example
     /// ```no_run
     /// # use charted::APIClient;
@@ -97,34 +112,205 @@ impl IndexesContainer {
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// #   let client = APIClient::default();
     /// client.indexes().get("noel").await?;
-    /// // => Ok(charted::models::ChartIndexYaml { ... })
+    /// // => Ok(Some(charted::models::ChartIndexYaml { ... }))
+    /// # Ok(())
     /// # }
     /// ```
     pub async fn get<S: Into<String>>(&self, name: S) -> Result<Option<ChartIndexYaml>> {
-        match self
+        self.fetch(format!("/indexes/{}", name.into())).await
+    }
+
+    /// Downloads the chart package of an entry from a user or organization's `index.yaml`. The SHA-256 digest
+    /// of the package is computed while it is downloaded and compared against the entry's `digest`, and
+    /// [`Error::DigestMismatch`] is returned if they don't match, or [`Error::MissingDigest`] if the entry doesn't
+    /// have a digest (see [`IndexesContainer::allow_missing_digests`]). Relative URLs are resolved against the
+    /// index's URL, like Helm does. Credentials are only sent if the package is hosted on the API server itself.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::APIClient;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// let indexes = client.indexes();
+    /// let index = indexes.get("noel").await?.unwrap();
+    /// let spec = index.latest("hello").unwrap();
+    ///
+    /// indexes.download("noel", spec).await?;
+    /// // => Ok(Vec<u8>)
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::DigestMismatch`]: ../enum.Error.html#variant.DigestMismatch
+    /// [`Error::MissingDigest`]: ../enum.Error.html#variant.MissingDigest
+    pub async fn download<N: AsRef<str>>(&self, name: N, spec: &ChartIndexSpec) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.download_into(name.as_ref(), spec, &mut bytes).await?;
+
+        Ok(bytes)
+    }
+
+    /// Same as [`IndexesContainer::download`], but streams the chart package into the given directory as
+    /// `<name>-<version>.tgz` and returns its path. The package is written to a temporary file first, so
+    /// the file only exists if its digest was verified. An error is returned if the chart's name or version
+    /// would escape the directory, i.e. if they contain a path separator or `..`.
+    pub async fn download_to<N: AsRef<str>, P: AsRef<Path>>(
+        &self,
+        name: N,
+        spec: &ChartIndexSpec,
+        dir: P,
+    ) -> Result<PathBuf> {
+        let dir = dir.as_ref().to_path_buf();
+        let path = dir.join(package_file_name(spec)?);
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let (file, temp_path) = blocking(move || Ok(tempfile::NamedTempFile::new_in(dir)?.into_parts())).await?;
+        let mut file = tokio::fs::File::from_std(file);
+        self.download_into(name.as_ref(), spec, &mut file).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        let persisted = path.clone();
+        blocking(move || temp_path.persist(persisted).map_err(|e| Error::Io(e.error))).await?;
+
+        Ok(path)
+    }
+
+    // Indexes are cached in the client's cache store like any other response, which revalidates
+    // them with the `ETag` that charted-server sends with every index. The cache keeps the index's
+    // text, so it's parsed again on every call, even when the cached index is still fresh.
+    async fn fetch(&self, endpoint: String) -> Result<Option<ChartIndexYaml>> {
+        let url = self.client.url(&endpoint);
+        let (payload, content_type) = self
             .client
-            .request_yaml::<ChartIndexYaml, Body, String>(format!("/indexes/{}", name.into()), Method::GET, None, None)
-            .await
-        {
-            Ok(res) => Ok(Some(res)),
-            Err(e) => match e {
-                // This occurs if there is a database error or if the chart index was not found
-                Error::YamlSerialization { payload, .. } => {
-                    let serialized: APIResponse<Empty> =
-                        serde_json::from_str(&payload).map_err(|e| Error::JsonSerialization { error: e, payload })?;
-
-                    assert!(!serialized.success);
-                    let errors = serialized.errors.unwrap();
-                    if let Some(err) = errors.first() {
-                        if err.code.as_str() == "REST_HANDLER_NOT_FOUND" {
-                            return Ok(None);
-                        }
-                    }
-
-                    Err(Error::APIServer { errors })
+            .request_text_with_content_type::<Body, &str>(&url, Method::GET, None, None)
+            .await?;
+
+        // charted-server only responds with JSON if there is a database error or if the chart index
+        // was not found, everything else has to be an `index.yaml`
+        let is_json = content_type
+            .and_then(|ty| {
+                ty.split(';')
+                    .next()
+                    .map(|ty| ty.trim().eq_ignore_ascii_case("application/json"))
+            })
+            .unwrap_or(false);
+
+        if !is_json {
+            return serde_yaml::from_str::<ChartIndexYaml>(&payload)
+                .map(Some)
+                .map_err(|error| Error::YamlSerialization { error, payload });
+        }
+
+        let serialized: APIResponse<Empty> =
+            serde_json::from_str(&payload).map_err(|e| Error::JsonSerialization { error: e, payload })?;
+
+        match serialized.into_result() {
+            Err(Error::APIServer { errors })
+                if errors.first().map(|err| err.code.as_str()) == Some("REST_HANDLER_NOT_FOUND") =>
+            {
+                Ok(None)
+            }
+
+            Err(e) => Err(e),
+            Ok(_) => Err(Error::String(format!(
+                "API server didn't respond with a chart index for {url}"
+            ))),
+        }
+    }
+
+    async fn download_into<W: AsyncWrite + Unpin>(
+        &self,
+        name: &str,
+        spec: &ChartIndexSpec,
+        mut writer: W,
+    ) -> Result<String> {
+        if spec.digest.is_none() && !self.allow_missing_digests {
+            return Err(Error::MissingDigest {
+                name: spec.name.clone(),
+                version: spec.version.clone(),
+            });
+        }
+
+        let url = spec
+            .urls
+            .first()
+            .ok_or_else(|| Error::String(format!("{} v{} doesn't have any URLs", spec.name, spec.version)))?;
+
+        let url = Url::parse(&format!("{}/", self.repository_url(name)))
+            .and_then(|base| base.join(url))
+            .map_err(|e| Error::String(format!("invalid chart URL '{url}': {e}")))?;
+
+        let mut res = self
+            .client
+            .request::<Body, &str>(url.as_str(), Method::GET, None, None)
+            .await?;
+
+        let status = res.status();
+        if !status.is_success() {
+            let payload = res.text().await?;
+            return match serde_json::from_str::<APIResponse<Empty>>(&payload) {
+                Ok(APIResponse {
+                    errors: Some(errors), ..
+                }) => Err(Error::APIServer { errors }),
+                _ => Err(Error::String(format!("unable to download {url}: {status}"))),
+            };
+        }
+
+        let mut hasher = Sha256::new();
+        while let Some(chunk) = res.chunk().await? {
+            hasher.update(&chunk);
+            writer.write_all(&chunk).await?;
+        }
+
+        writer.flush().await?;
+        let actual = hex::encode(hasher.finalize());
+        match &spec.digest {
+            Some(expected) => {
+                let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+                if !expected.eq_ignore_ascii_case(&actual) {
+                    return Err(Error::DigestMismatch {
+                        expected: expected.to_owned(),
+                        actual,
+                    });
                 }
-                _ => Err(e),
-            },
+            }
+
+            None => warn!(
+                "{} v{} doesn't have a digest in the index, unable to verify its integrity",
+                spec.name, spec.version
+            ),
+        }
+
+        Ok(actual)
+    }
+}
+
+/// Returns the file name that a chart package is saved as, `<name>-<version>.tgz`. The name and version
+/// come from an index that might not be trusted, so they can't contain anything that would make the
+/// file end up outside of the directory it's saved in.
+pub(crate) fn package_file_name(spec: &ChartIndexSpec) -> Result<String> {
+    for (field, value) in [("name", &spec.name), ("version", &spec.version)] {
+        if value.is_empty() || value.contains(['/', '\\', '\0']) || value.contains("..") {
+            return Err(Error::String(format!(
+                "chart {field} '{value}' can't be used as a file name"
+            )));
         }
     }
+
+    Ok(format!("{}-{}.tgz", spec.name, spec.version))
+}
+
+/// Runs blocking file system operations on tokio's blocking thread pool, so they don't stall the
+/// runtime that is driving the request.
+pub(crate) async fn blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::String(format!("blocking task failed: {e}")))?
 }
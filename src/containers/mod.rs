@@ -19,6 +19,16 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+#[cfg(feature = "indexes")]
 mod indexes;
+#[cfg(feature = "repositories")]
+mod repositories;
+#[cfg(feature = "search")]
+mod search;
 
+#[cfg(feature = "indexes")]
 pub use indexes::*;
+#[cfg(feature = "repositories")]
+pub use repositories::*;
+#[cfg(feature = "search")]
+pub use search::*;
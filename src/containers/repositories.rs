@@ -0,0 +1,114 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::Arc;
+
+use reqwest::{Body, Method};
+
+use crate::{
+    models::{APIResponse, Repository},
+    APIClient, Error, Result,
+};
+
+/// Represents a container for requesting to the `/repositories` REST handler, and the repositories that
+/// a user owns.
+#[derive(Debug, Clone)]
+pub struct RepositoriesContainer {
+    client: Arc<APIClient>,
+}
+
+impl RepositoriesContainer {
+    /// Creates a new [`RepositoriesContainer`] with the specified [`APIClient`]
+    ///
+    /// [`APIClient`]: struct.APIClient.html
+    pub(crate) fn new(client: APIClient) -> RepositoriesContainer {
+        RepositoriesContainer {
+            client: Arc::new(client),
+        }
+    }
+
+    /// Gets a repository by its ID, or `None` if it doesn't exist.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::APIClient;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// client.repositories().get(1).await?;
+    /// // => Ok(Some(charted::models::Repository { ... }))
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get(&self, id: u64) -> Result<Option<Repository>> {
+        let response = self
+            .client
+            .request_json::<APIResponse<Repository>, Body, String>(
+                format!("/repositories/{id}"),
+                Method::GET,
+                None,
+                None,
+            )
+            .await?;
+
+        match response.into_result() {
+            Ok(repository) => Ok(Some(repository)),
+            Err(Error::APIServer { errors })
+                if errors.first().map(|err| err.code.as_str()) == Some("UNKNOWN_REPOSITORY") =>
+            {
+                Ok(None)
+            }
+
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lists the repositories that a user owns, by the user's ID or name. An [`Error::APIServer`] error
+    /// with the `UNKNOWN_USER` code is returned if the user doesn't exist, which includes the name of an
+    /// organization since this only lists the repositories of users.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::APIClient;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// client.repositories().list_for_user("noel").await?;
+    /// // => Ok([charted::models::Repository { ... }])
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::APIServer`]: ../enum.Error.html#variant.APIServer
+    pub async fn list_for_user<S: AsRef<str>>(&self, user: S) -> Result<Vec<Repository>> {
+        self.client
+            .request_json::<APIResponse<Vec<Repository>>, Body, String>(
+                format!("/users/{}/repositories", user.as_ref()),
+                Method::GET,
+                None,
+                None,
+            )
+            .await?
+            .into_result()
+    }
+}
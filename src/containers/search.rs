@@ -0,0 +1,117 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{fmt::Debug, sync::Arc};
+
+use reqwest::{Body, Method};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    models::{APIResponse, Organization, Repository, SearchQuery, SearchResult, User},
+    APIClient, Result,
+};
+
+/// Represents a container for requesting to the `/search` REST handler. The Search API is only
+/// available if the server has the `search` feature enabled, which can be checked with [`APIClient::features`].
+///
+/// [`APIClient::features`]: struct.APIClient.html#method.features
+#[derive(Debug, Clone)]
+pub struct SearchContainer {
+    client: Arc<APIClient>,
+}
+
+impl SearchContainer {
+    /// Creates a new [`SearchContainer`] with the specified [`APIClient`]
+    ///
+    /// [`APIClient`]: struct.APIClient.html
+    pub(crate) fn new(client: APIClient) -> SearchContainer {
+        SearchContainer {
+            client: Arc::new(client),
+        }
+    }
+
+    /// Searches all the repositories that match the given [`SearchQuery`].
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::{APIClient, models::SearchQuery};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// client.search().repositories(SearchQuery::new("postgres").owner("noel").limit(10)).await?;
+    /// // => Ok(charted::models::SearchResult { hits: [charted::models::SearchHit { data: charted::models::Repository { ... } }], ... })
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn repositories(&self, query: &SearchQuery) -> Result<SearchResult<Repository>> {
+        self.search("repositories", query).await
+    }
+
+    /// Searches all the users that match the given [`SearchQuery`].
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::{APIClient, models::SearchQuery};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// client.search().users(&SearchQuery::new("noel")).await?;
+    /// // => Ok(charted::models::SearchResult { hits: [charted::models::SearchHit { data: charted::models::User { ... } }], ... })
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn users(&self, query: &SearchQuery) -> Result<SearchResult<User>> {
+        self.search("users", query).await
+    }
+
+    /// Searches all the organizations that match the given [`SearchQuery`].
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::{APIClient, models::SearchQuery};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #   let client = APIClient::default();
+    /// client.search().organizations(&SearchQuery::new("noelware")).await?;
+    /// // => Ok(charted::models::SearchResult { hits: [charted::models::SearchHit { data: charted::models::Organization { ... } }], ... })
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn organizations(&self, query: &SearchQuery) -> Result<SearchResult<Organization>> {
+        self.search("organizations", query).await
+    }
+
+    async fn search<T: DeserializeOwned + Debug>(&self, index: &str, query: &SearchQuery) -> Result<SearchResult<T>> {
+        let query = query.to_query_string()?;
+        self.client
+            .request_json::<APIResponse<SearchResult<T>>, Body, String>(
+                format!("/search/{index}?{query}"),
+                Method::GET,
+                None,
+                None,
+            )
+            .await?
+            .into_result()
+    }
+}
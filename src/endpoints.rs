@@ -0,0 +1,57 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// This file is generated by `cargo xtask codegen` from `openapi/charted.json`, do not edit it by hand.
+
+//! The methods of [`APIClient`] that are generated from the operations in charted-server's OpenAPI document.
+
+use reqwest::{Body, Method};
+
+use crate::{
+    models::{APIResponse, FeaturesResponse, InfoResponse, MainResponse},
+    APIClient, Result,
+};
+
+impl APIClient {
+    /// Sends the `GET /` request to the API server. Returns the [`MainResponse`] if the request was successful, which
+    /// will always will be unless a server error occurs.
+    pub async fn main(&self) -> Result<MainResponse> {
+        self.request_json::<APIResponse<MainResponse>, Body, &str>("/", Method::GET, None, None)
+            .await?
+            .into_result()
+    }
+
+    /// Sends the `GET /info` request to the API server. Returns the [`InfoResponse`] if the request was successful,
+    /// which will always will be unless a server error occurs.
+    pub async fn info(&self) -> Result<InfoResponse> {
+        self.request_json::<APIResponse<InfoResponse>, Body, &str>("/info", Method::GET, None, None)
+            .await?
+            .into_result()
+    }
+
+    /// Sends the `GET /features` request to the API server. Returns the [`FeaturesResponse`] if the request was
+    /// successful, which will always will be unless a server error occurs.
+    pub async fn features(&self) -> Result<FeaturesResponse> {
+        self.request_json::<APIResponse<FeaturesResponse>, Body, &str>("/features", Method::GET, None, None)
+            .await?
+            .into_result()
+    }
+}
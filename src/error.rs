@@ -21,6 +21,12 @@
 
 use thiserror::Error;
 
+#[cfg(feature = "indexes")]
+use crate::chart::DependencyError;
+#[cfg(feature = "yaml")]
+use crate::chart::Diagnostic;
+#[cfg(feature = "chart-packaging")]
+use crate::chart::ProvenanceError;
 use crate::models::ApiError;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -28,8 +34,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Unknown error: {0}")]
-    Unknown(#[from] Box<dyn std::error::Error>),
+    Unknown(#[from] Box<dyn std::error::Error + Send + Sync>),
 
+    #[cfg(feature = "yaml")]
     #[error("YAML serialization error: {error}")]
     YamlSerialization {
         #[source]
@@ -44,12 +51,47 @@ pub enum Error {
         payload: String,
     },
 
+    #[cfg(feature = "search")]
+    #[error("Query string serialization error: {0}")]
+    QueryString(#[from] serde_urlencoded::ser::Error),
+
     #[error("Request error: {0}")]
     Reqwest(#[from] reqwest::Error),
 
     #[error("API server error: {errors:?}")]
     APIServer { errors: Vec<ApiError> },
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[cfg(feature = "yaml")]
+    #[error("Chart is invalid: {diagnostics:?}")]
+    InvalidChart { diagnostics: Vec<Diagnostic> },
+
+    #[error("{builder} requires a non-empty '{field}'")]
+    MissingField { builder: &'static str, field: &'static str },
+
+    #[error("Digest mismatch: expected {expected}, got {actual}")]
+    DigestMismatch { expected: String, actual: String },
+
+    #[error("{name} v{version} doesn't have a digest, so its integrity can't be verified")]
+    MissingDigest { name: String, version: String },
+
+    #[cfg(feature = "indexes")]
+    #[error("Dependency resolution error: {0}")]
+    Dependency(#[from] DependencyError),
+
+    #[cfg(feature = "chart-packaging")]
+    #[error("Provenance error: {0}")]
+    Provenance(#[from] ProvenanceError),
+
+    #[error("Invalid version or constraint '{input}': {reason}")]
+    InvalidVersion { input: String, reason: String },
+
+    #[cfg(feature = "testing")]
+    #[error("No recorded interaction matches the request [{method} {path}]")]
+    UnmatchedRequest { method: String, path: String },
+
     #[error("{0}")]
     String(String),
 }
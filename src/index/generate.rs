@@ -0,0 +1,371 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use log::*;
+
+use crate::{
+    chart::{chart_digest_file, InspectedChart},
+    models::{ChartIndexSpec, ChartIndexYaml},
+    Result,
+};
+
+/// Represents a generator for an `index.yaml` from a directory of chart packages, which works like
+/// `helm repo index`. Packages in the directory itself and in its immediate subdirectories are indexed.
+///
+/// ## Example
+/// ```no_run
+/// # use charted::{index::IndexGenerator, models::ChartIndexYaml};
+/// #
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let existing = ChartIndexYaml::from_yaml(std::fs::read_to_string("./charts/index.yaml")?)?;
+/// let index = IndexGenerator::new("./charts")
+///     .base_url("https://charts.example.com")
+///     .merge(existing)
+///     .generate()?;
+///
+/// std::fs::write("./charts/index.yaml", index.to_yaml()?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexGenerator {
+    dir: PathBuf,
+    base_url: Option<String>,
+    merge: Option<ChartIndexYaml>,
+}
+
+impl IndexGenerator {
+    /// Creates a new [`IndexGenerator`] for the given directory.
+    pub fn new<P: AsRef<Path>>(dir: P) -> IndexGenerator {
+        IndexGenerator {
+            dir: dir.as_ref().to_path_buf(),
+            base_url: None,
+            merge: None,
+        }
+    }
+
+    /// Sets the URL that each package's path is joined with. If this isn't set, the URLs in the
+    /// index are relative to the `index.yaml` itself.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Merges the generated index with an existing one, like `helm repo index --merge`. Chart versions
+    /// that are only in the existing index are kept, and chart versions that are in both are replaced
+    /// with the generated one, but keep their `created` date if the package's digest didn't change.
+    pub fn merge(&mut self, existing: ChartIndexYaml) -> &mut Self {
+        self.merge = Some(existing);
+        self
+    }
+
+    /// Generates the index. Like Helm, only files that can't be read as a chart package or whose
+    /// `Chart.yaml` doesn't have a name or version are skipped; other problems that
+    /// [`ChartYaml::validate`][crate::models::ChartYaml::validate] reports don't keep a chart out of the index.
+    pub fn generate(&self) -> Result<ChartIndexYaml> {
+        let now = Utc::now();
+        let mut index = ChartIndexYaml {
+            api_version: "v1".into(),
+            generated: now,
+            ..Default::default()
+        };
+
+        for path in self.packages()? {
+            let relative = path
+                .strip_prefix(&self.dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            let chart = match InspectedChart::open(&path) {
+                Ok(inspected) if !inspected.chart.name.is_empty() && !inspected.chart.version.is_empty() => {
+                    inspected.chart
+                }
+
+                Ok(_) => {
+                    warn!("skipping {relative}: Chart.yaml is missing a name or version");
+                    continue;
+                }
+
+                Err(e) => {
+                    warn!("skipping {relative}: {e}");
+                    continue;
+                }
+            };
+
+            let entries = index.entries.entry(chart.name.clone()).or_default();
+            if entries.iter().any(|spec| spec.version == chart.version) {
+                warn!(
+                    "skipping {relative}: {}@{} was already indexed",
+                    chart.name, chart.version
+                );
+                continue;
+            }
+
+            let mut spec = ChartIndexSpec::from(chart);
+            spec.digest = Some(chart_digest_file(&path)?);
+            spec.urls = vec![self.url(&relative)];
+            spec.created = Some(now);
+
+            entries.push(spec);
+        }
+
+        if let Some(existing) = self.merge.clone() {
+            index.merge(existing);
+        }
+
+        index.sort_entries();
+        Ok(index)
+    }
+
+    fn url(&self, relative: &str) -> String {
+        match &self.base_url {
+            Some(base) => format!("{}/{relative}", base.trim_end_matches('/')),
+            None => relative.to_owned(),
+        }
+    }
+
+    /// Returns every `*.tgz` file in the directory and in its immediate subdirectories.
+    fn packages(&self) -> Result<Vec<PathBuf>> {
+        let mut packages = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                for entry in fs::read_dir(&path)? {
+                    let path = entry?.path();
+                    if is_package(&path) {
+                        packages.push(path);
+                    }
+                }
+            } else if is_package(&path) {
+                packages.push(path);
+            }
+        }
+
+        packages.sort();
+        Ok(packages)
+    }
+}
+
+fn is_package(path: &Path) -> bool {
+    path.is_file() && path.extension().map(|ext| ext == "tgz").unwrap_or(false)
+}
+
+impl ChartIndexYaml {
+    /// Merges another index into this one by each chart's name and version. Chart versions that aren't
+    /// in this index are added, and the ones that are keep this index's record. If both records have the
+    /// same digest, the `created` date from `other` is kept so that regenerating an index doesn't touch the
+    /// dates of packages that didn't change.
+    pub fn merge(&mut self, other: ChartIndexYaml) {
+        for (name, specs) in other.entries.into_iter() {
+            let entries = self.entries.entry(name).or_default();
+            for spec in specs.into_iter() {
+                match entries.iter_mut().find(|existing| existing.version == spec.version) {
+                    Some(existing) => {
+                        if existing.digest.is_some() && existing.digest == spec.digest && spec.created.is_some() {
+                            existing.created = spec.created;
+                        }
+                    }
+
+                    None => entries.push(spec),
+                }
+            }
+        }
+
+        for (key, value) in other.extra.into_iter() {
+            self.extra.entry(key).or_insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::chart_digest;
+    use chrono::TimeZone;
+    use flate2::{write::GzEncoder, Compression};
+
+    /// Writes a chart package that only has a `Chart.yaml`, without validating it like
+    /// [`ChartPackager`][crate::chart::ChartPackager] does.
+    fn write_package(dir: &Path, file: &str, chart_yaml: &str) -> Vec<u8> {
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(chart_yaml.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        archive
+            .append_data(&mut header, "chart/Chart.yaml", chart_yaml.as_bytes())
+            .unwrap();
+
+        let bytes = archive.into_inner().unwrap().finish().unwrap();
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, &bytes).unwrap();
+
+        bytes
+    }
+
+    fn chart_yaml(name: &str, version: &str) -> String {
+        format!("apiVersion: v2\nname: {name}\nversion: {version}\n")
+    }
+
+    fn versions(index: &ChartIndexYaml, name: &str) -> Vec<String> {
+        index.entries[name].iter().map(|spec| spec.version.clone()).collect()
+    }
+
+    #[test]
+    fn generates_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let hello = write_package(dir.path(), "hello-0.1.0.tgz", &chart_yaml("hello", "0.1.0"));
+        write_package(dir.path(), "nested/hello-0.2.0.tgz", &chart_yaml("hello", "0.2.0"));
+        write_package(
+            dir.path(),
+            "nested/deeper/hello-0.3.0.tgz",
+            &chart_yaml("hello", "0.3.0"),
+        );
+
+        let index = IndexGenerator::new(dir.path()).generate().unwrap();
+        assert_eq!(index.api_version, "v1");
+        assert_eq!(versions(&index, "hello"), ["0.2.0", "0.1.0"]);
+
+        let spec = &index.entries["hello"][1];
+        assert_eq!(spec.urls, ["hello-0.1.0.tgz"]);
+        assert_eq!(spec.digest.as_deref(), Some(chart_digest(&hello).as_str()));
+        assert_eq!(spec.created, Some(index.generated));
+
+        let index = IndexGenerator::new(dir.path())
+            .base_url("https://charts.example.com/")
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            index.entries["hello"][0].urls,
+            ["https://charts.example.com/nested/hello-0.2.0.tgz"]
+        );
+    }
+
+    #[test]
+    fn keeps_charts_that_helm_would_index() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // both of these are reported as errors by `ChartYaml::validate`, but Helm still indexes them
+        write_package(
+            dir.path(),
+            "icon-0.1.0.tgz",
+            "apiVersion: v2\nname: icon\nversion: 0.1.0\nicon: not a url\n",
+        );
+
+        write_package(
+            dir.path(),
+            "kube-0.1.0.tgz",
+            "apiVersion: v2\nname: kube\nversion: 0.1.0\nkubeVersion: not a constraint\n",
+        );
+
+        let index = IndexGenerator::new(dir.path()).generate().unwrap();
+        assert_eq!(versions(&index, "icon"), ["0.1.0"]);
+        assert_eq!(versions(&index, "kube"), ["0.1.0"]);
+    }
+
+    #[test]
+    fn skips_packages_that_are_not_charts() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(dir.path(), "hello-0.1.0.tgz", &chart_yaml("hello", "0.1.0"));
+        write_package(dir.path(), "unnamed-0.1.0.tgz", "apiVersion: v2\nversion: 0.1.0\n");
+        write_package(
+            dir.path(),
+            "unversioned-0.1.0.tgz",
+            "apiVersion: v2\nname: unversioned\n",
+        );
+        write_package(dir.path(), "broken-0.1.0.tgz", "name: [");
+        write_package(dir.path(), "duplicate-0.1.0.tgz", &chart_yaml("hello", "0.1.0"));
+        fs::write(dir.path().join("garbage.tgz"), b"not a tarball").unwrap();
+        write_package(dir.path(), "hello-0.2.0.tar", &chart_yaml("hello", "0.2.0"));
+
+        let index = IndexGenerator::new(dir.path()).generate().unwrap();
+        assert_eq!(index.entries.keys().collect::<Vec<_>>(), ["hello"]);
+        assert_eq!(index.entries["hello"].len(), 1);
+    }
+
+    #[test]
+    fn merges_existing_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let unchanged = write_package(dir.path(), "hello-0.1.0.tgz", &chart_yaml("hello", "0.1.0"));
+        write_package(dir.path(), "hello-0.2.0.tgz", &chart_yaml("hello", "0.2.0"));
+
+        let created = Utc.with_ymd_and_hms(2023, 3, 14, 18, 32, 18).unwrap();
+        let mut existing = ChartIndexYaml {
+            api_version: "v1".into(),
+            generated: created,
+            ..Default::default()
+        };
+
+        let spec = |version: &str, digest: &str, url: &str| {
+            let mut spec = ChartIndexSpec::builder("hello", version)
+                .digest(digest)
+                .url(url)
+                .created(created)
+                .build()
+                .unwrap();
+
+            spec.extra.insert("kept".into(), true.into());
+            spec
+        };
+
+        existing.entries.insert(
+            "hello".into(),
+            vec![
+                spec("0.1.0", &chart_digest(&unchanged), "old/hello-0.1.0.tgz"),
+                spec("0.2.0", "changed", "old/hello-0.2.0.tgz"),
+                spec("0.0.1", "removed", "old/hello-0.0.1.tgz"),
+            ],
+        );
+
+        existing.extra.insert("serverInfo".into(), "charted".into());
+
+        let index = IndexGenerator::new(dir.path()).merge(existing).generate().unwrap();
+        assert_eq!(versions(&index, "hello"), ["0.2.0", "0.1.0", "0.0.1"]);
+        assert_eq!(index.extra["serverInfo"], "charted");
+
+        let [latest, unchanged, removed] = &index.entries["hello"][..] else {
+            panic!("expected three versions");
+        };
+
+        // packages that are in the directory replace the existing records...
+        assert_eq!(latest.urls, ["hello-0.2.0.tgz"]);
+        assert_eq!(latest.created, Some(index.generated));
+        assert!(!latest.extra.contains_key("kept"));
+
+        // ...but keep their `created` date if the package didn't change
+        assert_eq!(unchanged.urls, ["hello-0.1.0.tgz"]);
+        assert_eq!(unchanged.created, Some(created));
+
+        // and versions that are only in the existing index are kept as-is
+        assert_eq!(removed.urls, ["old/hello-0.0.1.tgz"]);
+        assert!(removed.extra.contains_key("kept"));
+    }
+}
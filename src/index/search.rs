@@ -0,0 +1,247 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::{parse_version, VersionConstraint};
+use crate::models::{ChartIndexSpec, ChartIndexYaml, RepositoryType};
+
+/// Represents a query to search a [`ChartIndexYaml`] in-process, which is useful if the server
+/// doesn't have the `search` feature enabled. The text is matched fuzzily against a chart's name
+/// and keywords, and as a substring of its description; an empty query matches every chart. Chart
+/// versions that are marked as removed are never returned.
+///
+/// ```
+/// # use charted::{index::IndexSearch, models::ChartIndexYaml};
+/// #
+/// # let index = ChartIndexYaml::default();
+/// let hits = index.search(IndexSearch::new("postgres").kube_version("1.26.3").deprecated(false));
+/// for hit in hits {
+///     println!("{} {} [score {}]", hit.name, hit.spec.version, hit.score);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IndexSearch {
+    query: String,
+    kube_version: Option<String>,
+    chart_type: Option<RepositoryType>,
+    deprecated: Option<bool>,
+    maintainer: Option<String>,
+    all_versions: bool,
+}
+
+impl IndexSearch {
+    /// Creates a new [`IndexSearch`] with the text to fuzzy match on.
+    pub fn new<S: Into<String>>(query: S) -> IndexSearch {
+        IndexSearch {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Only returns charts whose `kubeVersion` constraint accepts the given Kubernetes version. Charts
+    /// without a `kubeVersion` are always accepted.
+    pub fn kube_version<S: Into<String>>(&mut self, version: S) -> &mut Self {
+        self.kube_version = Some(version.into());
+        self
+    }
+
+    /// Only returns charts of the given type. Charts without a `type` are considered to be
+    /// an [`RepositoryType::Application`], like Helm does.
+    pub fn chart_type(&mut self, ty: RepositoryType) -> &mut Self {
+        self.chart_type = Some(ty);
+        self
+    }
+
+    /// Only returns charts that are (or aren't) deprecated.
+    pub fn deprecated(&mut self, deprecated: bool) -> &mut Self {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    /// Only returns charts that have a maintainer whose name or email contains the given text.
+    pub fn maintainer<S: Into<String>>(&mut self, maintainer: S) -> &mut Self {
+        self.maintainer = Some(maintainer.into());
+        self
+    }
+
+    /// Whether if every matching version of a chart should be returned, rather than only
+    /// the newest matching version.
+    pub fn all_versions(&mut self, all: bool) -> &mut Self {
+        self.all_versions = all;
+        self
+    }
+
+    fn matches_filters(&self, spec: &ChartIndexSpec) -> bool {
+        if let Some(deprecated) = self.deprecated {
+            if spec.deprecated.unwrap_or(false) != deprecated {
+                return false;
+            }
+        }
+
+        if let Some(ty) = &self.chart_type {
+            if spec.chart_type.as_ref().unwrap_or(&RepositoryType::Application) != ty {
+                return false;
+            }
+        }
+
+        if let Some(maintainer) = &self.maintainer {
+            let maintainer = maintainer.to_lowercase();
+            let found = spec.maintainers.iter().any(|m| {
+                m.name.to_lowercase().contains(&maintainer)
+                    || m.email
+                        .as_ref()
+                        .map(|email| email.to_lowercase().contains(&maintainer))
+                        .unwrap_or(false)
+            });
+
+            if !found {
+                return false;
+            }
+        }
+
+        if let (Some(version), Some(constraint)) = (&self.kube_version, &spec.kube_version) {
+            let version = parse_version(version);
+            let constraint = constraint.parse::<VersionConstraint>();
+
+            // constraints that we can't understand are kept, since we can't
+            // prove that the chart isn't installable.
+            if let (Ok(version), Ok(constraint)) = (version, constraint) {
+                if !constraint.matches(&version) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn score(&self, spec: &ChartIndexSpec) -> Option<u32> {
+        let query = self.query.trim().to_lowercase();
+        if query.is_empty() {
+            return Some(0);
+        }
+
+        let name = fuzzy_score(&query, &spec.name).map(|s| s * 3);
+        let keywords = spec
+            .keywords
+            .iter()
+            .filter_map(|keyword| fuzzy_score(&query, keyword))
+            .max()
+            .map(|s| s * 2);
+
+        let description = spec.description.as_ref().and_then(|d| substring_score(&query, d));
+        [name, keywords, description].into_iter().flatten().max()
+    }
+}
+
+/// Represents a chart version that matched an [`IndexSearch`].
+#[derive(Debug, Clone)]
+pub struct IndexSearchHit<'a> {
+    /// The name of the chart, which is the key in [`ChartIndexYaml::entries`].
+    pub name: &'a str,
+
+    /// The chart version that matched.
+    pub spec: &'a ChartIndexSpec,
+
+    /// How relevant this hit is to the query, higher is better.
+    pub score: u32,
+}
+
+impl ChartIndexYaml {
+    /// Searches this index with the given [`IndexSearch`], returning the hits sorted by
+    /// relevance and then by name.
+    ///
+    /// A chart's versions are considered newest first; versions that aren't valid SemVer are
+    /// still searched, but they're ordered after every valid version in the order that they
+    /// appear in the index.
+    pub fn search(&self, search: &IndexSearch) -> Vec<IndexSearchHit<'_>> {
+        let mut hits = vec![];
+        for (name, specs) in self.entries.iter() {
+            let unparsable = specs.iter().filter(|spec| spec.semver().is_err());
+            let matching = self
+                .versions(name)
+                .into_iter()
+                .chain(unparsable)
+                .filter(|spec| !spec.removed && search.matches_filters(spec))
+                .filter_map(|spec| {
+                    search.score(spec).map(|score| IndexSearchHit {
+                        name: name.as_str(),
+                        spec,
+                        score,
+                    })
+                });
+
+            if search.all_versions {
+                hits.extend(matching);
+            } else {
+                hits.extend(matching.take(1));
+            }
+        }
+
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(b.name)));
+        hits
+    }
+}
+
+/// The lowest score of a subsequence match, which keeps subsequences whose characters are spread
+/// out over the whole haystack from being returned as hits.
+const MIN_SUBSEQUENCE_SCORE: u32 = 20;
+
+/// Scores how well `needle` (which must be lowercase) matches `haystack`, ranking exact matches
+/// over prefixes and prefixes over substrings. Returns `None` if the haystack doesn't contain
+/// the needle.
+fn substring_score(needle: &str, haystack: &str) -> Option<u32> {
+    let haystack = haystack.to_lowercase();
+    if haystack == needle {
+        return Some(100);
+    }
+
+    if haystack.starts_with(needle) {
+        return Some(80);
+    }
+
+    haystack
+        .find(needle)
+        .map(|pos| 60u32.saturating_sub(pos as u32).max(40))
+}
+
+/// Like [`substring_score`], but also matches `haystack` if it contains every character of the
+/// needle in order, which is ranked under substrings and penalized by how spread out the
+/// characters are. This is only used for short text like names and keywords, since almost
+/// any short needle is a subsequence of a long description.
+fn fuzzy_score(needle: &str, haystack: &str) -> Option<u32> {
+    if let Some(score) = substring_score(needle, haystack) {
+        return Some(score);
+    }
+
+    let haystack = haystack.to_lowercase();
+    let mut chars = haystack.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for n in needle.chars() {
+        let (idx, _) = chars.find(|(_, c)| *c == n)?;
+        first.get_or_insert(idx);
+        last = idx;
+    }
+
+    let span = (last - first.unwrap_or(0) + 1) as u32;
+    let gaps = span.saturating_sub(needle.chars().count() as u32);
+    Some(30u32.saturating_sub(gaps)).filter(|score| *score >= MIN_SUBSEQUENCE_SCORE)
+}
@@ -0,0 +1,639 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{fmt::Display, str::FromStr};
+
+use semver::{BuildMetadata, Prerelease, Version};
+
+use crate::{
+    models::{ChartIndexSpec, ChartIndexYaml},
+    Error, Result,
+};
+
+/// Parses a chart version the same way Helm does, which is more lenient than SemVer 2: a leading `v`
+/// is allowed and a missing minor or patch version is treated as `0`.
+///
+/// ```
+/// # use charted::index::parse_version;
+/// #
+/// assert_eq!(parse_version("v1.2").unwrap(), semver::Version::new(1, 2, 0));
+/// assert_eq!(parse_version("1.2.3-beta.1").unwrap().pre.as_str(), "beta.1");
+/// assert!(parse_version("latest").is_err());
+/// ```
+pub fn parse_version<S: AsRef<str>>(version: S) -> Result<Version> {
+    let input = version.as_ref();
+    let partial = PartialVersion::parse(input)?;
+    if partial.major.is_none() || partial.wildcard {
+        return Err(invalid(input, "wildcards are only allowed in constraints"));
+    }
+
+    Ok(partial.floor())
+}
+
+/// Represents a version constraint using the same syntax as Helm (and the [Masterminds/semver] library
+/// it uses), for example `>=1.2, <2`, `~1.2.3`, `^2`, `1.2.x`, `1.2 - 1.4.5` or `1.x || >=3.1`.
+///
+/// Pre-release versions are only matched when a comparator in the same `||` group has a pre-release
+/// itself, which is why Kubernetes version constraints are usually written as `>=1.20.0-0`.
+///
+/// ```
+/// # use charted::index::VersionConstraint;
+/// # use semver::Version;
+/// #
+/// let constraint: VersionConstraint = ">=1.2, <2".parse().unwrap();
+/// assert!(constraint.matches(&Version::new(1, 4, 0)));
+/// assert!(!constraint.matches(&Version::new(2, 0, 0)));
+/// assert!(!constraint.matches(&"1.5.0-rc.1".parse().unwrap()));
+/// ```
+///
+/// [Masterminds/semver]: https://github.com/Masterminds/semver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    raw: String,
+    groups: Vec<ConstraintGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConstraintGroup {
+    checks: Vec<Check>,
+    allows_prerelease: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Check {
+    Eq(Version),
+    Gt(Version),
+    Ge(Version),
+    Lt(Version),
+    Le(Version),
+    Ne(Version),
+
+    /// Matches anything outside of `[start, end)`, used for wildcard `!=` comparators.
+    NotWithin(Version, Version),
+}
+
+impl Check {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Check::Eq(v) => cmp_precedence(version, v).is_eq(),
+            Check::Ne(v) => !cmp_precedence(version, v).is_eq(),
+            Check::Gt(v) => cmp_precedence(version, v).is_gt(),
+            Check::Ge(v) => cmp_precedence(version, v).is_ge(),
+            Check::Lt(v) => cmp_precedence(version, v).is_lt(),
+            Check::Le(v) => cmp_precedence(version, v).is_le(),
+            Check::NotWithin(start, end) => {
+                cmp_precedence(version, start).is_lt() || cmp_precedence(version, end).is_ge()
+            }
+        }
+    }
+}
+
+impl VersionConstraint {
+    /// Returns the constraint as it was written.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Checks if the given version satisfies this constraint.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.groups.iter().any(|group| {
+            if !version.pre.is_empty() && !group.allows_prerelease {
+                return false;
+            }
+
+            group.checks.iter().all(|check| check.matches(version))
+        })
+    }
+}
+
+impl Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut groups = vec![];
+        for group in s.split("||") {
+            let tokens = group.replace(',', " ");
+            let tokens = tokens.split_whitespace().collect::<Vec<_>>();
+            if tokens.is_empty() {
+                return Err(invalid(s, "empty constraint"));
+            }
+
+            let mut checks = vec![];
+            let mut allows_prerelease = false;
+            let mut i = 0;
+            while i < tokens.len() {
+                // operators can be separated from their version, i.e. `>= 1.2`
+                let mut comparator = tokens[i].to_owned();
+                if comparator.chars().all(|c| "=!<>~^".contains(c)) {
+                    i += 1;
+                    let version = tokens.get(i).ok_or_else(|| invalid(s, "operator without a version"))?;
+                    comparator.push_str(version);
+                }
+
+                // hyphen ranges, i.e. `1.2 - 1.4.5`
+                if tokens.get(i + 1) == Some(&"-") {
+                    let end = tokens
+                        .get(i + 2)
+                        .ok_or_else(|| invalid(s, "hyphen range without an end"))?;
+                    let start = PartialVersion::parse(&comparator)?;
+                    let end = PartialVersion::parse(end)?;
+
+                    allows_prerelease |= !start.pre.is_empty() || !end.pre.is_empty();
+                    checks.push(Check::Ge(start.floor()));
+                    checks.push(match end.ceiling() {
+                        Some(ceiling) => Check::Lt(ceiling),
+                        None => Check::Le(end.floor()),
+                    });
+
+                    i += 3;
+                    continue;
+                }
+
+                let (op, version) = split_operator(&comparator);
+                let partial = PartialVersion::parse(version)?;
+                allows_prerelease |= !partial.pre.is_empty();
+                partial
+                    .push_checks(op, &mut checks)
+                    .map_err(|reason| invalid(s, reason))?;
+
+                i += 1;
+            }
+
+            groups.push(ConstraintGroup {
+                checks,
+                allows_prerelease,
+            });
+        }
+
+        Ok(VersionConstraint {
+            raw: s.to_owned(),
+            groups,
+        })
+    }
+}
+
+fn split_operator(comparator: &str) -> (&str, &str) {
+    let idx = comparator
+        .find(|c: char| !"=!<>~^".contains(c))
+        .unwrap_or(comparator.len());
+
+    (&comparator[..idx], &comparator[idx..])
+}
+
+/// A version where the minor and patch versions can be missing or a wildcard (`x`, `X` or `*`).
+#[derive(Debug, Clone)]
+struct PartialVersion {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Prerelease,
+    build: BuildMetadata,
+    wildcard: bool,
+}
+
+impl PartialVersion {
+    fn parse(input: &str) -> Result<PartialVersion> {
+        let trimmed = input.trim().trim_start_matches(['v', 'V']);
+        let core_end = trimmed
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | 'x' | 'X' | '*')))
+            .unwrap_or(trimmed.len());
+
+        let (core, rest) = trimmed.split_at(core_end);
+        let (pre, build) = match rest.split_once('+') {
+            Some((pre, build)) => (pre, build),
+            None => (rest, ""),
+        };
+
+        if !pre.is_empty() && !pre.starts_with('-') {
+            return Err(invalid(input, "unexpected characters after the version"));
+        }
+
+        let pre = Prerelease::new(pre.trim_start_matches('-')).map_err(|e| invalid(input, e))?;
+        let build = BuildMetadata::new(build).map_err(|e| invalid(input, e))?;
+
+        let parts = core.split('.').collect::<Vec<_>>();
+        if core.is_empty() || parts.len() > 3 {
+            return Err(invalid(input, "expected a version like `1`, `1.2` or `1.2.3`"));
+        }
+
+        let mut wildcard = false;
+        let mut numbers = [None; 3];
+        for (idx, part) in parts.iter().enumerate() {
+            if matches!(*part, "x" | "X" | "*") {
+                wildcard = true;
+                continue;
+            }
+
+            if wildcard {
+                return Err(invalid(input, "a version can't be specified after a wildcard"));
+            }
+
+            numbers[idx] = Some(part.parse::<u64>().map_err(|e| invalid(input, e))?);
+        }
+
+        Ok(PartialVersion {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre,
+            build,
+            wildcard,
+        })
+    }
+
+    /// Whether if this version was fully specified, i.e. `1.2.3`.
+    fn is_exact(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    /// The lowest version that this partial version covers, `1.2` is `1.2.0`
+    fn floor(&self) -> Version {
+        Version {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+            build: self.build.clone(),
+        }
+    }
+
+    /// The version right after the last version this partial version covers, `1.2` is `1.3.0`. This
+    /// is `None` for exact versions and `*`.
+    fn ceiling(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), None, _) => Some(Version::new(major + 1, 0, 0)),
+            (Some(major), Some(minor), None) => Some(Version::new(major, minor + 1, 0)),
+            _ => None,
+        }
+    }
+
+    fn push_checks(&self, op: &str, checks: &mut Vec<Check>) -> std::result::Result<(), &'static str> {
+        let floor = self.floor();
+        if self.major.is_none() {
+            // `*`, `>=*`, etc. match everything, while `<*` or `!=*` can never match.
+            return match op {
+                "" | "=" | ">=" | "=>" | "<=" | "=<" | "~" | "~>" | "^" => Ok(()),
+                _ => Err("wildcard can't be used with this operator"),
+            };
+        }
+
+        match op {
+            "" | "=" => match self.ceiling() {
+                Some(ceiling) => {
+                    checks.push(Check::Ge(floor));
+                    checks.push(Check::Lt(ceiling));
+                }
+                None => checks.push(Check::Eq(floor)),
+            },
+
+            "!=" => match self.ceiling() {
+                Some(ceiling) => checks.push(Check::NotWithin(floor, ceiling)),
+                None => checks.push(Check::Ne(floor)),
+            },
+
+            ">" => match self.ceiling() {
+                Some(ceiling) => checks.push(Check::Ge(ceiling)),
+                None => checks.push(Check::Gt(floor)),
+            },
+
+            ">=" | "=>" => checks.push(Check::Ge(floor)),
+            "<" => checks.push(Check::Lt(floor)),
+            "<=" | "=<" => match self.ceiling() {
+                Some(ceiling) => checks.push(Check::Lt(ceiling)),
+                None => checks.push(Check::Le(floor)),
+            },
+
+            "~" | "~>" => {
+                let major = floor.major;
+                let ceiling = match self.minor {
+                    Some(minor) => Version::new(major, minor + 1, 0),
+                    None => Version::new(major + 1, 0, 0),
+                };
+
+                checks.push(Check::Ge(floor));
+                checks.push(Check::Lt(ceiling));
+            }
+
+            "^" => {
+                let ceiling = match (floor.major, self.minor) {
+                    (0, Some(0)) if self.is_exact() => Version::new(0, 0, floor.patch + 1),
+                    (0, Some(minor)) => Version::new(0, minor + 1, 0),
+                    (major, _) => Version::new(major + 1, 0, 0),
+                };
+
+                checks.push(Check::Ge(floor));
+                checks.push(Check::Lt(ceiling));
+            }
+
+            _ => return Err("unknown operator"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares two versions by SemVer precedence, which ignores build metadata.
+fn cmp_precedence(a: &Version, b: &Version) -> std::cmp::Ordering {
+    (a.major, a.minor, a.patch)
+        .cmp(&(b.major, b.minor, b.patch))
+        .then_with(|| a.pre.cmp(&b.pre))
+}
+
+fn invalid<R: Display>(input: &str, reason: R) -> Error {
+    Error::InvalidVersion {
+        input: input.to_owned(),
+        reason: reason.to_string(),
+    }
+}
+
+impl ChartIndexSpec {
+    /// Parses this chart's `version` field with the same rules that Helm uses.
+    pub fn semver(&self) -> Result<Version> {
+        parse_version(&self.version)
+    }
+}
+
+impl ChartIndexYaml {
+    /// Returns every version of a chart that has a valid version, sorted from the newest to
+    /// the oldest release. Versions that can't be parsed, or that were marked as removed from
+    /// the repository, are skipped.
+    pub fn versions<S: AsRef<str>>(&self, name: S) -> Vec<&ChartIndexSpec> {
+        let mut versions = self
+            .entries
+            .get(name.as_ref())
+            .map(|specs| {
+                specs
+                    .iter()
+                    .filter(|spec| !spec.removed)
+                    .filter_map(|spec| spec.semver().ok().map(|version| (version, spec)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        versions.sort_by(|(a, _), (b, _)| cmp_precedence(b, a));
+        versions.into_iter().map(|(_, spec)| spec).collect()
+    }
+
+    /// Resolves the newest version of a chart that satisfies the given [`VersionConstraint`], using
+    /// Helm's constraint syntax.
+    ///
+    /// ```
+    /// # use charted::models::ChartIndexYaml;
+    /// #
+    /// # fn main() -> charted::Result<()> {
+    /// # let index = ChartIndexYaml::default();
+    /// if let Some(spec) = index.resolve("mychart", ">=1.2, <2")? {
+    ///     println!("resolved mychart@{}", spec.version);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve<N: AsRef<str>, C: AsRef<str>>(&self, name: N, constraint: C) -> Result<Option<&ChartIndexSpec>> {
+        let constraint = constraint.as_ref().parse::<VersionConstraint>()?;
+        Ok(self.resolve_constraint(name, &constraint))
+    }
+
+    /// Same as [`ChartIndexYaml::resolve`], but with a constraint that was already parsed.
+    pub fn resolve_constraint<N: AsRef<str>>(
+        &self,
+        name: N,
+        constraint: &VersionConstraint,
+    ) -> Option<&ChartIndexSpec> {
+        self.versions(name)
+            .into_iter()
+            .find(|spec| spec.semver().map(|v| constraint.matches(&v)).unwrap_or(false))
+    }
+
+    /// Returns the newest stable (non pre-release) version of a chart.
+    pub fn latest<S: AsRef<str>>(&self, name: S) -> Option<&ChartIndexSpec> {
+        self.versions(name)
+            .into_iter()
+            .find(|spec| spec.semver().map(|v| v.pre.is_empty()).unwrap_or(false))
+    }
+
+    /// Returns the newest version of a chart, including pre-release versions.
+    pub fn latest_prerelease<S: AsRef<str>>(&self, name: S) -> Option<&ChartIndexSpec> {
+        self.versions(name).into_iter().next()
+    }
+
+    /// Sorts every chart's versions from the newest to the oldest release, like Helm does before
+    /// writing an `index.yaml`. Versions that can't be parsed are moved to the end.
+    pub fn sort_entries(&mut self) {
+        for specs in self.entries.values_mut() {
+            specs.sort_by(|a, b| match (a.semver(), b.semver()) {
+                (Ok(a), Ok(b)) => cmp_precedence(&b, &a),
+                (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+                (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+                (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(constraint, version, matches)`, which follow the semantics of Masterminds/semver's
+    /// `Constraints.Check`.
+    const CASES: &[(&str, &str, bool)] = &[
+        // exact versions and wildcards
+        ("1.2.3", "1.2.3", true),
+        ("=1.2.3", "1.2.4", false),
+        ("v1.2", "1.2.9", true),
+        ("1.2", "1.3.0", false),
+        ("1.2.x", "1.2.0", true),
+        ("1.2.x", "1.3.0", false),
+        ("1.x", "1.9.9", true),
+        ("1.x", "2.0.0", false),
+        ("*", "0.0.1", true),
+        ("x", "100.0.0", true),
+        ("!=1.2.3", "1.2.4", true),
+        ("!=1.2.3", "1.2.3", false),
+        ("!=1.2.x", "1.2.7", false),
+        ("!=1.2.x", "1.3.0", true),
+        // comparisons
+        (">1.2.3", "1.2.4", true),
+        (">1.2.3", "1.2.3", false),
+        (">1.2", "1.2.9", false),
+        (">1.2", "1.3.0", true),
+        (">=1.2.3", "1.2.3", true),
+        ("<1.2.3", "1.2.2", true),
+        ("<1.2", "1.2.0", false),
+        ("<=1.2.3", "1.2.3", true),
+        ("<=1.2", "1.2.9", true),
+        ("<=1.2", "1.3.0", false),
+        (">= 1.2, < 2", "1.9.0", true),
+        (">=1.2 <2", "2.0.0", false),
+        ("<1 || >=3", "2.0.0", false),
+        ("<1 || >=3", "3.1.0", true),
+        // tilde
+        ("~1.2.3", "1.2.9", true),
+        ("~1.2.3", "1.3.0", false),
+        ("~1.2.3", "1.2.2", false),
+        ("~1.2", "1.2.0", true),
+        ("~1.2", "1.3.0", false),
+        ("~>1.2.x", "1.2.5", true),
+        ("~1", "1.9.0", true),
+        ("~1", "2.0.0", false),
+        ("~0.0.3", "0.0.9", true),
+        ("~0.0.3", "0.1.0", false),
+        ("~*", "5.0.0", true),
+        // caret
+        ("^1.2.3", "1.9.9", true),
+        ("^1.2.3", "2.0.0", false),
+        ("^1.2.3", "1.2.2", false),
+        ("^1.2", "1.2.0", true),
+        ("^1", "1.9.0", true),
+        ("^1", "2.0.0", false),
+        ("^0.2.3", "0.2.9", true),
+        ("^0.2.3", "0.3.0", false),
+        ("^0.2", "0.2.0", true),
+        ("^0.2", "0.3.0", false),
+        ("^0.0.3", "0.0.3", true),
+        ("^0.0.3", "0.0.4", false),
+        ("^0.0.3", "0.1.0", false),
+        ("^0.0", "0.0.9", true),
+        ("^0.0", "0.1.0", false),
+        ("^0.0.x", "0.0.9", true),
+        ("^0.0.x", "0.1.0", false),
+        ("^0", "0.9.0", true),
+        ("^0", "1.0.0", false),
+        ("^*", "1.0.0", true),
+        // hyphen ranges
+        ("1.2 - 1.4.5", "1.2.0", true),
+        ("1.2 - 1.4.5", "1.4.5", true),
+        ("1.2 - 1.4.5", "1.4.6", false),
+        ("1.2.3 - 2.3", "2.3.9", true),
+        ("1.2.3 - 2.3", "2.4.0", false),
+        ("1.2.3 - 2", "2.9.9", true),
+        ("1.2.3 - 2", "1.2.2", false),
+        // pre-releases only match when the group asks for them
+        ("1.2.3", "1.2.3-beta", false),
+        (">=1.2", "1.3.0-rc.1", false),
+        ("^1.2.3", "1.5.0-beta", false),
+        (">=1.2.3-0", "1.2.3-beta", true),
+        (">=1.2.3-alpha", "1.2.3-beta", true),
+        (">=1.2.3-beta", "1.2.3-alpha", false),
+        (">=1.20.0-0", "1.26.3-eks-a1b2c3", true),
+        (">=1.20.0-0, <2", "1.26.3-gke.100", true),
+        (">=1.20.0-0 || >=3", "1.26.3-gke.100", true),
+        ("<1 || >=3", "3.1.0-rc.1", false),
+        ("^1.2.3-beta.2", "1.2.3-beta.3", true),
+        ("^1.2.3-beta.2", "1.2.3-beta.1", false),
+        ("~1.2.3-beta.2", "1.2.3", true),
+        ("1.2.3-beta.1 - 1.2.3", "1.2.3-beta.2", true),
+        // build metadata is ignored
+        ("1.2.3", "1.2.3+build.5", true),
+        ("=1.2.3+build.1", "1.2.3+build.2", true),
+    ];
+
+    #[test]
+    fn constraints() {
+        let mut failures = vec![];
+        for (constraint, version, expected) in CASES {
+            let parsed = constraint
+                .parse::<VersionConstraint>()
+                .unwrap_or_else(|e| panic!("`{constraint}` didn't parse: {e}"));
+
+            let version = parse_version(version).unwrap();
+            if parsed.matches(&version) != *expected {
+                failures.push(format!("`{constraint}` matching `{version}` should be {expected}"));
+            }
+        }
+
+        assert!(failures.is_empty(), "{failures:#?}");
+    }
+
+    #[test]
+    fn invalid_constraints() {
+        for constraint in [
+            "",
+            "||",
+            ">=",
+            "1.2 -",
+            "<*",
+            "1.x.3",
+            "1.2.3.4",
+            "1.2.3beta",
+            "%1.2",
+            "latest",
+        ] {
+            assert!(
+                constraint.parse::<VersionConstraint>().is_err(),
+                "`{constraint}` should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn skips_removed_versions() {
+        let mut index = ChartIndexYaml::default();
+        index.entries.insert(
+            "hello".into(),
+            [
+                ("1.1.0", true),
+                ("1.0.0", false),
+                ("1.2.0-rc.1", true),
+                ("0.9.0", false),
+            ]
+            .into_iter()
+            .map(|(version, removed)| {
+                ChartIndexSpec::builder("hello", version)
+                    .removed(removed)
+                    .build()
+                    .unwrap()
+            })
+            .collect(),
+        );
+
+        let version = |spec: Option<&ChartIndexSpec>| spec.map(|spec| spec.version.clone());
+        assert_eq!(
+            index
+                .versions("hello")
+                .iter()
+                .map(|spec| &spec.version)
+                .collect::<Vec<_>>(),
+            ["1.0.0", "0.9.0"]
+        );
+
+        assert_eq!(version(index.resolve("hello", "^1").unwrap()), Some("1.0.0".into()));
+        assert_eq!(version(index.resolve("hello", ">=1.1.0-0").unwrap()), None);
+        assert_eq!(version(index.latest("hello")), Some("1.0.0".into()));
+        assert_eq!(version(index.latest_prerelease("hello")), Some("1.0.0".into()));
+    }
+
+    #[test]
+    fn versions() {
+        assert_eq!(parse_version("1").unwrap(), Version::new(1, 0, 0));
+        assert_eq!(parse_version("V2.1").unwrap(), Version::new(2, 1, 0));
+        assert_eq!(parse_version("1.2.3+meta").unwrap().build.as_str(), "meta");
+        assert!(parse_version("1.x").is_err());
+        assert!(parse_version("*").is_err());
+        assert!(parse_version("").is_err());
+    }
+}
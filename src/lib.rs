@@ -30,16 +30,42 @@
 //! Read the [`APIClient`] struct for more information on how to use this struct to make requests
 //! to the API server.
 //!
+//! ### Cargo Features
+//! Every feature except `gpg`, `blocking` and `testing` is enabled by default: `native-tls` and `rustls-tls`
+//! select the TLS implementation, `basic-auth` enables the `BasicAuthStrategy`, `cache` enables caching
+//! responses with the `cache` module, `yaml` enables the chart models and the `index` module, `indexes`,
+//! `repositories` and `search` enable their API containers (there is no feature per endpoint),
+//! `chart-packaging` enables packaging charts in the `chart` module, `gpg` enables signing and verifying
+//! provenance files with the `gpg` binary, `blocking` enables the synchronous client, and `testing`
+//! enables a fake server, fixtures and record/replay cassettes for testing code that uses this crate.
+//!
 //! [`APIClient`]: struct.APIClient.html
 
+pub mod api;
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "yaml")]
+pub mod chart;
+#[cfg(feature = "yaml")]
+pub mod index;
+#[cfg(all(feature = "indexes", feature = "chart-packaging"))]
+pub mod mirror;
 pub mod models;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 mod builder;
 mod client;
+#[cfg(any(feature = "indexes", feature = "repositories", feature = "search"))]
 mod containers;
+mod endpoints;
 mod error;
 
 pub use builder::*;
 pub use client::*;
+#[cfg(any(feature = "indexes", feature = "repositories", feature = "search"))]
+pub use containers::*;
 pub use error::*;
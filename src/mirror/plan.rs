@@ -0,0 +1,151 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::path::Path;
+
+use crate::{
+    chart::chart_digest_file,
+    models::{ChartIndexSpec, ChartIndexYaml},
+    Result,
+};
+
+/// Represents why a chart version needs to be mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The chart version doesn't exist in the target.
+    New,
+
+    /// The chart version exists in the target, but its digest is different.
+    Changed,
+}
+
+/// Represents a chart version that needs to be mirrored.
+#[derive(Debug, Clone)]
+pub struct PlannedChart {
+    /// The chart version from the source index.
+    pub spec: ChartIndexSpec,
+
+    /// Why the chart version needs to be mirrored.
+    pub kind: ChangeKind,
+}
+
+/// Represents the differences between a source chart repository and a target, which is what
+/// a [`Mirror`][super::Mirror] uses to only download what is new or changed. Chart versions that
+/// are marked as removed in the source are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct MirrorPlan {
+    /// The chart versions that need to be mirrored.
+    pub download: Vec<PlannedChart>,
+
+    /// The chart versions that are already in the target.
+    pub up_to_date: Vec<ChartIndexSpec>,
+}
+
+impl MirrorPlan {
+    /// Compares a source index with the index of another chart repository, like another
+    /// **charted-server** instance. Chart versions are matched by their name and version, and are
+    /// only considered changed if both of them have a digest.
+    pub fn against_index(source: &ChartIndexYaml, target: &ChartIndexYaml) -> MirrorPlan {
+        MirrorPlan::compare(source, |spec| {
+            let existing = target
+                .entries
+                .get(&spec.name)
+                .and_then(|specs| specs.iter().find(|existing| existing.version == spec.version));
+
+            Ok(match existing {
+                None => Some(ChangeKind::New),
+                Some(existing) => match (&spec.digest, &existing.digest) {
+                    (Some(expected), Some(actual)) if !digest_eq(expected, actual) => Some(ChangeKind::Changed),
+                    _ => None,
+                },
+            })
+        })
+        .expect("comparing two indexes never fails")
+    }
+
+    /// Compares a source index with a directory that holds chart packages as `<name>-<version>.tgz`, which
+    /// is how [`IndexesContainer::download_to`][crate::IndexesContainer::download_to] saves them. Packages
+    /// that already exist are only considered changed if their digest doesn't match the source index.
+    ///
+    /// This reads every package that exists synchronously, so async code should call it on a blocking
+    /// thread (i.e, with `tokio::task::spawn_blocking`), like [`Mirror::sync`][super::Mirror::sync] does.
+    pub fn against_directory<P: AsRef<Path>>(source: &ChartIndexYaml, dir: P) -> Result<MirrorPlan> {
+        let dir = dir.as_ref();
+        MirrorPlan::compare(source, |spec| {
+            // packages that can't be saved are planned anyway, so they're reported as a failure
+            let Ok(file_name) = package_name(spec) else {
+                return Ok(Some(ChangeKind::New));
+            };
+
+            let path = dir.join(file_name);
+            if !path.is_file() {
+                return Ok(Some(ChangeKind::New));
+            }
+
+            Ok(match &spec.digest {
+                Some(expected) if !digest_eq(expected, &chart_digest_file(&path)?) => Some(ChangeKind::Changed),
+                _ => None,
+            })
+        })
+    }
+
+    /// Checks if there is nothing to mirror.
+    pub fn is_empty(&self) -> bool {
+        self.download.is_empty()
+    }
+
+    fn compare<F: FnMut(&ChartIndexSpec) -> Result<Option<ChangeKind>>>(
+        source: &ChartIndexYaml,
+        mut changed: F,
+    ) -> Result<MirrorPlan> {
+        let mut names = source.entries.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut plan = MirrorPlan::default();
+        for name in names.into_iter() {
+            for spec in source.entries[name].iter().filter(|spec| !spec.removed) {
+                match changed(spec)? {
+                    Some(kind) => plan.download.push(PlannedChart {
+                        spec: spec.clone(),
+                        kind,
+                    }),
+
+                    None => plan.up_to_date.push(spec.clone()),
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+/// Returns the file name that a chart package is saved as, or an error if the chart's name or
+/// version would escape the mirror's directory.
+pub(crate) fn package_name(spec: &ChartIndexSpec) -> Result<String> {
+    crate::containers::package_file_name(spec)
+}
+
+fn digest_eq(expected: &str, actual: &str) -> bool {
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+    let actual = actual.strip_prefix("sha256:").unwrap_or(actual);
+
+    expected.eq_ignore_ascii_case(actual)
+}
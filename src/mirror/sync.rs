@@ -0,0 +1,313 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::HashSet,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use futures_util::{stream, StreamExt};
+use log::*;
+
+use super::{package_name, ChangeKind, MirrorPlan};
+use crate::{containers::blocking, models::ChartIndexYaml, Error, IndexesContainer, Result};
+
+/// Represents a chart version that was mirrored.
+#[derive(Debug, Clone)]
+pub struct MirroredChart {
+    /// The name of the chart.
+    pub name: String,
+
+    /// The version of the chart.
+    pub version: String,
+
+    /// Why the chart version was mirrored.
+    pub kind: ChangeKind,
+
+    /// The path of where the chart package was saved.
+    pub path: PathBuf,
+}
+
+/// Represents a chart version that couldn't be mirrored.
+#[derive(Debug)]
+pub struct MirrorFailure {
+    /// The name of the chart.
+    pub name: String,
+
+    /// The version of the chart.
+    pub version: String,
+
+    /// Why the chart version couldn't be mirrored.
+    pub error: Error,
+}
+
+/// Represents the result of [`Mirror::sync`] or [`Mirror::apply`].
+#[derive(Debug, Default)]
+pub struct MirrorSummary {
+    /// The chart versions that were downloaded.
+    pub downloaded: Vec<MirroredChart>,
+
+    /// How many chart versions were already mirrored.
+    pub up_to_date: usize,
+
+    /// The chart versions that couldn't be downloaded, which are retried on the next sync.
+    pub failed: Vec<MirrorFailure>,
+}
+
+impl MirrorSummary {
+    /// Checks if every chart version was mirrored.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Represents a mirror of a user or organization's chart repository into a local directory. Chart
+/// packages are downloaded concurrently, their digests are verified against the index, and they
+/// are only written once they were verified, so an interrupted sync can be resumed by running it
+/// again: packages that were already mirrored are skipped.
+///
+/// Only local directories can be written to, since **charted-server**'s API doesn't have an endpoint
+/// to upload chart packages. Another **charted-server** instance can still be compared against with
+/// [`Mirror::plan_against`], and the packages it's missing can be downloaded with [`Mirror::apply`]
+/// and published to it from there.
+///
+/// ## Example
+/// ```no_run
+/// # use charted::{mirror::Mirror, APIClient};
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = APIClient::default_with_url("https://charts.noelware.org/api");
+/// let summary = Mirror::new(client.indexes())
+///     .concurrency(8)
+///     .sync("noel", "./mirror")
+///     .await?;
+///
+/// println!("downloaded {} charts, {} were up to date", summary.downloaded.len(), summary.up_to_date);
+/// for failure in summary.failed.iter() {
+///     eprintln!("unable to mirror {}@{}: {}", failure.name, failure.version, failure.error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    indexes: IndexesContainer,
+    concurrency: usize,
+}
+
+impl Mirror {
+    /// Creates a new [`Mirror`] that downloads from the given [`IndexesContainer`].
+    pub fn new(indexes: IndexesContainer) -> Mirror {
+        Mirror {
+            indexes,
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many chart packages can be downloaded at the same time, which is `4` by default.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Mirrors a user or organization's chart repository into the given directory. Once every package
+    /// was downloaded, an `index.yaml` is written into the directory with the chart versions that were
+    /// mirrored, so the directory can be served as a chart repository itself.
+    pub async fn sync<N: AsRef<str>, P: AsRef<Path>>(&self, name: N, dir: P) -> Result<MirrorSummary> {
+        let (name, dir) = (name.as_ref(), dir.as_ref());
+        let source = self.source(name).await?;
+
+        tokio::fs::create_dir_all(dir).await?;
+        let plan = {
+            let (source, dir) = (source.clone(), dir.to_path_buf());
+
+            // hashing the packages that are already mirrored is blocking I/O
+            blocking(move || MirrorPlan::against_directory(&source, dir)).await?
+        };
+
+        let summary = self.apply(name, &plan, dir).await;
+        write_index(&source, &summary, dir).await?;
+
+        Ok(summary)
+    }
+
+    /// Compares a user or organization's chart repository with one on another **charted-server**
+    /// instance (or another user or organization on the same one), where `target_name` is the
+    /// user or organization in `target`. If the target doesn't have a chart repository, every chart
+    /// version is planned as new.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use charted::{mirror::Mirror, APIClient};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let source = APIClient::default_with_url("https://charts.noelware.org/api");
+    /// let target = APIClient::default_with_url("https://charts.internal.example.com/api");
+    /// let plan = Mirror::new(source.indexes())
+    ///     .plan_against("noel", &target.indexes(), "noel")
+    ///     .await?;
+    ///
+    /// for planned in plan.download.iter() {
+    ///     println!("{}@{} is {:?}", planned.spec.name, planned.spec.version, planned.kind);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan_against<N: AsRef<str>, T: AsRef<str>>(
+        &self,
+        name: N,
+        target: &IndexesContainer,
+        target_name: T,
+    ) -> Result<MirrorPlan> {
+        let source = self.source(name.as_ref()).await?;
+        let target = target.get(target_name.as_ref()).await?.unwrap_or_default();
+
+        Ok(MirrorPlan::against_index(&source, &target))
+    }
+
+    /// Downloads the chart versions in the plan into the given directory, where `name` is the user or
+    /// organization that the chart versions are from.
+    pub async fn apply<N: AsRef<str>, P: AsRef<Path>>(&self, name: N, plan: &MirrorPlan, dir: P) -> MirrorSummary {
+        let (name, dir) = (name.as_ref(), dir.as_ref());
+        let results = stream::iter(plan.download.iter())
+            .map(|planned| async move {
+                debug!(
+                    "mirroring {}@{} into {}",
+                    planned.spec.name,
+                    planned.spec.version,
+                    dir.display()
+                );
+                let result = self.indexes.download_to(name, &planned.spec, dir).await;
+
+                (planned, result)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut summary = MirrorSummary {
+            up_to_date: plan.up_to_date.len(),
+            ..Default::default()
+        };
+
+        for (planned, result) in results.into_iter() {
+            match result {
+                Ok(path) => summary.downloaded.push(MirroredChart {
+                    name: planned.spec.name.clone(),
+                    version: planned.spec.version.clone(),
+                    kind: planned.kind,
+                    path,
+                }),
+
+                Err(error) => {
+                    warn!(
+                        "unable to mirror {}@{}: {error}",
+                        planned.spec.name, planned.spec.version
+                    );
+                    summary.failed.push(MirrorFailure {
+                        name: planned.spec.name.clone(),
+                        version: planned.spec.version.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+
+        summary
+            .downloaded
+            .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        summary
+    }
+
+    async fn source(&self, name: &str) -> Result<ChartIndexYaml> {
+        self.indexes
+            .get(name)
+            .await?
+            .ok_or_else(|| Error::String(format!("user or organization '{name}' doesn't have a chart repository")))
+    }
+}
+
+/// Writes the source index into the mirror's directory, keeping only the chart versions that
+/// exist in it and pointing their URLs to the packages next to the index.
+async fn write_index(source: &ChartIndexYaml, summary: &MirrorSummary, dir: &Path) -> Result<()> {
+    let mut index = ChartIndexYaml {
+        generated: Utc::now(),
+        ..source.clone()
+    };
+
+    for specs in index.entries.values_mut() {
+        specs.retain(|spec| {
+            !spec.removed
+                && !summary
+                    .failed
+                    .iter()
+                    .any(|failure| failure.name == spec.name && failure.version == spec.version)
+        });
+
+        // packages with a name that can't be saved were never written
+        specs.retain_mut(|spec| match package_name(spec) {
+            Ok(file_name) => {
+                spec.urls = vec![file_name];
+                true
+            }
+
+            Err(_) => false,
+        });
+    }
+
+    let packages = index
+        .entries
+        .values()
+        .flatten()
+        .map(|spec| spec.urls[0].clone())
+        .collect::<Vec<_>>();
+
+    let missing = {
+        let dir = dir.to_path_buf();
+        blocking(move || {
+            Ok(packages
+                .into_iter()
+                .filter(|file_name| !dir.join(file_name).is_file())
+                .collect::<HashSet<_>>())
+        })
+        .await?
+    };
+
+    for specs in index.entries.values_mut() {
+        specs.retain(|spec| !missing.contains(&spec.urls[0]));
+    }
+
+    index.entries.retain(|_, specs| !specs.is_empty());
+
+    let (contents, dir) = (index.to_yaml()?, dir.to_path_buf());
+    blocking(move || {
+        let mut file = tempfile::NamedTempFile::new_in(&dir)?;
+        file.write_all(contents.as_bytes())?;
+        file.persist(dir.join("index.yaml")).map_err(|e| e.error)?;
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,409 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, Utc};
+use serde_yaml::Value;
+
+use super::{ApiVersion, ChartDependency, ChartIndexSpec, ChartMaintainer, RepositoryType, StringOrImportValue};
+use crate::{Error, Result};
+
+/// Represents a builder for constructing a [`ChartIndexSpec`].
+///
+/// ## Example
+/// ```
+/// # use charted::models::{ChartDependency, ChartIndexSpec, ChartMaintainer};
+/// #
+/// # fn main() -> charted::Result<()> {
+/// let spec = ChartIndexSpec::builder("hello-world", "0.1.0")
+///     .description("Says hello to the world")
+///     .app_version("1.0.0")
+///     .maintainer(ChartMaintainer::builder("Noel").email("cutie@floofy.dev").build()?)
+///     .dependency(ChartDependency::builder("postgresql").version("12.x").build()?)
+///     .url("charts/hello-world-0.1.0.tgz")
+///     .build()?;
+///
+/// assert_eq!(spec.name, "hello-world");
+/// assert_eq!(spec.dependencies[0].name, "postgresql");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChartIndexSpecBuilder {
+    spec: ChartIndexSpec,
+}
+
+impl ChartIndexSpec {
+    /// Returns a [`ChartIndexSpecBuilder`] for a chart with the given name and version.
+    pub fn builder<N: Into<String>, V: Into<String>>(name: N, version: V) -> ChartIndexSpecBuilder {
+        ChartIndexSpecBuilder {
+            spec: ChartIndexSpec {
+                api_version: Some(ApiVersion::default()),
+                name: name.into(),
+                version: version.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ChartIndexSpecBuilder {
+    /// Sets the chart's `apiVersion`, which is `v2` by default.
+    pub fn api_version(&mut self, api_version: ApiVersion) -> &mut Self {
+        self.spec.api_version = Some(api_version);
+        self
+    }
+
+    /// Sets the SemVer constraint of the Kubernetes versions that the chart supports.
+    pub fn kube_version<S: Into<String>>(&mut self, kube_version: S) -> &mut Self {
+        self.spec.kube_version = Some(kube_version.into());
+        self
+    }
+
+    /// Sets the chart's description.
+    pub fn description<S: Into<String>>(&mut self, description: S) -> &mut Self {
+        self.spec.description = Some(description.into());
+        self
+    }
+
+    /// Sets the type of the chart.
+    pub fn chart_type(&mut self, chart_type: RepositoryType) -> &mut Self {
+        self.spec.chart_type = Some(chart_type);
+        self
+    }
+
+    /// Adds a keyword about this chart.
+    pub fn keyword<S: Into<String>>(&mut self, keyword: S) -> &mut Self {
+        self.spec.keywords.push(keyword.into());
+        self
+    }
+
+    /// Adds multiple keywords about this chart.
+    pub fn keywords<I: IntoIterator<Item = S>, S: Into<String>>(&mut self, keywords: I) -> &mut Self {
+        self.spec.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the URL of the chart's homepage.
+    pub fn home<S: Into<String>>(&mut self, home: S) -> &mut Self {
+        self.spec.home = Some(home.into());
+        self
+    }
+
+    /// Adds a URL to the chart's source code.
+    pub fn source<S: Into<String>>(&mut self, source: S) -> &mut Self {
+        self.spec.sources.push(source.into());
+        self
+    }
+
+    /// Adds a dependency of this chart.
+    pub fn dependency(&mut self, dependency: ChartDependency) -> &mut Self {
+        self.spec.dependencies.push(dependency);
+        self
+    }
+
+    /// Adds a maintainer of this chart.
+    pub fn maintainer(&mut self, maintainer: ChartMaintainer) -> &mut Self {
+        self.spec.maintainers.push(maintainer);
+        self
+    }
+
+    /// Sets the URL of the chart's icon.
+    pub fn icon<S: Into<String>>(&mut self, icon: S) -> &mut Self {
+        self.spec.icon = Some(icon.into());
+        self
+    }
+
+    /// Sets the version of the application that this chart deploys.
+    pub fn app_version<S: Into<String>>(&mut self, app_version: S) -> &mut Self {
+        self.spec.app_version = Some(app_version.into());
+        self
+    }
+
+    /// Marks this chart version as deprecated.
+    pub fn deprecated(&mut self, deprecated: bool) -> &mut Self {
+        self.spec.deprecated = Some(deprecated);
+        self
+    }
+
+    /// Adds an annotation to this chart.
+    pub fn annotation<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> &mut Self {
+        self.spec.annotations.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a URL that the chart package can be downloaded from.
+    pub fn url<S: Into<String>>(&mut self, url: S) -> &mut Self {
+        self.spec.urls.push(url.into());
+        self
+    }
+
+    /// Sets the date of when this chart version was added to the index.
+    pub fn created(&mut self, created: DateTime<Utc>) -> &mut Self {
+        self.spec.created = Some(created);
+        self
+    }
+
+    /// Sets the SHA-256 digest of the chart package, encoded in hexadecimal.
+    pub fn digest<S: Into<String>>(&mut self, digest: S) -> &mut Self {
+        self.spec.digest = Some(digest.into());
+        self
+    }
+
+    /// Marks this chart version as removed from the repository.
+    pub fn removed(&mut self, removed: bool) -> &mut Self {
+        self.spec.removed = removed;
+        self
+    }
+
+    /// Sets the values path that enables or disables this chart when it's used as a dependency,
+    /// which is only used by `apiVersion: v1` charts.
+    pub fn condition<S: Into<String>>(&mut self, condition: S) -> &mut Self {
+        self.spec.condition = Some(condition.into());
+        self
+    }
+
+    /// Adds a tag that enables or disables this chart when it's used as a dependency, which is only
+    /// used by `apiVersion: v1` charts. Tags are kept comma-separated, like they are in `index.yaml`.
+    pub fn tag<S: Into<String>>(&mut self, tag: S) -> &mut Self {
+        let tag = tag.into();
+        match self.spec.tags.as_mut() {
+            Some(tags) if !tags.is_empty() => {
+                tags.push(',');
+                tags.push_str(&tag);
+            }
+
+            _ => self.spec.tags = Some(tag),
+        }
+
+        self
+    }
+
+    /// Sets a field that this SDK doesn't know about, which is written to `index.yaml` as-is.
+    pub fn extra<K: Into<String>, V: Into<Value>>(&mut self, key: K, value: V) -> &mut Self {
+        self.spec.extra.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builds the [`ChartIndexSpec`] and resets this builder, so it can be chained after the
+    /// setters above. This returns [`Error::MissingField`] if the chart's name or version is empty.
+    pub fn build(&mut self) -> Result<ChartIndexSpec> {
+        require("ChartIndexSpec", "name", &self.spec.name)?;
+        require("ChartIndexSpec", "version", &self.spec.version)?;
+
+        Ok(std::mem::take(&mut self.spec))
+    }
+}
+
+/// Represents a builder for constructing a [`ChartDependency`].
+#[derive(Debug, Clone, Default)]
+pub struct ChartDependencyBuilder {
+    dependency: ChartDependency,
+}
+
+impl ChartDependency {
+    /// Returns a [`ChartDependencyBuilder`] for a dependency on the chart with the given name.
+    pub fn builder<S: Into<String>>(name: S) -> ChartDependencyBuilder {
+        ChartDependencyBuilder {
+            dependency: ChartDependency {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ChartDependencyBuilder {
+    /// Sets the version (or SemVer constraint) of the dependency.
+    pub fn version<S: Into<String>>(&mut self, version: S) -> &mut Self {
+        self.dependency.version = Some(version.into());
+        self
+    }
+
+    /// Sets the URL of the chart repository that the dependency lives in.
+    pub fn repository<S: Into<String>>(&mut self, repository: S) -> &mut Self {
+        self.dependency.repository = Some(repository.into());
+        self
+    }
+
+    /// Sets the comma-separated values paths that enable or disable the dependency.
+    pub fn condition<S: Into<String>>(&mut self, condition: S) -> &mut Self {
+        self.dependency.condition = Some(condition.into());
+        self
+    }
+
+    /// Adds a tag that enables or disables the dependency.
+    pub fn tag<S: Into<String>>(&mut self, tag: S) -> &mut Self {
+        self.dependency.tags.push(tag.into());
+        self
+    }
+
+    /// Adds an item to the dependency's `import-values`, which can be an exported value's name
+    /// or an [`ImportValue`][super::ImportValue].
+    pub fn import_value<V: Into<StringOrImportValue>>(&mut self, value: V) -> &mut Self {
+        self.dependency.import_values.push(value.into());
+        self
+    }
+
+    /// Sets the alias of the dependency.
+    pub fn alias<S: Into<String>>(&mut self, alias: S) -> &mut Self {
+        self.dependency.alias = Some(alias.into());
+        self
+    }
+
+    /// Builds the [`ChartDependency`] and resets this builder. This returns [`Error::MissingField`]
+    /// if the dependency's name is empty; the version can be left unset for charts that are vendored
+    /// in the `charts/` directory.
+    pub fn build(&mut self) -> Result<ChartDependency> {
+        require("ChartDependency", "name", &self.dependency.name)?;
+        Ok(std::mem::take(&mut self.dependency))
+    }
+}
+
+/// Represents a builder for constructing a [`ChartMaintainer`].
+#[derive(Debug, Clone, Default)]
+pub struct ChartMaintainerBuilder {
+    maintainer: ChartMaintainer,
+}
+
+impl ChartMaintainer {
+    /// Returns a [`ChartMaintainerBuilder`] for a maintainer with the given name.
+    pub fn builder<S: Into<String>>(name: S) -> ChartMaintainerBuilder {
+        ChartMaintainerBuilder {
+            maintainer: ChartMaintainer {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ChartMaintainerBuilder {
+    /// Sets the maintainer's email address.
+    pub fn email<S: Into<String>>(&mut self, email: S) -> &mut Self {
+        self.maintainer.email = Some(email.into());
+        self
+    }
+
+    /// Sets the maintainer's website.
+    pub fn url<S: Into<String>>(&mut self, url: S) -> &mut Self {
+        self.maintainer.url = Some(url.into());
+        self
+    }
+
+    /// Builds the [`ChartMaintainer`] and resets this builder. This returns [`Error::MissingField`]
+    /// if the maintainer's name is empty.
+    pub fn build(&mut self) -> Result<ChartMaintainer> {
+        require("ChartMaintainer", "name", &self.maintainer.name)?;
+        Ok(std::mem::take(&mut self.maintainer))
+    }
+}
+
+fn require(builder: &'static str, field: &'static str, value: &str) -> Result<()> {
+    match value.trim().is_empty() {
+        true => Err(Error::MissingField { builder, field }),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing_field<T: std::fmt::Debug>(result: Result<T>) -> (&'static str, &'static str) {
+        match result {
+            Err(Error::MissingField { builder, field }) => (builder, field),
+            other => panic!("expected a missing field, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_empty_names_and_versions() {
+        assert_eq!(
+            missing_field(ChartIndexSpec::builder("", "0.1.0").build()),
+            ("ChartIndexSpec", "name")
+        );
+
+        assert_eq!(
+            missing_field(ChartIndexSpec::builder("hello", " ").build()),
+            ("ChartIndexSpec", "version")
+        );
+
+        assert_eq!(
+            missing_field(ChartDependency::builder("").version("1.x").build()),
+            ("ChartDependency", "name")
+        );
+
+        assert_eq!(
+            missing_field(ChartMaintainer::builder("").email("cutie@floofy.dev").build()),
+            ("ChartMaintainer", "name")
+        );
+
+        // vendored dependencies don't need a version
+        assert!(ChartDependency::builder("common").build().unwrap().version.is_none());
+    }
+
+    #[test]
+    fn build_resets_the_builder() {
+        let mut builder = ChartIndexSpec::builder("hello", "0.1.0");
+        builder.description("Says hello");
+
+        assert_eq!(builder.build().unwrap().description.as_deref(), Some("Says hello"));
+        assert_eq!(missing_field(builder.build()), ("ChartIndexSpec", "name"));
+    }
+
+    #[test]
+    fn conditions_tags_and_extra_fields() {
+        let spec = ChartIndexSpec::builder("hello", "0.1.0")
+            .api_version(ApiVersion::V1)
+            .condition("hello.enabled")
+            .tag("greetings")
+            .tag("examples")
+            .extra("serverInfo", "charted")
+            .build()
+            .unwrap();
+
+        assert_eq!(spec.condition.as_deref(), Some("hello.enabled"));
+        assert_eq!(spec.tags.as_deref(), Some("greetings,examples"));
+
+        let yaml = serde_yaml::to_value(&spec).unwrap();
+        assert_eq!(yaml["condition"], "hello.enabled");
+        assert_eq!(yaml["tags"], "greetings,examples");
+        assert_eq!(yaml["serverInfo"], "charted");
+    }
+
+    #[test]
+    fn dependencies() {
+        let dependency = ChartDependency::builder("postgresql")
+            .version("12.x")
+            .repository("https://charts.noelware.org/api/indexes/noel")
+            .condition("postgresql.enabled")
+            .tag("database")
+            .import_value("data")
+            .alias("db")
+            .build()
+            .unwrap();
+
+        assert_eq!(dependency.condition.as_deref(), Some("postgresql.enabled"));
+        assert_eq!(dependency.tags, ["database"]);
+        assert_eq!(dependency.import_values[0].as_string(), Some("data"));
+        assert_eq!(dependency.alias.as_deref(), Some("db"));
+    }
+}
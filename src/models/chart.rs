@@ -19,24 +19,36 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{DateTime, Utc};
 use serde::{de::Visitor, Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use super::RepositoryType;
+use crate::{Error, Result};
 
 /// The `apiVersion` field in a **Chart.yaml** file. This should be set to v2 that require atleast Helm 3, charts supporting
-/// previous Helm charts should use v1 and are still installable by Helm 3.
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+/// previous Helm charts should use v1 and are still installable by Helm 3. API versions that this version doesn't know
+/// about are kept in the [`ApiVersion::Other`] variant.
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum ApiVersion {
     /// Charts that support previous Helm versions and are also installable by Helm 3.
-    #[serde(rename = "v1")]
     V1,
 
     /// Charts that support only Helm 3.
-    #[serde(rename = "v2")]
     V2,
+
+    /// API version that was added in a newer version of Helm.
+    Other(String),
 }
 
+string_enum!(ApiVersion {
+    V1 => "v1",
+    V2 => "v2",
+});
+
 #[allow(clippy::derivable_impls)]
 impl Default for ApiVersion {
     fn default() -> Self {
@@ -44,58 +56,66 @@ impl Default for ApiVersion {
     }
 }
 
-/// Represents the repository type that **charted-server** supports.
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
-pub enum RepositoryType {
-    #[serde(rename = "application")]
-    Application,
-
-    #[serde(rename = "library")]
-    Library,
-
-    #[serde(rename = "operator")]
-    Operator,
+/// Represents an item in a dependency's `import-values`, which is either the name of a value that the
+/// subchart exports (which imports `exports.<name>` into the parent's root), or a pair of child and parent
+/// paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum StringOrImportValue {
+    /// The name of a value in the subchart's `exports` mapping.
+    String(String),
+
+    /// A pair of the path in the subchart's values and the path in the parent's values.
+    Import(ImportValue),
 }
 
-#[allow(clippy::derivable_impls)]
-impl Default for RepositoryType {
-    fn default() -> Self {
-        RepositoryType::Application
+impl StringOrImportValue {
+    /// Returns the exported value's name if this is the string form.
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            StringOrImportValue::String(string) => Some(string.as_str()),
+            StringOrImportValue::Import(_) => None,
+        }
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct StringOrImportValue {
-    string: Option<String>,
-    import_value: Option<ImportValue>,
-}
+    /// Returns the child and parent paths if this is the map form.
+    pub fn as_import_value(&self) -> Option<&ImportValue> {
+        match self {
+            StringOrImportValue::String(_) => None,
+            StringOrImportValue::Import(import) => Some(import),
+        }
+    }
 
-impl StringOrImportValue {
-    pub fn string(&self) -> Option<String> {
-        self.string.clone()
+    /// Returns the child and parent paths that this item imports, where the string form is a
+    /// shorthand for importing `exports.<name>` into the root (`.`) of the parent's values.
+    pub fn to_import_value(&self) -> ImportValue {
+        match self {
+            StringOrImportValue::String(string) => ImportValue::new(format!("exports.{string}"), "."),
+            StringOrImportValue::Import(import) => import.clone(),
+        }
     }
+}
 
-    pub fn import_value(&self) -> Option<ImportValue> {
-        self.import_value.clone()
+impl From<String> for StringOrImportValue {
+    fn from(value: String) -> Self {
+        StringOrImportValue::String(value)
     }
 }
 
-impl Serialize for StringOrImportValue {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if let Some(string) = self.string.clone() {
-            return serializer.serialize_str(string.as_str());
-        }
+impl From<&str> for StringOrImportValue {
+    fn from(value: &str) -> Self {
+        StringOrImportValue::String(value.to_owned())
+    }
+}
 
-        let import_value = self.import_value.clone().expect("missing import-value");
-        import_value.serialize(serializer)
+impl From<ImportValue> for StringOrImportValue {
+    fn from(value: ImportValue) -> Self {
+        StringOrImportValue::Import(value)
     }
 }
 
 impl<'de> Deserialize<'de> for StringOrImportValue {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
@@ -111,35 +131,29 @@ impl<'de> Visitor<'de> for StringOrImportValueVisitor {
         formatter.write_str("String or ImportValue")
     }
 
-    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(StringOrImportValue {
-            string: Some(v),
-            import_value: None,
-        })
+        Ok(StringOrImportValue::String(v))
     }
 
-    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(StringOrImportValue {
-            string: Some(v.to_owned()),
-            import_value: None,
-        })
+        Ok(StringOrImportValue::String(v.to_owned()))
     }
 
-    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
     where
         A: serde::de::MapAccess<'de>,
     {
         let mut child = None;
         let mut parent = None;
 
-        while let Some(key) = map.next_key()? {
-            match key {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
                 "child" => {
                     if child.is_some() {
                         return Err(serde::de::Error::duplicate_field("child"));
@@ -156,7 +170,7 @@ impl<'de> Visitor<'de> for StringOrImportValueVisitor {
                     parent = Some(map.next_value()?);
                 }
 
-                _ => return Err(serde::de::Error::unknown_field(key, &["child", "parent"])),
+                _ => return Err(serde::de::Error::unknown_field(&key, &["child", "parent"])),
             }
         }
 
@@ -168,17 +182,14 @@ impl<'de> Visitor<'de> for StringOrImportValueVisitor {
             return Err(serde::de::Error::missing_field("child"));
         }
 
-        Ok(StringOrImportValue {
-            string: None,
-            import_value: Some(ImportValue {
-                parent: parent.unwrap(),
-                child: child.unwrap(),
-            }),
-        })
+        Ok(StringOrImportValue::Import(ImportValue {
+            parent: parent.unwrap(),
+            child: child.unwrap(),
+        }))
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub struct ImportValue {
     /// The source key of the values to be imported
     pub child: String,
@@ -187,15 +198,101 @@ pub struct ImportValue {
     pub parent: String,
 }
 
+impl ImportValue {
+    /// Creates a new [`ImportValue`] that imports the `child` path of a subchart's values into
+    /// the `parent` path of the parent chart's values.
+    pub fn new<C: Into<String>, P: Into<String>>(child: C, parent: P) -> ImportValue {
+        ImportValue {
+            child: child.into(),
+            parent: parent.into(),
+        }
+    }
+}
+
 /// The index file is a yaml file called `index.yaml`. It contains some metadata about the package,
 /// including the contents of a chart's `Chart.yaml` file. A valid chart repository must have an index file.
 /// The index file contains information about each chart in the chart repository.
 /// The `helm repo index` command will generate an index file based on a given local directory that contains packaged charts.
+///
+/// Use [`ChartIndexYaml::from_yaml`] and [`ChartIndexYaml::to_yaml`] to read and write an `index.yaml` that
+/// is compatible with the ones Helm generates.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ChartIndexYaml {
+    /// The version of the index file format, which is always `v1`.
+    #[serde(rename = "apiVersion")]
     pub api_version: String,
+
+    /// All the charts in this repository, keyed by the chart's name.
+    #[serde(default)]
     pub entries: HashMap<String, Vec<ChartIndexSpec>>,
+
+    /// The date of when this index file was generated.
+    #[serde(with = "super::time")]
     pub generated: DateTime<Utc>,
+
+    /// List of public keys (in ASCII-armored format) that can be used to verify
+    /// the provenance files of the charts in this repository.
+    #[serde(rename = "publicKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub public_keys: Vec<String>,
+
+    /// List of annotations keyed by name and value.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub annotations: HashMap<String, String>,
+
+    /// Fields that this SDK doesn't know about, which are kept so they can be written back as-is.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl ChartIndexYaml {
+    /// Parses an `index.yaml` file.
+    pub fn from_yaml<S: AsRef<str>>(contents: S) -> Result<ChartIndexYaml> {
+        serde_yaml::from_str(contents.as_ref()).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: contents.as_ref().to_owned(),
+        })
+    }
+
+    /// Serializes this index into YAML the same way Helm does, where every mapping's keys
+    /// are sorted alphabetically.
+    pub fn to_yaml(&self) -> Result<String> {
+        let value = serde_yaml::to_value(self).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })?;
+
+        serde_yaml::to_string(&sort_keys(value)).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })
+    }
+}
+
+/// Recursively sorts every mapping's keys, since Helm marshals YAML through JSON objects.
+pub(crate) fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Mapping(mapping) => {
+            let mut entries = mapping
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)))
+                .collect::<Vec<_>>();
+
+            entries.sort_by(|(a, _), (b, _)| match (a.as_str(), b.as_str()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                _ => std::cmp::Ordering::Equal,
+            });
+
+            Value::Mapping(entries.into_iter().collect::<Mapping>())
+        }
+
+        Value::Sequence(seq) => Value::Sequence(seq.into_iter().map(sort_keys).collect()),
+        Value::Tagged(mut tagged) => {
+            tagged.value = sort_keys(tagged.value);
+            Value::Tagged(tagged)
+        }
+
+        value => value,
+    }
 }
 
 /// In Helm, one chart may depend on any number of other charts. These dependencies can be dynamically linked using the
@@ -220,16 +317,21 @@ pub struct ChartDependency {
     pub condition: Option<String>,
 
     /// Tags that can be used to group charts together.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 
     /// Import values holds the mapping of source values to parent keys to be imported. Each
     /// item can be a string or pair of child/parent sublist items.
-    #[serde(rename = "import-values")]
+    #[serde(rename = "import-values", default, skip_serializing_if = "Vec::is_empty")]
     pub import_values: Vec<StringOrImportValue>,
 
     /// Alias to be used for the chart. Useful when you have to add the same chart multiple times
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alias: Option<String>,
+
+    /// Fields that this SDK doesn't know about, which are kept so they can be written back as-is.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
 }
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
@@ -250,7 +352,8 @@ pub struct ChartMaintainer {
 pub struct ChartIndexSpec {
     /// The apiVersion field should be v2 for Helm charts that require at least Helm 3. Charts supporting previous
     /// Helm versions have an apiVersion set to v1 and are still installable by Helm 3.
-    pub api_version: ApiVersion,
+    #[serde(rename = "apiVersion", skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<ApiVersion>,
 
     /// The name of the chart
     pub name: String,
@@ -272,7 +375,7 @@ pub struct ChartIndexSpec {
     pub chart_type: Option<RepositoryType>,
 
     /// A list of keywords about this project
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub keywords: Vec<String>,
 
     /// The URL of this Helm chart's homepage.
@@ -280,17 +383,17 @@ pub struct ChartIndexSpec {
     pub home: Option<String>,
 
     /// A list of URLs to the source code for the Helm chart.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub sources: Vec<String>,
 
     /// In Helm, one chart may depend on any number of other charts. These dependencies can be dynamically linked using the dependencies'
     /// field in Chart.yaml or brought in to the charts/ directory and managed manually. The charts required by the current chart are defined as a list in
     /// the dependencies field.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<ChartDependency>,
 
     /// List of maintainers that maintain this Helm chart
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub maintainers: Vec<ChartMaintainer>,
 
     /// A URL, SVG icon, or image to be used as the repository's icon.
@@ -316,5 +419,165 @@ pub struct ChartIndexSpec {
     pub deprecated: Option<bool>,
 
     /// List of annotations keyed by name and value.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub annotations: HashMap<String, String>,
+
+    /// A YAML path that resolves to a [`bool`], used for enabling or disabling this chart when
+    /// it is used as a dependency. This is only used by `apiVersion: v1` charts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /// Comma-separated tags used to group charts for enabling or disabling them when this chart is used
+    /// as a dependency. This is only used by `apiVersion: v1` charts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+
+    /// List of URLs that the chart package for this version can be downloaded from. Relative
+    /// URLs are resolved against the URL of the `index.yaml` itself.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+
+    /// The date of when this chart version was added to the index.
+    #[serde(default, with = "super::time::option", skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Utc>>,
+
+    /// Whether if this chart version was removed from the repository.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+
+    /// The SHA-256 digest of the chart package, encoded in hexadecimal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    /// Fields that this SDK doesn't know about, which are kept so they can be written back as-is.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// Represents a chart's `Chart.yaml` file, which holds the metadata about a chart that is packaged
+/// alongside its templates and values. Use [`ChartYaml::validate`] to check if it would be accepted by Helm.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChartYaml {
+    /// The apiVersion field should be v2 for Helm charts that require at least Helm 3. Charts supporting previous
+    /// Helm versions have an apiVersion set to v1 and are still installable by Helm 3. This is required.
+    #[serde(rename = "apiVersion", skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<ApiVersion>,
+
+    /// The name of the chart, this is required.
+    #[serde(default)]
+    pub name: String,
+
+    /// A valid SemVer 2 version key, this is required.
+    #[serde(default)]
+    pub version: String,
+
+    /// The optional kubeVersion field can define SemVer constraints on supported Kubernetes versions.
+    #[serde(rename = "kubeVersion", skip_serializing_if = "Option::is_none")]
+    pub kube_version: Option<String>,
+
+    /// Single-sentence description of this Helm chart
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The type of the chart, Helm treats a missing type as an application chart.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub chart_type: Option<RepositoryType>,
+
+    /// A list of keywords about this project
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+
+    /// The URL of this Helm chart's homepage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home: Option<String>,
+
+    /// A list of URLs to the source code for the Helm chart.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+
+    /// List of charts that this chart depends on, which is only supported by `apiVersion: v2` charts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<ChartDependency>,
+
+    /// List of maintainers that maintain this Helm chart
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintainers: Vec<ChartMaintainer>,
+
+    /// A URL, SVG icon, or image to be used as the chart's icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// The version of the application that this chart deploys, which has no impact
+    /// on chart version calculations.
+    #[serde(rename = "appVersion", skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+
+    /// Whether this chart is deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// List of annotations keyed by name and value.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub annotations: HashMap<String, String>,
+
+    /// A YAML path that resolves to a [`bool`], only used by `apiVersion: v1` charts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /// Comma-separated tags to group charts together, only used by `apiVersion: v1` charts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+
+    /// Fields that this SDK doesn't know about, which are kept so they can be written back as-is.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl ChartYaml {
+    /// Parses a `Chart.yaml` file.
+    pub fn from_yaml<S: AsRef<str>>(contents: S) -> Result<ChartYaml> {
+        serde_yaml::from_str(contents.as_ref()).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: contents.as_ref().to_owned(),
+        })
+    }
+
+    /// Serializes this `Chart.yaml` into YAML, with every mapping's keys sorted alphabetically.
+    pub fn to_yaml(&self) -> Result<String> {
+        let value = serde_yaml::to_value(self).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })?;
+
+        serde_yaml::to_string(&sort_keys(value)).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })
+    }
+}
+
+impl From<ChartYaml> for ChartIndexSpec {
+    fn from(chart: ChartYaml) -> ChartIndexSpec {
+        ChartIndexSpec {
+            api_version: chart.api_version,
+            name: chart.name,
+            version: chart.version,
+            kube_version: chart.kube_version,
+            description: chart.description,
+            chart_type: chart.chart_type,
+            keywords: chart.keywords,
+            home: chart.home,
+            sources: chart.sources,
+            dependencies: chart.dependencies,
+            maintainers: chart.maintainers,
+            icon: chart.icon,
+            app_version: chart.app_version,
+            deprecated: chart.deprecated,
+            annotations: chart.annotations,
+            condition: chart.condition,
+            tags: chart.tags,
+            extra: chart.extra,
+            ..Default::default()
+        }
+    }
 }
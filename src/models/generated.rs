@@ -19,59 +19,70 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-//! All the responses for the main endpoints (i.e, `/features`) that aren't categorized.
+// This file is generated by `cargo xtask codegen` from `openapi/charted.json`, do not edit it by hand.
+
+//! The models that are generated from the schemas in charted-server's OpenAPI document.
 
 use std::collections::HashMap;
 
 use super::DistributionType;
 
+/// Represents the response object for the `GET /` REST handler.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct MainResponse {
+    /// Documentation URI.
+    pub docs: String,
+
+    /// The message, which will always be "Hello, world!"
+    pub message: String,
+
+    /// You know, for Helm charts?
+    pub tagline: String,
+}
+
 /// Represents the response object for the `GET /info` REST handler.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct InfoResponse {
-    /// The distribution the server is running off from.
-    pub distribution: DistributionType,
+    /// The build date, in ISO-8601 format.
+    pub build_date: String,
 
     /// The commit hash from the Git repository.
     pub commit_hash: String,
 
-    /// The build date, in ISO-8601 format.
-    pub build_date: String,
+    /// The distribution the server is running off from.
+    pub distribution: DistributionType,
 
     /// The product name, which will always be `charted-server`.
     pub product: String,
 
-    /// The version of the server
-    pub version: String,
-
-    /// The vendor that maintains this project, will always be Noelware if
-    /// pulled from official sources.
+    /// The vendor that maintains this project, will always be Noelware if pulled from official sources.
     pub vendor: String,
-}
-
-/// Represents the response object for the `GET /` REST handler.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
-pub struct MainResponse {
-    /// The message, which will always be "Hello, world!"
-    pub message: String,
 
-    /// You know, for Helm charts?
-    pub tagline: String,
-
-    /// Documentation URI.
-    pub docs: String,
+    /// The version of the server
+    pub version: String,
 }
 
 /// Represents the response object for the `GET /features` REST handler.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct FeaturesResponse {
+    /// Whether if the server keeps audit logs.
+    pub audit_logs: bool,
+
     /// Whether if the server is using a local Docker registry.
     pub docker_registry: bool,
 
+    /// The session integrations that the server supports, and whether if they are enabled.
+    pub integrations: HashMap<String, bool>,
+
     /// Whether if the server is invite-only.
     pub is_invite_only: bool,
+
+    /// Whether if users can register on the server.
     pub registrations: bool,
-    pub integrations: HashMap<String, bool>,
-    pub audit_logs: bool,
-    pub webhooks: bool,
+
+    /// Whether if the Search API is enabled.
     pub search: bool,
+
+    /// Whether if the server supports webhooks.
+    pub webhooks: bool,
 }
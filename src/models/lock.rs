@@ -0,0 +1,122 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use super::{sort_keys, ChartDependency, StringOrImportValue};
+use crate::{Error, Result};
+
+/// Represents a chart's `Chart.lock` file, which pins every dependency in `Chart.yaml` to
+/// the exact version that was resolved.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ChartLock {
+    /// The resolved dependencies, which only have their `name`, `repository` and `version` set.
+    #[serde(default)]
+    pub dependencies: Vec<ChartDependency>,
+
+    /// The digest of the requested and the resolved dependencies, see [`ChartLock::compute_digest`].
+    pub digest: String,
+
+    /// The date of when this lock file was generated.
+    #[serde(with = "super::time")]
+    pub generated: DateTime<Utc>,
+}
+
+impl ChartLock {
+    /// Parses a `Chart.lock` file.
+    pub fn from_yaml<S: AsRef<str>>(contents: S) -> Result<ChartLock> {
+        serde_yaml::from_str(contents.as_ref()).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: contents.as_ref().to_owned(),
+        })
+    }
+
+    /// Serializes this lock file into YAML the same way Helm does, where every mapping's keys
+    /// are sorted alphabetically.
+    pub fn to_yaml(&self) -> Result<String> {
+        let value = serde_yaml::to_value(self).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })?;
+
+        serde_yaml::to_string(&sort_keys(value)).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })
+    }
+
+    /// Computes the digest that Helm stores in `Chart.lock`, which is the SHA-256 digest of the requested
+    /// dependencies (from `Chart.yaml`) and the locked dependencies, encoded the same way Helm's
+    /// `json.Marshal` does.
+    pub fn compute_digest(requested: &[ChartDependency], locked: &[ChartDependency]) -> String {
+        let requested = requested.iter().map(GoDependency::from).collect::<Vec<_>>();
+        let locked = locked.iter().map(GoDependency::from).collect::<Vec<_>>();
+
+        // Go escapes HTML characters in strings when marshalling JSON, which only
+        // can appear in string literals (i.e, version constraints like `>=1.2`).
+        let json = serde_json::to_string(&(requested, locked))
+            .expect("dependencies to always be serializable")
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026")
+            .replace('\u{2028}', "\\u2028")
+            .replace('\u{2029}', "\\u2029");
+
+        format!("sha256:{}", hex::encode(Sha256::digest(json.as_bytes())))
+    }
+}
+
+/// Mirrors the field order and `omitempty` rules of Helm's `chart.Dependency` struct.
+#[derive(serde::Serialize)]
+struct GoDependency<'a> {
+    name: &'a str,
+
+    #[serde(skip_serializing_if = "str::is_empty")]
+    version: &'a str,
+    repository: &'a str,
+
+    #[serde(skip_serializing_if = "str::is_empty")]
+    condition: &'a str,
+
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+
+    #[serde(rename = "import-values", skip_serializing_if = "<[_]>::is_empty")]
+    import_values: &'a [StringOrImportValue],
+
+    #[serde(skip_serializing_if = "str::is_empty")]
+    alias: &'a str,
+}
+
+impl<'a> From<&'a ChartDependency> for GoDependency<'a> {
+    fn from(dependency: &'a ChartDependency) -> GoDependency<'a> {
+        GoDependency {
+            name: &dependency.name,
+            version: dependency.version.as_deref().unwrap_or_default(),
+            repository: dependency.repository.as_deref().unwrap_or_default(),
+            condition: dependency.condition.as_deref().unwrap_or_default(),
+            tags: &dependency.tags,
+            import_values: &dependency.import_values,
+            alias: dependency.alias.as_deref().unwrap_or_default(),
+        }
+    }
+}
@@ -19,50 +19,88 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "yaml")]
+mod builders;
+#[cfg(feature = "yaml")]
 mod chart;
-mod main;
+mod generated;
+#[cfg(feature = "yaml")]
+mod lock;
+#[cfg(any(feature = "search", feature = "testing"))]
+mod organization;
+mod repository;
 mod response;
+#[cfg(feature = "search")]
+mod search;
+#[cfg(feature = "yaml")]
+mod time;
+#[cfg(any(feature = "search", feature = "testing"))]
+mod user;
 
+#[cfg(feature = "yaml")]
+pub use builders::*;
+#[cfg(feature = "yaml")]
 pub use chart::*;
-pub use main::*;
+pub use generated::*;
+#[cfg(feature = "yaml")]
+pub use lock::*;
+#[cfg(any(feature = "search", feature = "testing"))]
+pub use organization::*;
+pub use repository::*;
 pub use response::*;
+#[cfg(feature = "search")]
+pub use search::*;
+#[cfg(any(feature = "search", feature = "testing"))]
+pub use user::*;
 
+#[cfg(feature = "indexes")]
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub(crate) struct Empty;
 
-/// Represents the charted-server distribution type that it was built from.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Represents the charted-server distribution type that it was built from. Distribution types that
+/// this version doesn't know about are kept in the [`DistributionType::Other`] variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum DistributionType {
     /// Distribution type that is running on a Kubernetes cluster, most likely using the charted-server
     /// Helm chart or Noel on Cloud Kubernetes Operator
-    #[serde(rename = "kubernetes")]
     Kubernetes,
 
     /// Distribution type that is unknown, might not recommend touching this one!
-    #[serde(rename = "unknown")]
     Unknown,
 
     /// Distribution type that is using the **charted-server** Docker image from Noelware or GitHub's
     /// container registry.
-    #[serde(rename = "docker")]
     Docker,
 
     /// Distribution type that represents the server being pulled from systems that use
     /// the RPM package distribution.
-    #[serde(rename = "rpm")]
     RPM,
 
     /// Distribution type that represents the server being pulled from systems that use
     /// the Debian package distribution (Ubuntu/Debian-based systems).
-    #[serde(rename = "deb")]
     Deb,
 
     /// Distribution type that represents the server being ran from the [GitHub repository](https://github.com/charted-dev/charted),
     /// or built locally.
-    #[serde(rename = "git")]
     Git,
+
+    /// Distribution type that was added in a newer version of **charted-server**.
+    Other(String),
 }
 
+string_enum!(DistributionType {
+    Kubernetes => "kubernetes",
+    Unknown => "unknown",
+    Docker => "docker",
+    RPM => "rpm",
+    Deb => "deb",
+    Git => "git",
+});
+
 #[allow(clippy::derivable_impls)]
 impl Default for DistributionType {
     fn default() -> Self {
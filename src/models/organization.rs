@@ -0,0 +1,68 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, Utc};
+
+use super::User;
+
+/// Represents an organization that can own repositories on **charted-server**.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Organization {
+    /// Whether if this organization is a verified publisher or not.
+    #[serde(default)]
+    pub verified_publisher: bool,
+
+    /// The Twitter handle that this organization owns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twitter_handle: Option<String>,
+
+    /// The email address to use when fetching this organization's Gravatar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gravatar_email: Option<String>,
+
+    /// The display name for this organization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// The date of when this organization was created.
+    pub created_at: DateTime<Utc>,
+
+    /// The date of when this organization was last updated.
+    pub updated_at: DateTime<Utc>,
+
+    /// The hash of the organization's icon that is stored on the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_hash: Option<String>,
+
+    /// Whether if this organization is private or not.
+    #[serde(default)]
+    pub private: bool,
+
+    /// The user that owns this organization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<User>,
+
+    /// The organization's name, which is unique across users and organizations.
+    pub name: String,
+
+    /// The organization's snowflake ID.
+    pub id: u64,
+}
@@ -0,0 +1,89 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[cfg(any(feature = "repositories", feature = "search", feature = "testing"))]
+use chrono::{DateTime, Utc};
+
+/// Represents a repository that holds a Helm chart and all of its releases.
+#[cfg(any(feature = "repositories", feature = "search", feature = "testing"))]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Repository {
+    /// Short description about this repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether if this repository is deprecated or not.
+    #[serde(default)]
+    pub deprecated: bool,
+
+    /// The date of when this repository was created.
+    pub created_at: DateTime<Utc>,
+
+    /// The date of when this repository was last updated.
+    pub updated_at: DateTime<Utc>,
+
+    /// The hash of the repository's icon that is stored on the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_hash: Option<String>,
+
+    /// Whether if this repository is private or not.
+    #[serde(default)]
+    pub private: bool,
+
+    /// The snowflake ID of the user or organization that owns this repository.
+    pub owner_id: u64,
+
+    /// The repository's name.
+    pub name: String,
+
+    /// The type of chart this repository holds.
+    #[serde(rename = "type", default)]
+    pub repository_type: RepositoryType,
+
+    /// The repository's snowflake ID.
+    pub id: u64,
+}
+
+/// Represents the repository type that **charted-server** supports. Repository types that this version
+/// doesn't know about are kept in the [`RepositoryType::Other`] variant.
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum RepositoryType {
+    Application,
+    Library,
+    Operator,
+
+    /// Repository type that was added in a newer version of **charted-server** or Helm.
+    Other(String),
+}
+
+string_enum!(RepositoryType {
+    Application => "application",
+    Library => "library",
+    Operator => "operator",
+});
+
+#[allow(clippy::derivable_impls)]
+impl Default for RepositoryType {
+    fn default() -> Self {
+        RepositoryType::Application
+    }
+}
@@ -35,6 +35,25 @@ where
     pub errors: Option<Vec<ApiError>>,
 }
 
+impl<T: Debug> APIResponse<T> {
+    /// Returns the response's data if the request was successful, otherwise its errors are returned as
+    /// an [`Error::APIServer`][crate::Error::APIServer]. Responses that don't follow that shape (i.e, a
+    /// successful response without any data) are returned as an [`Error::String`][crate::Error::String].
+    pub fn into_result(self) -> crate::Result<T> {
+        match (self.success, self.data, self.errors) {
+            (true, Some(data), _) => Ok(data),
+            (false, _, Some(errors)) => Err(crate::Error::APIServer { errors }),
+            (true, None, _) => Err(crate::Error::String(
+                "API server responded successfully without any data".into(),
+            )),
+
+            (false, _, None) => Err(crate::Error::String(
+                "API server responded with an unsuccessful response without any errors".into(),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ApiError {
     pub code: String,
@@ -47,3 +66,26 @@ impl Display for ApiError {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn response(json: &str) -> crate::Result<u64> {
+        serde_json::from_str::<APIResponse<u64>>(json).unwrap().into_result()
+    }
+
+    #[test]
+    fn into_result() {
+        assert_eq!(response(r#"{"success":true,"data":1}"#).unwrap(), 1);
+        match response(r#"{"success":false,"errors":[{"code":"INTERNAL_SERVER_ERROR","message":"oops"}]}"#) {
+            Err(Error::APIServer { errors }) => assert_eq!(errors[0].code, "INTERNAL_SERVER_ERROR"),
+            other => panic!("expected an API server error, got {other:?}"),
+        }
+
+        // neither of these should panic
+        assert!(matches!(response(r#"{"success":false}"#), Err(Error::String(_))));
+        assert!(matches!(response(r#"{"success":true}"#), Err(Error::String(_))));
+    }
+}
@@ -0,0 +1,213 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Models for the Search API, which is only available if the server has the `search` feature enabled.
+
+use std::fmt::Debug;
+
+use serde::Serializer;
+
+use super::RepositoryType;
+
+/// Represents the field to sort search hits by.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SearchSortBy {
+    /// Sorts hits by how relevant they are to the query, this is the default.
+    #[serde(rename = "relevance")]
+    Relevance,
+
+    /// Sorts hits by their name.
+    #[serde(rename = "name")]
+    Name,
+
+    /// Sorts hits by the date they were created.
+    #[serde(rename = "created_at")]
+    CreatedAt,
+
+    /// Sorts hits by the date they were last updated.
+    #[serde(rename = "updated_at")]
+    UpdatedAt,
+}
+
+/// Represents the direction to sort search hits in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SortOrder {
+    #[serde(rename = "asc")]
+    Ascending,
+
+    #[serde(rename = "desc")]
+    Descending,
+}
+
+/// Represents a query to send to the Search API. The repository-specific filters (`owner`, `type`,
+/// `keywords` and `deprecated`) are ignored by the server when searching users or organizations.
+///
+/// ```
+/// # use charted::models::{RepositoryType, SearchQuery, SearchSortBy};
+/// #
+/// let mut query = SearchQuery::new("postgres");
+/// query
+///     .owner("noel")
+///     .repository_type(RepositoryType::Application)
+///     .keyword("database")
+///     .sort_by(SearchSortBy::UpdatedAt)
+///     .limit(10);
+///
+/// assert_eq!(
+///     query.to_query_string()?,
+///     "q=postgres&owner=noel&type=application&keywords=database&sort=updated_at&limit=10"
+/// );
+/// # Ok::<(), charted::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SearchQuery {
+    #[serde(rename = "q")]
+    query: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    repository_type: Option<RepositoryType>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "serialize_keywords")]
+    keywords: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<bool>,
+
+    #[serde(rename = "sort", skip_serializing_if = "Option::is_none")]
+    sort_by: Option<SearchSortBy>,
+
+    #[serde(rename = "order", skip_serializing_if = "Option::is_none")]
+    sort_order: Option<SortOrder>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<u32>,
+}
+
+impl SearchQuery {
+    /// Creates a new [`SearchQuery`] with the full-text query to search for.
+    pub fn new<S: Into<String>>(query: S) -> SearchQuery {
+        SearchQuery {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Only returns repositories that are owned by the user or organization with the given name.
+    pub fn owner<S: Into<String>>(&mut self, owner: S) -> &mut Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Only returns repositories with the given [`RepositoryType`].
+    pub fn repository_type(&mut self, ty: RepositoryType) -> &mut Self {
+        self.repository_type = Some(ty);
+        self
+    }
+
+    /// Only returns repositories that have this keyword, this can be called multiple
+    /// times to require more than one keyword.
+    pub fn keyword<S: Into<String>>(&mut self, keyword: S) -> &mut Self {
+        self.keywords.push(keyword.into());
+        self
+    }
+
+    /// Only returns repositories that have all of the given keywords.
+    pub fn keywords<I: IntoIterator<Item = S>, S: Into<String>>(&mut self, keywords: I) -> &mut Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only returns repositories that are (or aren't) deprecated.
+    pub fn deprecated(&mut self, deprecated: bool) -> &mut Self {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    /// Sets the field to sort the search hits by.
+    pub fn sort_by(&mut self, sort_by: SearchSortBy) -> &mut Self {
+        self.sort_by = Some(sort_by);
+        self
+    }
+
+    /// Sets the direction to sort the search hits in.
+    pub fn sort_order(&mut self, order: SortOrder) -> &mut Self {
+        self.sort_order = Some(order);
+        self
+    }
+
+    /// Sets the maximum amount of hits to return.
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the amount of hits to skip, used for pagination.
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns the URL-encoded query string that is sent to the Search API.
+    pub fn to_query_string(&self) -> crate::Result<String> {
+        serde_urlencoded::to_string(self).map_err(Into::into)
+    }
+}
+
+fn serialize_keywords<S: Serializer>(keywords: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(keywords.join(",").as_str())
+}
+
+/// Represents the response object for the Search API.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchResult<T>
+where
+    T: Debug,
+{
+    /// How long the search took on the server, in milliseconds.
+    #[serde(default)]
+    pub took: u64,
+
+    /// The total amount of hits that matched the query, regardless of the limit or offset.
+    pub total_hits: u64,
+
+    /// The hits that were returned from the search.
+    pub hits: Vec<SearchHit<T>>,
+}
+
+/// Represents a single hit from the Search API.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchHit<T>
+where
+    T: Debug,
+{
+    /// The relevance score of this hit, if the search backend provides one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+
+    /// The entity that matched the query.
+    pub data: T,
+}
@@ -0,0 +1,70 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Serializes timestamps the same way Helm does, which uses Go's `time.RFC3339Nano` format: trailing
+//! zeros in the fractional seconds are trimmed and UTC is written as `Z`.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub(crate) fn format(time: &DateTime<Utc>) -> String {
+    let formatted = time.to_rfc3339_opts(SecondsFormat::Nanos, true);
+    let (datetime, _) = formatted.split_once('Z').unwrap_or((formatted.as_str(), ""));
+    let datetime = match datetime.split_once('.') {
+        Some((secs, fraction)) => match fraction.trim_end_matches('0') {
+            "" => secs.to_owned(),
+            fraction => format!("{secs}.{fraction}"),
+        },
+        None => datetime.to_owned(),
+    };
+
+    format!("{datetime}Z")
+}
+
+pub(crate) fn serialize<S: Serializer>(time: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(format(time).as_str())
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    let time = String::deserialize(deserializer)?;
+    DateTime::parse_from_rfc3339(time.as_str())
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(serde::de::Error::custom)
+}
+
+pub(crate) mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(time: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => super::serialize(time, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapper(#[serde(with = "super")] DateTime<Utc>);
+
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(time)| time))
+    }
+}
@@ -0,0 +1,62 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use chrono::{DateTime, Utc};
+
+/// Represents a user that is registered on **charted-server**.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct User {
+    /// Whether if this user is a verified publisher or not.
+    #[serde(default)]
+    pub verified_publisher: bool,
+
+    /// The email address to use when fetching this user's Gravatar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gravatar_email: Option<String>,
+
+    /// Short description about this user, will be displayed on their profile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The hash of the user's avatar that is stored on the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_hash: Option<String>,
+
+    /// The date of when this user was registered.
+    pub created_at: DateTime<Utc>,
+
+    /// The date of when this user was last updated.
+    pub updated_at: DateTime<Utc>,
+
+    /// The user's username, which is unique across users and organizations.
+    pub username: String,
+
+    /// Whether if this user is an administrator of this **charted-server** instance.
+    #[serde(default)]
+    pub admin: bool,
+
+    /// The user's display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The user's snowflake ID.
+    pub id: u64,
+}
@@ -0,0 +1,416 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose, Engine};
+use reqwest::{header::HeaderMap, Client, Request, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// Headers that are never written to a cassette, since they contain credentials.
+const REDACTED_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+/// The value that redacted headers are recorded with.
+pub const REDACTED: &str = "[redacted]";
+
+/// Whether a [`Cassette`] records interactions with the API server or replays them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Requests are sent to the API server, and every request and its response is recorded to be written
+    /// to the cassette file.
+    Record,
+
+    /// Requests are never sent; the response of the first unused interaction that matches the
+    /// request's method, path and body is returned, or an [`Error::UnmatchedRequest`] if none match.
+    Replay,
+}
+
+/// Represents a YAML file of recorded interactions with the API server, which an [`APIClient`] can use
+/// to record requests once and then replay them in deterministic, offline tests. Credentials in the
+/// `Authorization` and cookie headers are redacted before anything is written.
+///
+/// Requests are matched by their method, path (with the query string) and body, while the host is
+/// ignored so a cassette recorded against one server can be replayed with any base URL. Each
+/// interaction is only replayed once, in the order that they were recorded.
+///
+/// ## Example
+/// ```no_run
+/// # use charted::{testing::Cassette, APIClient};
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let cassette = if std::env::var("RECORD").is_ok() {
+///     Cassette::record("tests/cassettes/info.yaml")
+/// } else {
+///     Cassette::replay("tests/cassettes/info.yaml")?
+/// };
+///
+/// let client = APIClient::builder().cassette(cassette).build();
+/// client.info().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`APIClient`]: ../struct.APIClient.html
+/// [`Error::UnmatchedRequest`]: ../enum.Error.html#variant.UnmatchedRequest
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+    state: Arc<Mutex<CassetteState>>,
+}
+
+#[derive(Debug)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+
+    // The file that interactions are written to when recording, and how many of them were already written
+    record_to: Option<PathBuf>,
+    saved: usize,
+}
+
+/// Represents a request and the response that the API server sent back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request that was sent, which is matched against requests when replaying.
+    pub request: RecordedRequest,
+
+    /// The response that the API server sent back, which is returned when the request is replayed.
+    pub response: RecordedResponse,
+}
+
+/// Represents a recorded request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The HTTP method, i.e. `GET`.
+    pub method: String,
+
+    /// The path of the URL, with the query string if there was one.
+    pub path: String,
+
+    /// The headers that were set on the request, without the client's default headers.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// The body of the request, if there was one.
+    #[serde(flatten)]
+    pub body: RecordedBody,
+}
+
+/// Represents a recorded response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The HTTP status code.
+    pub status: u16,
+
+    /// The headers that the API server sent back.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// The body of the response, if there was one.
+    #[serde(flatten)]
+    pub body: RecordedBody,
+}
+
+/// Represents the body of a recorded request or response. UTF-8 bodies are written as-is, so they
+/// are readable and can be edited by hand, while binary bodies (i.e, chart packages) are base64-encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedBody {
+    /// The body, if it was valid UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+
+    /// The base64-encoded body, if it wasn't valid UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+impl RecordedBody {
+    fn new(bytes: &[u8]) -> RecordedBody {
+        if bytes.is_empty() {
+            return RecordedBody::default();
+        }
+
+        match std::str::from_utf8(bytes) {
+            Ok(body) => RecordedBody {
+                body: Some(body.to_owned()),
+                body_base64: None,
+            },
+
+            Err(_) => RecordedBody {
+                body: None,
+                body_base64: Some(general_purpose::STANDARD.encode(bytes)),
+            },
+        }
+    }
+
+    /// Returns the decoded bytes of this body, which is empty if there was no body.
+    pub fn bytes(&self) -> Result<Vec<u8>> {
+        match (&self.body, &self.body_base64) {
+            (Some(body), _) => Ok(body.as_bytes().to_vec()),
+            (None, Some(encoded)) => general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| Error::Unknown(Box::new(e))),
+
+            (None, None) => Ok(vec![]),
+        }
+    }
+}
+
+impl Cassette {
+    /// Creates a [`Cassette`] that records every interaction into the file at `path`. The file is written
+    /// by [`Cassette::save`], or when the cassette and every clone of it is dropped (even if a test panics),
+    /// so recording never blocks the runtime while requests are made.
+    pub fn record<P: Into<PathBuf>>(path: P) -> Cassette {
+        let path = path.into();
+        Cassette {
+            mode: CassetteMode::Record,
+            path: path.clone(),
+            state: Arc::new(Mutex::new(CassetteState {
+                interactions: vec![],
+                used: vec![],
+                record_to: Some(path),
+                saved: 0,
+            })),
+        }
+    }
+
+    /// Loads the cassette file at `path` to replay its interactions.
+    pub fn replay<P: Into<PathBuf>>(path: P) -> Result<Cassette> {
+        let path = path.into();
+        let contents = fs::read_to_string(&path)?;
+        let file: CassetteFile = serde_yaml::from_str(&contents).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: contents,
+        })?;
+
+        Ok(Cassette {
+            mode: CassetteMode::Replay,
+            path,
+            state: Arc::new(Mutex::new(CassetteState {
+                used: vec![false; file.interactions.len()],
+                interactions: file.interactions,
+                record_to: None,
+                saved: 0,
+            })),
+        })
+    }
+
+    /// Returns the [`CassetteMode`] of this cassette.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Returns the path of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns every interaction that was recorded, or that was loaded to be replayed.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    /// Returns the interactions that weren't replayed yet, which can be asserted to be empty at the end of
+    /// a test to check that every recorded request was made.
+    pub fn unused(&self) -> Vec<Interaction> {
+        let state = self.state.lock().unwrap();
+        state
+            .interactions
+            .iter()
+            .zip(&state.used)
+            .filter(|(_, used)| !**used)
+            .map(|(interaction, _)| interaction.clone())
+            .collect()
+    }
+
+    /// Executes a request with the given client when recording, or replays it.
+    pub(crate) async fn execute(&self, client: &Client, req: Request) -> Result<Response> {
+        let request = RecordedRequest {
+            method: req.method().to_string(),
+            path: match req.url().query() {
+                Some(query) => format!("{}?{query}", req.url().path()),
+                None => req.url().path().to_owned(),
+            },
+            headers: headers(req.headers()),
+            body: RecordedBody::new(req.body().and_then(|body| body.as_bytes()).unwrap_or_default()),
+        };
+
+        match self.mode {
+            CassetteMode::Replay => {
+                let mut state = self.state.lock().unwrap();
+                let CassetteState { interactions, used, .. } = &mut *state;
+                let Some((interaction, used)) = interactions
+                    .iter()
+                    .zip(used.iter_mut())
+                    .find(|(interaction, used)| !**used && matches(&interaction.request, &request))
+                else {
+                    return Err(Error::UnmatchedRequest {
+                        method: request.method,
+                        path: request.path,
+                    });
+                };
+
+                *used = true;
+                response(&interaction.response)
+            }
+
+            CassetteMode::Record => {
+                let res = client.execute(req).await.map_err(Error::Reqwest)?;
+                let status = res.status().as_u16();
+                let response_headers = headers(res.headers());
+                let bytes = res.bytes().await.map_err(Error::Reqwest)?;
+                let recorded = RecordedResponse {
+                    status,
+                    headers: response_headers,
+                    body: RecordedBody::new(&bytes),
+                };
+
+                let res = response(&recorded)?;
+                let mut state = self.state.lock().unwrap();
+                state.interactions.push(Interaction {
+                    request,
+                    response: recorded,
+                });
+
+                state.used.push(true);
+                Ok(res)
+            }
+        }
+    }
+
+    /// Writes every recorded interaction to the cassette file, which isn't needed since the file is
+    /// also written on drop, but it surfaces any error that writing the file returns. This does nothing
+    /// when replaying.
+    pub async fn save(&self) -> Result<()> {
+        let (contents, count) = {
+            let state = self.state.lock().unwrap();
+            if state.record_to.is_none() {
+                return Ok(());
+            }
+
+            (state.contents()?, state.interactions.len())
+        };
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(&self.path, contents).await?;
+
+        let mut state = self.state.lock().unwrap();
+        state.saved = state.saved.max(count);
+        Ok(())
+    }
+}
+
+impl CassetteState {
+    fn contents(&self) -> Result<String> {
+        let file = CassetteFile {
+            interactions: self.interactions.clone(),
+        };
+
+        serde_yaml::to_string(&file).map_err(|e| Error::YamlSerialization {
+            error: e,
+            payload: String::new(),
+        })
+    }
+}
+
+// Writes the interactions that weren't saved yet, once the last clone of the cassette is dropped
+impl Drop for CassetteState {
+    fn drop(&mut self) {
+        let Some(path) = self.record_to.as_ref() else {
+            return;
+        };
+
+        if self.saved == self.interactions.len() {
+            return;
+        }
+
+        let result = self.contents().and_then(|contents| {
+            if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(path, contents).map_err(Error::from)
+        });
+
+        if let Err(error) = result {
+            eprintln!("failed to write cassette {}: {error}", path.display());
+        }
+    }
+}
+
+fn matches(recorded: &RecordedRequest, request: &RecordedRequest) -> bool {
+    recorded.method.eq_ignore_ascii_case(&request.method)
+        && recorded.path == request.path
+        && recorded.body.bytes().ok() == request.body.bytes().ok()
+}
+
+fn headers(map: &HeaderMap) -> BTreeMap<String, String> {
+    let mut headers = BTreeMap::<String, String>::new();
+    for (name, value) in map {
+        let name = name.as_str().to_owned();
+        let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+            REDACTED.to_owned()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).to_string()
+        };
+
+        headers
+            .entry(name)
+            .and_modify(|existing| {
+                if existing != REDACTED {
+                    existing.push_str(", ");
+                    existing.push_str(&value);
+                }
+            })
+            .or_insert(value);
+    }
+
+    headers
+}
+
+fn response(recorded: &RecordedResponse) -> Result<Response> {
+    let mut builder = http::Response::builder().status(recorded.status);
+    for (name, value) in &recorded.headers {
+        builder = builder.header(name, value);
+    }
+
+    builder
+        .body(recorded.body.bytes()?)
+        .map(Response::from)
+        .map_err(|e| Error::Unknown(Box::new(e)))
+}
@@ -0,0 +1,183 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Ready-made models that look like the ones that **charted-server** returns. Every timestamp is
+//! fixed so that tests are deterministic.
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::models::{
+    ChartIndexSpec, ChartIndexYaml, ChartMaintainer, DistributionType, FeaturesResponse, InfoResponse, MainResponse,
+    Organization, Repository, RepositoryType, User,
+};
+
+/// The timestamp that every fixture uses.
+pub fn timestamp() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2023, 3, 14, 18, 32, 18).unwrap()
+}
+
+/// Returns the response of the `GET /` REST handler.
+pub fn main() -> MainResponse {
+    MainResponse {
+        message: "Hello, world! 👋".into(),
+        tagline: "You know, for Helm charts?".into(),
+        docs: "https://charts.noelware.org/docs".into(),
+    }
+}
+
+/// Returns the response of the `GET /info` REST handler.
+pub fn info() -> InfoResponse {
+    InfoResponse {
+        distribution: DistributionType::Docker,
+        commit_hash: "d8f2a19".into(),
+        build_date: "2023-03-14T18:32:18Z".into(),
+        product: "charted-server".into(),
+        version: "0.4.0-unstable.3".into(),
+        vendor: "Noelware, LLC.".into(),
+    }
+}
+
+/// Returns the response of the `GET /features` REST handler, where only the Docker registry
+/// feature is disabled.
+pub fn features() -> FeaturesResponse {
+    FeaturesResponse {
+        docker_registry: false,
+        is_invite_only: false,
+        registrations: true,
+        integrations: Default::default(),
+        audit_logs: true,
+        webhooks: true,
+        search: true,
+    }
+}
+
+/// Returns a user with the given ID and username.
+pub fn user<S: Into<String>>(id: u64, username: S) -> User {
+    User {
+        created_at: timestamp(),
+        updated_at: timestamp(),
+        username: username.into(),
+        id,
+        ..Default::default()
+    }
+}
+
+/// Returns an organization with the given ID and name, which is owned by `owner`.
+pub fn organization<S: Into<String>>(id: u64, name: S, owner: User) -> Organization {
+    Organization {
+        created_at: timestamp(),
+        updated_at: timestamp(),
+        owner: Some(owner),
+        name: name.into(),
+        id,
+        ..Default::default()
+    }
+}
+
+/// Returns an application repository with the given ID and name, which is owned by the user or
+/// organization with the ID of `owner_id`.
+pub fn repository<S: Into<String>>(id: u64, owner_id: u64, name: S) -> Repository {
+    Repository {
+        description: Some("Helm chart for testing".into()),
+        created_at: timestamp(),
+        updated_at: timestamp(),
+        owner_id,
+        name: name.into(),
+        repository_type: RepositoryType::Application,
+        id,
+        ..Default::default()
+    }
+}
+
+/// Returns an index entry for the given chart name and version, without any URLs or digest.
+///
+/// ## Panics
+/// This panics if the name or version is empty.
+pub fn chart_spec<N: Into<String>, V: Into<String>>(name: N, version: V) -> ChartIndexSpec {
+    let name = name.into();
+    ChartIndexSpec::builder(name.clone(), version)
+        .description(format!("Helm chart for {name}"))
+        .app_version("1.0.0")
+        .chart_type(RepositoryType::Application)
+        .maintainer(
+            ChartMaintainer::builder("Noel")
+                .email("cutie@floofy.dev")
+                .build()
+                .unwrap(),
+        )
+        .created(timestamp())
+        .build()
+        .expect("chart name and version to be non-empty")
+}
+
+/// Returns an index with a few versions of the `hello-world` and `postgresql` charts, including
+/// a pre-release.
+pub fn chart_index() -> ChartIndexYaml {
+    let mut index = ChartIndexYaml {
+        api_version: "v1".into(),
+        generated: timestamp(),
+        ..Default::default()
+    };
+
+    index.entries.insert(
+        "hello-world".into(),
+        vec![
+            chart_spec("hello-world", "1.0.0-beta.1"),
+            chart_spec("hello-world", "0.2.0"),
+            chart_spec("hello-world", "0.1.0"),
+        ],
+    );
+
+    index
+        .entries
+        .insert("postgresql".into(), vec![chart_spec("postgresql", "12.1.0")]);
+
+    index
+}
+
+/// Returns the excerpt of the Bitnami chart repository's index in `tests/fixtures/helm/bitnami.yaml`.
+///
+/// The committed excerpt is a hand-written stand-in in the shape of Bitnami's index until it is regenerated
+/// with `scripts/regenerate-helm-fixtures.sh`, so its digests don't belong to any real package.
+pub fn bitnami_index() -> ChartIndexYaml {
+    ChartIndexYaml::from_yaml(include_str!("../../tests/fixtures/helm/bitnami.yaml"))
+        .expect("the bitnami fixture is a valid index")
+}
+
+/// Returns a minimal chart package (`.tgz`) for the given chart name and version.
+#[cfg(feature = "chart-packaging")]
+pub fn chart_package<N: AsRef<str>, V: AsRef<str>>(name: N, version: V) -> Vec<u8> {
+    let dir = tempfile::tempdir().expect("unable to create a temporary directory");
+    let chart_yaml = format!(
+        "apiVersion: v2\nname: {}\nversion: {}\ndescription: Helm chart for testing\nicon: https://charts.noelware.org/icon.png\n",
+        name.as_ref(),
+        version.as_ref()
+    );
+
+    std::fs::write(dir.path().join("Chart.yaml"), chart_yaml).unwrap();
+    std::fs::write(dir.path().join("values.yaml"), "replicas: 1\n").unwrap();
+
+    crate::chart::ChartPackager::new(dir.path())
+        .modified(timestamp())
+        .package()
+        .expect("the fixture chart is valid")
+        .into_bytes()
+}
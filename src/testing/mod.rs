@@ -0,0 +1,48 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Test support for code that uses this crate, which is only available with the `testing` feature. The
+//! [`FakeServer`] is an in-process **charted-server** that keeps everything in memory, so tests can run
+//! offline, and the [`fixtures`] module has ready-made models to fill it with. Interactions with a real
+//! server can also be recorded into a [`Cassette`] once and replayed afterwards.
+//!
+//! ## Example
+//! ```
+//! # use charted::testing::{fixtures, FakeServer};
+//! #
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = FakeServer::start();
+//! server.add_user(fixtures::user(1, "noel"));
+//! server.set_index("noel", fixtures::chart_index());
+//!
+//! let client = server.client();
+//! client.health().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod cassette;
+pub mod fixtures;
+mod server;
+
+pub use cassette::*;
+pub use server::*;
@@ -0,0 +1,659 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Arc, RwLock},
+    thread::JoinHandle,
+};
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use sha2::{Digest, Sha256};
+use tokio::sync::oneshot;
+
+use super::fixtures;
+use crate::{
+    models::{
+        APIResponse, ApiError, ChartIndexSpec, ChartIndexYaml, FeaturesResponse, InfoResponse, MainResponse,
+        Organization, Repository, User,
+    },
+    APIClient,
+};
+
+#[derive(Debug, Default)]
+struct FakeState {
+    main: MainResponse,
+    info: InfoResponse,
+    features: FeaturesResponse,
+    users: Vec<User>,
+    organizations: Vec<Organization>,
+    repositories: Vec<Repository>,
+    indexes: HashMap<String, ChartIndexYaml>,
+    packages: HashMap<String, Vec<u8>>,
+    requests: Vec<String>,
+    authorizations: Vec<Option<String>>,
+}
+
+type SharedState = Arc<RwLock<FakeState>>;
+
+/// Represents an in-process fake **charted-server** that keeps its state in memory. It listens on a random
+/// port on `127.0.0.1` and runs on its own thread, so it can be used from both async and blocking tests. The
+/// server is shut down once it's dropped.
+///
+/// It implements the following REST handlers, and responds with the same `APIResponse` envelopes and
+/// error codes as **charted-server**:
+///
+/// - `GET /`, `GET /health`, `GET /info` and `GET /features`;
+/// - `GET /indexes/{idOrName}`, which supports conditional requests with `If-None-Match`;
+/// - `GET /users/{idOrName}` and `GET /users/{idOrName}/repositories`;
+/// - `GET /organizations/{idOrName}`;
+/// - `GET /repositories/{id}`;
+/// - `GET /search/{repositories,users,organizations}` (with the `search` feature), which applies every
+///   filter of a `SearchQuery` and responds with `400 Bad Request` to unknown or invalid query parameters;
+/// - `GET /charts/{owner}/{name}-{version}.tgz` for chart packages added with [`FakeServer::add_chart`].
+#[derive(Debug)]
+pub struct FakeServer {
+    url: String,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeServer {
+    /// Starts a new [`FakeServer`] with the [`fixtures::main`], [`fixtures::info`] and [`fixtures::features`]
+    /// responses, and no users, organizations, repositories or indexes.
+    ///
+    /// ## Panics
+    /// This panics if the server couldn't listen on a local port.
+    pub fn start() -> FakeServer {
+        let state: SharedState = Arc::new(RwLock::new(FakeState {
+            main: fixtures::main(),
+            info: fixtures::info(),
+            features: fixtures::features(),
+            ..Default::default()
+        }));
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("unable to bind the fake server");
+        listener.set_nonblocking(true).unwrap();
+
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let router = router(state.clone());
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("unable to create the fake server's runtime");
+
+            // The server is cancelled instead of shut down gracefully: a graceful shutdown waits for
+            // every connection to be closed, which never happens if the client's runtime is the one
+            // that is blocked on dropping the server.
+            runtime.spawn(async move {
+                axum::Server::from_tcp(listener)
+                    .expect("unable to serve the fake server")
+                    .serve(router.into_make_service())
+                    .await
+                    .expect("the fake server failed");
+            });
+
+            runtime.block_on(async {
+                let _ = signal.await;
+            });
+        });
+
+        FakeServer {
+            url,
+            state,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        }
+    }
+
+    /// Returns the base URL of the server, i.e. `http://127.0.0.1:41235`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns an [`APIClient`] that requests to this server.
+    pub fn client(&self) -> APIClient {
+        APIClient::default_with_url(self.url.clone())
+    }
+
+    /// Returns every request that the server received as `METHOD /path?query`, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.read().unwrap().requests.clone()
+    }
+
+    /// Returns the `Authorization` header of every request that the server received, in the same
+    /// order as [`FakeServer::requests`].
+    pub fn authorizations(&self) -> Vec<Option<String>> {
+        self.state.read().unwrap().authorizations.clone()
+    }
+
+    /// Replaces the response of the `GET /info` REST handler.
+    pub fn set_info(&self, info: InfoResponse) -> &Self {
+        self.state.write().unwrap().info = info;
+        self
+    }
+
+    /// Replaces the response of the `GET /features` REST handler.
+    pub fn set_features(&self, features: FeaturesResponse) -> &Self {
+        self.state.write().unwrap().features = features;
+        self
+    }
+
+    /// Adds a user, which can be requested by its ID or username.
+    pub fn add_user(&self, user: User) -> &Self {
+        self.state.write().unwrap().users.push(user);
+        self
+    }
+
+    /// Adds an organization, which can be requested by its ID or name.
+    pub fn add_organization(&self, organization: Organization) -> &Self {
+        self.state.write().unwrap().organizations.push(organization);
+        self
+    }
+
+    /// Adds a repository, which can be requested by its ID.
+    pub fn add_repository(&self, repository: Repository) -> &Self {
+        self.state.write().unwrap().repositories.push(repository);
+        self
+    }
+
+    /// Sets the chart index of a user or organization, which is served under both its name and ID.
+    pub fn set_index<S: Into<String>>(&self, owner: S, index: ChartIndexYaml) -> &Self {
+        self.state.write().unwrap().indexes.insert(owner.into(), index);
+        self
+    }
+
+    /// Adds a chart package to a user or organization's index and serves it. The entry's URL and
+    /// digest are set from the package, and the updated entry is returned.
+    pub fn add_chart<S: Into<String>, B: Into<Vec<u8>>>(
+        &self,
+        owner: S,
+        mut spec: ChartIndexSpec,
+        package: B,
+    ) -> ChartIndexSpec {
+        let (owner, package) = (owner.into(), package.into());
+        let path = format!("/charts/{owner}/{}-{}.tgz", spec.name, spec.version);
+
+        spec.urls = vec![format!("{}{path}", self.url)];
+        spec.digest = Some(hex::encode(Sha256::digest(&package)));
+
+        let mut state = self.state.write().unwrap();
+        state.packages.insert(path, package);
+
+        let index = state.indexes.entry(owner).or_insert_with(|| ChartIndexYaml {
+            api_version: "v1".into(),
+            generated: fixtures::timestamp(),
+            ..Default::default()
+        });
+
+        let entries = index.entries.entry(spec.name.clone()).or_default();
+        entries.retain(|existing| existing.version != spec.version);
+        entries.insert(0, spec.clone());
+
+        spec
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn router(state: SharedState) -> Router {
+    let router = Router::new()
+        .route(
+            "/",
+            get(|State(state): State<SharedState>| async move { ok(state.read().unwrap().main.clone()) }),
+        )
+        .route("/health", get(|| async { "OK" }))
+        .route(
+            "/info",
+            get(|State(state): State<SharedState>| async move { ok(state.read().unwrap().info.clone()) }),
+        )
+        .route(
+            "/features",
+            get(|State(state): State<SharedState>| async move { ok(state.read().unwrap().features.clone()) }),
+        )
+        .route("/indexes/:id_or_name", get(index))
+        .route("/users/:id_or_name", get(user))
+        .route("/users/:id_or_name/repositories", get(user_repositories))
+        .route("/organizations/:id_or_name", get(organization))
+        .route("/repositories/:id", get(repository))
+        .route("/charts/*path", get(package));
+
+    #[cfg(feature = "search")]
+    let router = router.route("/search/:index", get(search::search));
+
+    router
+        .fallback(|| async { not_found("REST_HANDLER_NOT_FOUND", "Route was not found") })
+        .layer(middleware::from_fn_with_state(state.clone(), record))
+        .with_state(state)
+}
+
+async fn record(State(state): State<SharedState>, req: Request<Body>, next: Next<Body>) -> Response {
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_default();
+
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_owned());
+
+    {
+        let mut state = state.write().unwrap();
+        state.requests.push(format!("{} {path}", req.method()));
+        state.authorizations.push(authorization);
+    }
+
+    next.run(req).await
+}
+
+fn ok<T: serde::Serialize + std::fmt::Debug>(data: T) -> Response {
+    Json(APIResponse {
+        success: true,
+        data: Some(data),
+        errors: None,
+    })
+    .into_response()
+}
+
+fn not_found(code: &str, message: &str) -> Response {
+    let body = APIResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ApiError {
+            code: code.into(),
+            message: message.into(),
+        }]),
+    };
+
+    (StatusCode::NOT_FOUND, Json(body)).into_response()
+}
+
+#[cfg(feature = "search")]
+fn bad_request(code: &str, message: &str) -> Response {
+    let body = APIResponse::<()> {
+        success: false,
+        data: None,
+        errors: Some(vec![ApiError {
+            code: code.into(),
+            message: message.into(),
+        }]),
+    };
+
+    (StatusCode::BAD_REQUEST, Json(body)).into_response()
+}
+
+// Resolves a user or organization's name from its ID or name
+fn owner_name(state: &FakeState, id_or_name: &str) -> Option<String> {
+    let id = id_or_name.parse::<u64>().ok();
+    state
+        .users
+        .iter()
+        .find(|user| Some(user.id) == id || user.username == id_or_name)
+        .map(|user| user.username.clone())
+        .or_else(|| {
+            state
+                .organizations
+                .iter()
+                .find(|org| Some(org.id) == id || org.name == id_or_name)
+                .map(|org| org.name.clone())
+        })
+        .or_else(|| id.is_none().then(|| id_or_name.to_owned()))
+}
+
+async fn index(State(state): State<SharedState>, Path(id_or_name): Path<String>, headers: HeaderMap) -> Response {
+    let state = state.read().unwrap();
+    let Some(index) = owner_name(&state, &id_or_name).and_then(|name| state.indexes.get(&name)) else {
+        return not_found("REST_HANDLER_NOT_FOUND", "Index was not found");
+    };
+
+    let body = index.to_yaml().expect("index can be serialized");
+    let etag = format!("\"{}\"", hex::encode(Sha256::digest(body.as_bytes())));
+    if headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) == Some(etag.as_str()) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/yaml; charset=utf-8".to_owned()),
+            (header::ETAG, etag),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn user(State(state): State<SharedState>, Path(id_or_name): Path<String>) -> Response {
+    let id = id_or_name.parse::<u64>().ok();
+    let state = state.read().unwrap();
+    match state
+        .users
+        .iter()
+        .find(|user| Some(user.id) == id || user.username == id_or_name)
+    {
+        Some(user) => ok(user.clone()),
+        None => not_found("UNKNOWN_USER", "User was not found"),
+    }
+}
+
+async fn user_repositories(State(state): State<SharedState>, Path(id_or_name): Path<String>) -> Response {
+    let id = id_or_name.parse::<u64>().ok();
+    let state = state.read().unwrap();
+    match state
+        .users
+        .iter()
+        .find(|user| Some(user.id) == id || user.username == id_or_name)
+    {
+        Some(user) => ok(state
+            .repositories
+            .iter()
+            .filter(|repo| repo.owner_id == user.id)
+            .cloned()
+            .collect::<Vec<_>>()),
+
+        None => not_found("UNKNOWN_USER", "User was not found"),
+    }
+}
+
+async fn organization(State(state): State<SharedState>, Path(id_or_name): Path<String>) -> Response {
+    let id = id_or_name.parse::<u64>().ok();
+    let state = state.read().unwrap();
+    match state
+        .organizations
+        .iter()
+        .find(|org| Some(org.id) == id || org.name == id_or_name)
+    {
+        Some(org) => ok(org.clone()),
+        None => not_found("UNKNOWN_ORGANIZATION", "Organization was not found"),
+    }
+}
+
+async fn repository(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let id = id.parse::<u64>().ok();
+    let state = state.read().unwrap();
+    match state.repositories.iter().find(|repo| Some(repo.id) == id) {
+        Some(repo) => ok(repo.clone()),
+        None => not_found("UNKNOWN_REPOSITORY", "Repository was not found"),
+    }
+}
+
+async fn package(State(state): State<SharedState>, Path(path): Path<String>) -> Response {
+    let state = state.read().unwrap();
+    match state.packages.get(&format!("/charts/{path}")) {
+        Some(package) => ([(header::CONTENT_TYPE, "application/tar+gzip")], package.clone()).into_response(),
+        None => not_found("REST_HANDLER_NOT_FOUND", "Chart package was not found"),
+    }
+}
+
+#[cfg(feature = "search")]
+mod search {
+    use std::{collections::HashMap, fmt::Debug, str::FromStr};
+
+    use axum::{
+        extract::{Path, Query, State},
+        response::Response,
+    };
+    use chrono::{DateTime, Utc};
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{bad_request, not_found, ok, FakeState, SharedState};
+    use crate::models::{
+        Organization, Repository, RepositoryType, SearchHit, SearchResult, SearchSortBy, SortOrder, User,
+    };
+
+    /// The query parameters of a search request. The repository filters are ignored when searching
+    /// users or organizations, like charted-server does, but they still have to be valid.
+    struct Filters {
+        query: String,
+        owner: Option<String>,
+        repository_type: Option<RepositoryType>,
+        keywords: Vec<String>,
+        deprecated: Option<bool>,
+        sort_by: SearchSortBy,
+        sort_order: SortOrder,
+        limit: usize,
+        offset: usize,
+    }
+
+    /// The error code and message of a query parameter that was rejected.
+    struct Invalid(&'static str, String);
+
+    impl Filters {
+        // Parses the query parameters, failing on any that a `SearchQuery` doesn't send so that a test
+        // catches a query string that charted-server wouldn't understand
+        fn parse(params: HashMap<String, String>) -> Result<Filters, Invalid> {
+            let mut filters = Filters {
+                query: String::new(),
+                owner: None,
+                repository_type: None,
+                keywords: vec![],
+                deprecated: None,
+                sort_by: SearchSortBy::Relevance,
+                sort_order: SortOrder::Ascending,
+                limit: usize::MAX,
+                offset: 0,
+            };
+
+            for (name, value) in params {
+                match name.as_str() {
+                    "q" => filters.query = value.to_lowercase(),
+                    "owner" => filters.owner = Some(value),
+                    "type" => match value.parse() {
+                        Ok(RepositoryType::Other(_)) | Err(_) => return Err(invalid(&name, &value)),
+                        Ok(ty) => filters.repository_type = Some(ty),
+                    },
+                    "keywords" => filters.keywords = value.split(',').map(|k| k.to_owned()).collect(),
+                    "deprecated" => filters.deprecated = Some(parse(&name, &value)?),
+                    "sort" => filters.sort_by = variant(&name, &value)?,
+                    "order" => filters.sort_order = variant(&name, &value)?,
+                    "limit" => filters.limit = parse(&name, &value)?,
+                    "offset" => filters.offset = parse(&name, &value)?,
+                    _ => {
+                        return Err(Invalid(
+                            "UNKNOWN_QUERY_PARAMETER",
+                            format!("unknown query parameter '{name}'"),
+                        ))
+                    }
+                }
+            }
+
+            Ok(filters)
+        }
+    }
+
+    fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, Invalid> {
+        value.parse().map_err(|_| invalid(name, value))
+    }
+
+    fn variant<T: DeserializeOwned>(name: &str, value: &str) -> Result<T, Invalid> {
+        serde_json::from_value(serde_json::Value::String(value.to_owned())).map_err(|_| invalid(name, value))
+    }
+
+    fn invalid(name: &str, value: &str) -> Invalid {
+        Invalid(
+            "INVALID_QUERY_PARAMETER",
+            format!("'{value}' is not a valid value for '{name}'"),
+        )
+    }
+
+    /// A document in one of the search indexes.
+    trait Document: Clone + Debug + Serialize {
+        fn name(&self) -> &str;
+        fn created_at(&self) -> DateTime<Utc>;
+        fn updated_at(&self) -> DateTime<Utc>;
+
+        fn matches(&self, _state: &FakeState, _filters: &Filters) -> bool {
+            true
+        }
+    }
+
+    impl Document for User {
+        fn name(&self) -> &str {
+            &self.username
+        }
+
+        fn created_at(&self) -> DateTime<Utc> {
+            self.created_at
+        }
+
+        fn updated_at(&self) -> DateTime<Utc> {
+            self.updated_at
+        }
+    }
+
+    impl Document for Organization {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn created_at(&self) -> DateTime<Utc> {
+            self.created_at
+        }
+
+        fn updated_at(&self) -> DateTime<Utc> {
+            self.updated_at
+        }
+    }
+
+    impl Document for Repository {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn created_at(&self) -> DateTime<Utc> {
+            self.created_at
+        }
+
+        fn updated_at(&self) -> DateTime<Utc> {
+            self.updated_at
+        }
+
+        // Repositories don't have keywords, so they're looked up in the chart's entries in its owner's index
+        fn matches(&self, state: &FakeState, filters: &Filters) -> bool {
+            let owner = state
+                .users
+                .iter()
+                .find(|user| user.id == self.owner_id)
+                .map(|user| user.username.as_str())
+                .or_else(|| {
+                    state
+                        .organizations
+                        .iter()
+                        .find(|org| org.id == self.owner_id)
+                        .map(|org| org.name.as_str())
+                });
+
+            let has_keywords = || {
+                owner
+                    .and_then(|owner| state.indexes.get(owner))
+                    .and_then(|index| index.entries.get(&self.name))
+                    .map(|specs| {
+                        specs
+                            .iter()
+                            .any(|spec| filters.keywords.iter().all(|keyword| spec.keywords.contains(keyword)))
+                    })
+                    .unwrap_or(false)
+            };
+
+            filters.owner.iter().all(|name| owner == Some(name.as_str()))
+                && filters.repository_type.iter().all(|ty| &self.repository_type == ty)
+                && filters
+                    .deprecated
+                    .iter()
+                    .all(|&deprecated| self.deprecated == deprecated)
+                && (filters.keywords.is_empty() || has_keywords())
+        }
+    }
+
+    // Matches every document where the name contains the query, which is enough for tests
+    pub(super) async fn search(
+        State(state): State<SharedState>,
+        Path(index): Path<String>,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> Response {
+        let filters = match Filters::parse(params) {
+            Ok(filters) => filters,
+            Err(Invalid(code, message)) => return bad_request(code, &message),
+        };
+
+        let state = state.read().unwrap();
+        match index.as_str() {
+            "repositories" => hits(&state, &state.repositories, &filters),
+            "users" => hits(&state, &state.users, &filters),
+            "organizations" => hits(&state, &state.organizations, &filters),
+            _ => not_found("REST_HANDLER_NOT_FOUND", "Route was not found"),
+        }
+    }
+
+    fn hits<T: Document>(state: &FakeState, documents: &[T], filters: &Filters) -> Response {
+        let mut hits = documents
+            .iter()
+            .filter(|doc| doc.name().to_lowercase().contains(&filters.query) && doc.matches(state, filters))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match filters.sort_by {
+            SearchSortBy::Relevance => {}
+            SearchSortBy::Name => hits.sort_by(|a, b| a.name().cmp(b.name())),
+            SearchSortBy::CreatedAt => hits.sort_by_key(|doc| doc.created_at()),
+            SearchSortBy::UpdatedAt => hits.sort_by_key(|doc| doc.updated_at()),
+        }
+
+        if filters.sort_order == SortOrder::Descending {
+            hits.reverse();
+        }
+
+        ok(SearchResult {
+            took: 0,
+            total_hits: hits.len() as u64,
+            hits: hits
+                .into_iter()
+                .skip(filters.offset)
+                .take(filters.limit)
+                .map(|data| SearchHit { score: Some(1.0), data })
+                .collect(),
+        })
+    }
+}
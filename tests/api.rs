@@ -0,0 +1,54 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Checks that the containers can be used through the traits in the `api` module.
+
+#![cfg(all(feature = "testing", feature = "indexes"))]
+
+use charted::{
+    api::{IndexesApi, MetaApi},
+    testing::{fixtures, FakeServer},
+};
+
+async fn versions(indexes: &dyn IndexesApi, name: &str, chart: &str) -> Vec<String> {
+    match indexes.get(name).await.unwrap() {
+        Some(index) => index.entries[chart].iter().map(|spec| spec.version.clone()).collect(),
+        None => vec![],
+    }
+}
+
+#[tokio::test]
+async fn trait_objects() {
+    let server = FakeServer::start();
+    server.set_index("noel", fixtures::chart_index());
+
+    let meta: Box<dyn MetaApi> = Box::new(server.client());
+    meta.health().await.unwrap();
+    assert_eq!(meta.features().await.unwrap().search, fixtures::features().search);
+
+    let indexes = server.client().indexes();
+    assert_eq!(
+        versions(&indexes, "noel", "hello-world").await,
+        ["1.0.0-beta.1", "0.2.0", "0.1.0"]
+    );
+
+    assert!(versions(&indexes, "ice", "hello-world").await.is_empty());
+}
@@ -0,0 +1,100 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests the blocking client against the in-process fake server from the `testing` feature.
+
+#![cfg(all(feature = "testing", feature = "blocking"))]
+
+use charted::{
+    blocking::APIClient,
+    testing::{fixtures, FakeServer},
+};
+
+#[test]
+fn meta() {
+    let server = FakeServer::start();
+    let client = APIClient::default_with_url(server.url());
+
+    client.health().unwrap();
+    assert_eq!(client.main().unwrap().message, fixtures::main().message);
+    assert_eq!(client.info().unwrap().version, fixtures::info().version);
+    assert_eq!(server.requests(), ["GET /health", "GET /", "GET /info"]);
+}
+
+#[cfg(feature = "indexes")]
+#[test]
+fn indexes() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.set_index("noel", fixtures::chart_index());
+
+    let indexes = APIClient::from(server.client()).indexes();
+    let index = indexes.get("noel").unwrap().unwrap();
+    assert_eq!(index.entries["hello-world"].len(), 3);
+    assert!(indexes.get_by_id(1).unwrap().is_some());
+    assert!(indexes.get("ice").unwrap().is_none());
+}
+
+#[cfg(all(feature = "indexes", feature = "chart-packaging"))]
+#[test]
+fn download_to() {
+    let server = FakeServer::start();
+    let package = fixtures::chart_package("hello-world", "0.1.0");
+    let spec = server.add_chart("noel", fixtures::chart_spec("hello-world", "0.1.0"), package.clone());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = APIClient::from(server.client())
+        .indexes()
+        .download_to("noel", &spec, dir.path())
+        .unwrap();
+
+    assert_eq!(path, dir.path().join("hello-world-0.1.0.tgz"));
+    assert_eq!(std::fs::read(path).unwrap(), package);
+}
+
+#[cfg(feature = "repositories")]
+#[test]
+fn repositories() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+
+    let repositories = APIClient::from(server.client()).repositories();
+    assert_eq!(repositories.get(3).unwrap().unwrap().name, "postgresql");
+    assert!(repositories.get(4).unwrap().is_none());
+    assert_eq!(repositories.list_for_user("noel").unwrap().len(), 1);
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn search() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+
+    let search = APIClient::from(server.client()).search();
+    let repositories = search
+        .repositories(charted::models::SearchQuery::new("postgres").limit(1))
+        .unwrap();
+
+    assert_eq!(repositories.total_hits, 1);
+    assert_eq!(repositories.hits[0].data.name, "postgresql");
+}
@@ -0,0 +1,154 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for caching responses in a [`CacheStore`][charted::cache::CacheStore]. Responses are replayed
+//! from cassettes, where each interaction can only be replayed once, so a request that isn't answered
+//! from the cache fails once its interaction was used.
+
+#![cfg(all(feature = "testing", feature = "cache"))]
+
+use std::path::Path;
+
+use charted::{
+    cache::{FileStore, MemoryStore},
+    testing::{fixtures, Cassette, FakeServer},
+    APIClient, Error,
+};
+
+const MAIN: &str = r#"{"success":true,"data":{"docs":"https://charts.noelware.org/docs","message":"Hello, world!","tagline":"You know, for Helm charts?"}}"#;
+
+/// Writes a cassette where every interaction is a `GET` request to `path` that is answered with
+/// the given headers and body.
+fn cassette(dir: &Path, interactions: &[(&str, &str, &str)]) -> Cassette {
+    let mut contents = String::from("interactions:\n");
+    for (path, headers, body) in interactions {
+        contents.push_str(&format!(
+            "  - request: {{ method: GET, path: '{path}' }}\n    response:\n      status: 200\n      headers: {{ {headers} }}\n      body: '{body}'\n"
+        ));
+    }
+
+    let path = dir.join("cassette.yaml");
+    std::fs::write(&path, contents).unwrap();
+    Cassette::replay(path).unwrap()
+}
+
+fn client(cassette: &Cassette) -> APIClient {
+    APIClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .cache(MemoryStore::new())
+        .cassette(cassette.clone())
+        .build()
+}
+
+fn is_unmatched<T: std::fmt::Debug>(result: charted::Result<T>) -> bool {
+    matches!(result, Err(Error::UnmatchedRequest { .. }))
+}
+
+#[tokio::test]
+async fn fresh_responses_are_reused() {
+    let dir = tempfile::tempdir().unwrap();
+    let cassette = cassette(dir.path(), &[("/", "cache-control: max-age=60", MAIN)]);
+    let client = client(&cassette);
+
+    assert_eq!(client.main().await.unwrap().message, "Hello, world!");
+    assert_eq!(client.main().await.unwrap().message, "Hello, world!");
+
+    // the cache can be opted out of
+    assert!(is_unmatched(client.uncached().main().await));
+}
+
+#[tokio::test]
+async fn responses_without_caching_headers_are_not_cached() {
+    let dir = tempfile::tempdir().unwrap();
+    let cassette = cassette(
+        dir.path(),
+        &[
+            ("/", "content-type: application/json", MAIN),
+            ("/", "cache-control: public", MAIN),
+            ("/", "cache-control: 'no-store, max-age=60'", MAIN),
+        ],
+    );
+
+    let client = client(&cassette);
+    for _ in 0..3 {
+        client.main().await.unwrap();
+    }
+
+    assert!(cassette.unused().is_empty());
+    assert!(is_unmatched(client.main().await));
+}
+
+#[tokio::test]
+async fn health_is_never_cached() {
+    let dir = tempfile::tempdir().unwrap();
+    let cassette = cassette(
+        dir.path(),
+        &[
+            ("/health", "cache-control: max-age=60", "OK"),
+            ("/health", "cache-control: max-age=60", "OK"),
+        ],
+    );
+
+    let client = client(&cassette);
+    client.health().await.unwrap();
+    client.health().await.unwrap();
+    assert!(cassette.unused().is_empty());
+}
+
+#[cfg(feature = "indexes")]
+#[tokio::test]
+async fn indexes_are_revalidated() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.set_index("noel", fixtures::chart_index());
+
+    let dir = tempfile::tempdir().unwrap();
+    let client = APIClient::builder()
+        .base_url(server.url())
+        .cache(FileStore::new(dir.path()))
+        .build();
+
+    let indexes = client.clone().indexes();
+    let index = indexes.get("noel").await.unwrap().unwrap();
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    // the fake server only sends an `ETag`, so the cached index is revalidated and the
+    // server responds with a `304 Not Modified` without a body
+    let cached = indexes.get("noel").await.unwrap().unwrap();
+    assert_eq!(cached.entries.len(), index.entries.len());
+    assert_eq!(server.requests(), ["GET /indexes/noel", "GET /indexes/noel"]);
+
+    // the cache is shared with clients that use the same directory
+    let other = APIClient::builder()
+        .base_url(server.url())
+        .cache(FileStore::new(dir.path()))
+        .build();
+
+    assert!(other.indexes().get("noel").await.unwrap().is_some());
+
+    // indexes that change are downloaded again
+    let mut changed = fixtures::chart_index();
+    changed.entries.remove("postgresql");
+    server.set_index("noel", changed);
+
+    let index = indexes.get("noel").await.unwrap().unwrap();
+    assert!(!index.entries.contains_key("postgresql"));
+}
@@ -0,0 +1,128 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Records interactions with the fake server into a cassette, and replays them without it.
+
+#![cfg(feature = "testing")]
+
+use charted::{
+    auth::ApiKeyStrategy,
+    testing::{fixtures, Cassette, CassetteMode, FakeServer, REDACTED},
+    APIClient, Error,
+};
+
+#[tokio::test]
+async fn record_and_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cassettes/meta.yaml");
+
+    {
+        let server = FakeServer::start();
+        let cassette = Cassette::record(&path);
+        let client = APIClient::builder()
+            .base_url(server.url())
+            .auth_strategy(ApiKeyStrategy::new("super secret"))
+            .cassette(cassette.clone())
+            .build();
+
+        client.health().await.unwrap();
+        assert!(
+            !path.exists(),
+            "the cassette shouldn't be written while requests are made"
+        );
+
+        cassette.save().await.unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("/health"));
+
+        // the rest is written when the last clone of the cassette is dropped
+        client.info().await.unwrap();
+        assert_eq!(cassette.interactions().len(), 2);
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("/info"));
+    assert!(!contents.contains("super secret"));
+    assert!(contents.contains(REDACTED));
+
+    // nothing is listening on this port, so every response has to come from the cassette
+    let cassette = Cassette::replay(&path).unwrap();
+    assert_eq!(cassette.mode(), CassetteMode::Replay);
+
+    let client = APIClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .cassette(cassette.clone())
+        .build();
+
+    client.health().await.unwrap();
+    assert_eq!(client.info().await.unwrap().version, fixtures::info().version);
+    assert!(cassette.unused().is_empty());
+
+    // every interaction is only replayed once
+    assert!(matches!(
+        client.health().await,
+        Err(Error::UnmatchedRequest { ref path, .. }) if path == "/health"
+    ));
+}
+
+#[cfg(feature = "indexes")]
+#[tokio::test]
+async fn malformed_index_responses() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("malformed.yaml");
+    std::fs::write(
+        &path,
+        r#"interactions:
+  - request: { method: GET, path: /indexes/noel }
+    response: { status: 500, headers: { content-type: application/json }, body: '{"success":false}' }
+  - request: { method: GET, path: /indexes/ice }
+    response: { status: 200, headers: { content-type: application/json }, body: '{"success":true}' }
+  - request: { method: GET, path: /indexes/noelware }
+    response:
+      status: 503
+      headers: { content-type: application/json }
+      body: '{"success":false,"errors":[{"code":"INTERNAL_SERVER_ERROR","message":"database is down"}]}'
+  - request: { method: GET, path: /indexes/broken }
+    response: { status: 200, headers: { content-type: text/yaml }, body: "apiVersion: v1\nentries: [" }
+  - request: { method: GET, path: /indexes/untyped }
+    response: { status: 200, body: "entries: 1" }
+"#,
+    )
+    .unwrap();
+
+    let indexes = APIClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .cassette(Cassette::replay(&path).unwrap())
+        .build()
+        .indexes();
+
+    // responses that the index can't be parsed from are errors, not panics
+    assert!(matches!(indexes.get("noel").await, Err(Error::String(_))));
+    assert!(matches!(indexes.get("ice").await, Err(Error::String(_))));
+    match indexes.get("noelware").await {
+        Err(Error::APIServer { errors }) => assert_eq!(errors[0].message, "database is down"),
+        other => panic!("expected an API server error, got {other:?}"),
+    }
+
+    // an index that isn't valid YAML is a YAML error, even if the server didn't send a content type
+    for name in ["broken", "untyped"] {
+        assert!(matches!(indexes.get(name).await, Err(Error::YamlSerialization { .. })));
+    }
+}
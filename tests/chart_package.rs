@@ -0,0 +1,173 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for packaging a chart directory with [`ChartPackager`] and inspecting the result
+//! with [`InspectedChart`].
+
+#![cfg(feature = "chart-packaging")]
+
+use std::{fs, path::Path};
+
+use charted::chart::{ChartPackager, InspectedChart};
+use serde_yaml::Value;
+
+const FILES: &[(&str, &str)] = &[
+    (
+        "Chart.yaml",
+        "apiVersion: v2\nname: hello\nversion: 0.1.0\nicon: https://charts.noelware.org/icon.png\n",
+    ),
+    ("values.yaml", "replicas: 1\n"),
+    ("README.md", "# hello\n"),
+    ("templates/deployment.yaml", "kind: Deployment\n"),
+    (
+        "templates/_helpers.tpl",
+        "{{- define \"hello.name\" -}}hello{{- end }}\n",
+    ),
+    ("templates/.hidden", "ignored by default\n"),
+    ("ci/values.yaml", "replicas: 3\n"),
+    (".helmignore", "ci/\n"),
+];
+
+fn write_chart<'a, I: IntoIterator<Item = &'a (&'a str, &'a str)>>(dir: &Path, files: I) {
+    for (path, contents) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+#[test]
+fn packaging_is_deterministic() {
+    let first = tempfile::tempdir().unwrap();
+    write_chart(first.path(), FILES);
+
+    // the same files, but created in the opposite order (and so with different
+    // modification times and directory order)
+    let second = tempfile::tempdir().unwrap();
+    write_chart(second.path(), FILES.iter().rev());
+
+    let package = ChartPackager::new(first.path()).package().unwrap();
+    assert_eq!(
+        package.files,
+        [
+            "Chart.yaml",
+            ".helmignore",
+            "README.md",
+            "templates/_helpers.tpl",
+            "templates/deployment.yaml",
+            "values.yaml"
+        ]
+    );
+
+    let again = ChartPackager::new(first.path()).package().unwrap();
+    let other = ChartPackager::new(second.path()).package().unwrap();
+    assert_eq!(package.bytes(), again.bytes());
+    assert_eq!(package.bytes(), other.bytes());
+
+    let modified = "2023-03-14T18:32:18Z".parse().unwrap();
+    let with_mtime = ChartPackager::new(first.path()).modified(modified).package().unwrap();
+    let with_mtime_again = ChartPackager::new(second.path()).modified(modified).package().unwrap();
+    assert_ne!(package.bytes(), with_mtime.bytes());
+    assert_eq!(with_mtime.bytes(), with_mtime_again.bytes());
+}
+
+#[test]
+fn inspect_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    write_chart(dir.path(), FILES);
+
+    let package = ChartPackager::new(dir.path()).package().unwrap();
+    let saved = package.save(dir.path().join("dist")).unwrap();
+    assert!(saved.ends_with("dist/hello-0.1.0.tgz"));
+
+    let inspected = InspectedChart::open(&saved).unwrap();
+    assert_eq!(inspected.chart.name, "hello");
+    assert_eq!(inspected.chart.version, "0.1.0");
+    assert_eq!(inspected.readme.as_deref(), Some("# hello\n"));
+    assert_eq!(inspected.values.unwrap()["replicas"], Value::from(1));
+
+    let mut files = package.files.clone();
+    files.sort();
+    assert_eq!(inspected.files, files);
+
+    let from_bytes = InspectedChart::from_reader(package.bytes()).unwrap();
+    assert_eq!(from_bytes.files, inspected.files);
+}
+
+#[test]
+fn negated_helmignore_rules_keep_only_matching_files() {
+    let dir = tempfile::tempdir().unwrap();
+    write_chart(dir.path(), &FILES[..FILES.len() - 1]);
+    fs::write(dir.path().join(".helmignore"), "!*.yaml\n").unwrap();
+
+    // like Helm, `templates/` and `ci/` are ignored as well since they don't match `*.yaml`
+    let package = ChartPackager::new(dir.path()).package().unwrap();
+    assert_eq!(package.files, ["Chart.yaml", "values.yaml"]);
+}
+
+#[test]
+fn invalid_charts_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("Chart.yaml"), "apiVersion: v2\nname: hello\n").unwrap();
+
+    assert!(matches!(
+        ChartPackager::new(dir.path()).package(),
+        Err(charted::Error::InvalidChart { .. })
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn follows_symlinks() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir().unwrap();
+    let shared = tempfile::tempdir().unwrap();
+    write_chart(dir.path(), &FILES[..2]);
+    write_chart(shared.path(), &[("configmap.yaml", "kind: ConfigMap\n")]);
+
+    symlink(shared.path(), dir.path().join("templates")).unwrap();
+    symlink(dir.path().join("values.yaml"), dir.path().join("values.prod.yaml")).unwrap();
+
+    let package = ChartPackager::new(dir.path()).package().unwrap();
+    assert_eq!(
+        package.files,
+        [
+            "Chart.yaml",
+            "templates/configmap.yaml",
+            "values.prod.yaml",
+            "values.yaml"
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_loops_are_errors() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir().unwrap();
+    write_chart(dir.path(), FILES);
+    symlink(dir.path(), dir.path().join("templates/loop")).unwrap();
+
+    let error = ChartPackager::new(dir.path()).package().unwrap_err();
+    assert!(error.to_string().contains("templates/loop"), "{error}");
+}
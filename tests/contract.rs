@@ -0,0 +1,516 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Contract tests that check the models against the schemas in charted-server's OpenAPI document
+//! (`openapi/charted.json`). Every model has to serialize into JSON that its schema accepts, and
+//! has to deserialize the examples that are generated from its schema, so a server upgrade that
+//! changes a schema is caught here instead of as an `Error::JsonSerialization` at runtime.
+//!
+//! The examples are generated three times for every schema: with only the required properties,
+//! with every property, and with every nullable property set to `null`.
+//!
+//! Only the schemas that this crate models have a contract; the rest of charted-server's API isn't
+//! covered by the SDK. The document has to be generated by the version of charted-server that is
+//! pinned in `openapi/VERSION` (with `scripts/generate-openapi.sh`): `document_is_from_charted_server`
+//! fails while it's the hand-written placeholder, which was written from the models and so can't
+//! catch drift from charted-server. It's ignored until the generated document is committed, and can
+//! be run with `cargo test --test contract -- --ignored`.
+
+#![cfg(feature = "testing")]
+
+use std::collections::BTreeSet;
+
+use charted::{
+    models::{
+        APIResponse, ApiError, DistributionType, FeaturesResponse, InfoResponse, MainResponse, Organization,
+        Repository, RepositoryType, SearchHit, SearchResult, User,
+    },
+    testing::fixtures,
+};
+use jsonschema::JSONSchema;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+/// A schema's name, and the check for the model that it belongs to.
+type Contract = (&'static str, fn(&str));
+
+/// Every schema in the OpenAPI document that is modelled by this crate.
+const CONTRACTS: &[Contract] = &[
+    ("ApiError", |name| {
+        check(
+            name,
+            &[ApiError {
+                code: "UNKNOWN_USER".into(),
+                message: "User was not found".into(),
+            }],
+        )
+    }),
+    ("ApiErrorResponse", |name| {
+        check(
+            name,
+            &[APIResponse::<()> {
+                success: false,
+                data: None,
+                errors: Some(vec![]),
+            }],
+        )
+    }),
+    ("MainResponse", |name| check::<MainResponse>(name, &[fixtures::main()])),
+    ("InfoResponse", |name| check::<InfoResponse>(name, &[fixtures::info()])),
+    ("FeaturesResponse", |name| {
+        check::<FeaturesResponse>(name, &[fixtures::features()])
+    }),
+    ("DistributionType", |name| {
+        use DistributionType::*;
+        check(name, &[Kubernetes, Unknown, Docker, RPM, Deb, Git])
+    }),
+    ("RepositoryType", |name| {
+        use RepositoryType::*;
+        check(name, &[Application, Library, Operator])
+    }),
+    ("User", |name| check(name, &[fixtures::user(1, "noel"), full_user()])),
+    ("Organization", |name| {
+        check(
+            name,
+            &[
+                fixtures::organization(2, "noelware", fixtures::user(1, "noel")),
+                Organization {
+                    owner: None,
+                    ..fixtures::organization(2, "noelware", full_user())
+                },
+                full_organization(),
+            ],
+        )
+    }),
+    ("Repository", |name| {
+        check(name, &[fixtures::repository(3, 1, "hello-world"), full_repository()])
+    }),
+    ("SearchResult", |name| check(name, &[search_result()])),
+    ("SearchHit", |name| {
+        check(
+            name,
+            &[
+                SearchHit {
+                    score: None,
+                    data: fixtures::repository(3, 1, "hello-world"),
+                },
+                search_result().hits.remove(0),
+            ],
+        )
+    }),
+];
+
+#[test]
+#[ignore = "openapi/charted.json is still the hand-written placeholder, run `./scripts/generate-openapi.sh` to replace it"]
+fn document_is_from_charted_server() {
+    let spec = spec();
+    assert!(
+        !spec["info"]["x-charted-placeholder"].as_bool().unwrap_or(false),
+        "openapi/charted.json is the hand-written placeholder, run `./scripts/generate-openapi.sh` to replace it"
+    );
+
+    assert_eq!(
+        spec["info"]["version"].as_str(),
+        Some(include_str!("../openapi/VERSION").trim()),
+        "openapi/charted.json wasn't generated by the version of charted-server in openapi/VERSION"
+    );
+}
+
+#[test]
+fn models_match_their_schemas() {
+    for (name, check) in CONTRACTS {
+        check(name);
+    }
+}
+
+#[test]
+fn every_contract_has_a_schema() {
+    let spec = spec();
+    for (name, _) in CONTRACTS {
+        assert!(
+            spec["components"]["schemas"].get(name).is_some(),
+            "the OpenAPI document doesn't have the `{name}` schema anymore"
+        );
+    }
+}
+
+#[test]
+fn responses_match_their_operations() {
+    fn ok<T: Serialize + std::fmt::Debug>(data: T) -> Value {
+        serde_json::to_value(APIResponse {
+            success: true,
+            data: Some(data),
+            errors: None,
+        })
+        .unwrap()
+    }
+
+    let responses = [
+        ("/", "200", ok(fixtures::main())),
+        ("/info", "200", ok(fixtures::info())),
+        ("/features", "200", ok(fixtures::features())),
+        ("/users/{idOrName}", "200", ok(full_user())),
+        ("/organizations/{idOrName}", "200", ok(full_organization())),
+        ("/repositories/{id}", "200", ok(full_repository())),
+        ("/users/{idOrName}/repositories", "200", ok(vec![full_repository()])),
+        ("/search/{index}", "200", ok(search_result())),
+        (
+            "/users/{idOrName}",
+            "404",
+            serde_json::to_value(APIResponse::<()> {
+                success: false,
+                data: None,
+                errors: Some(vec![ApiError {
+                    code: "UNKNOWN_USER".into(),
+                    message: "User was not found".into(),
+                }]),
+            })
+            .unwrap(),
+        ),
+    ];
+
+    let spec = spec();
+    for (path, status, response) in responses {
+        let schema = response_schema(&spec, path, status)
+            .unwrap_or_else(|| panic!("`GET {path}` doesn't have a JSON response for {status}"));
+
+        assert_valid(&compile(schema.clone()), &response, &format!("`GET {path}` ({status})"));
+    }
+}
+
+#[test]
+fn unknown_enum_values_are_kept() {
+    let mut info = serde_json::to_value(fixtures::info()).unwrap();
+    info["distribution"] = json!("nix");
+
+    let info: InfoResponse = serde_json::from_value(info).unwrap();
+    assert_eq!(info.distribution, DistributionType::Other("nix".into()));
+    assert_eq!(serde_json::to_value(&info).unwrap()["distribution"], "nix");
+
+    assert_eq!("docker".parse::<DistributionType>().unwrap(), DistributionType::Docker);
+    assert_eq!(DistributionType::RPM.to_string(), "rpm");
+}
+
+fn full_user() -> User {
+    User {
+        verified_publisher: true,
+        gravatar_email: Some("cutie@floofy.dev".into()),
+        description: Some("hewo".into()),
+        avatar_hash: Some("a1b2c3".into()),
+        admin: true,
+        name: Some("Noel".into()),
+        ..fixtures::user(1, "noel")
+    }
+}
+
+fn full_organization() -> Organization {
+    Organization {
+        verified_publisher: true,
+        twitter_handle: Some("Noelware".into()),
+        gravatar_email: Some("team@noelware.org".into()),
+        display_name: Some("Noelware".into()),
+        icon_hash: Some("d4e5f6".into()),
+        private: true,
+        ..fixtures::organization(2, "noelware", full_user())
+    }
+}
+
+fn full_repository() -> Repository {
+    Repository {
+        deprecated: true,
+        icon_hash: Some("a7b8c9".into()),
+        private: true,
+        repository_type: RepositoryType::Library,
+        ..fixtures::repository(3, 1, "hello-world")
+    }
+}
+
+fn search_result() -> SearchResult<Repository> {
+    SearchResult {
+        took: 3,
+        total_hits: 1,
+        hits: vec![SearchHit {
+            score: Some(0.92),
+            data: full_repository(),
+        }],
+    }
+}
+
+// Checks that every instance serializes into JSON that the schema accepts, and that the model
+// deserializes every example of the schema
+fn check<T: Serialize + DeserializeOwned>(name: &str, instances: &[T]) {
+    let spec = spec();
+    let schema = resolve(&spec, &spec["components"]["schemas"][name]);
+    let validator = compile(json!({ "$ref": format!("#/components/schemas/{name}") }));
+
+    for instance in instances {
+        let json = serde_json::to_value(instance).unwrap();
+        assert_valid(&validator, &json, &format!("serialized `{name}`"));
+    }
+
+    for example in examples(&spec, schema) {
+        assert_valid(&validator, &example, &format!("example of `{name}`"));
+        if let Err(e) = serde_json::from_value::<T>(example.clone()) {
+            panic!("unable to deserialize an example of `{name}`: {e}\n{example:#}");
+        }
+    }
+}
+
+fn assert_valid(validator: &JSONSchema, instance: &Value, what: &str) {
+    if let Err(errors) = validator.validate(instance) {
+        let errors = errors
+            .map(|e| format!("  - {} (at `{}`)", e, e.instance_path))
+            .collect::<Vec<_>>();
+
+        panic!("{what} doesn't match its schema:\n{}\n{instance:#}", errors.join("\n"));
+    }
+}
+
+fn spec() -> Value {
+    serde_json::from_str(include_str!("../openapi/charted.json")).unwrap()
+}
+
+// Follows a `$ref` in the document, or returns the value itself if it isn't one
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"].as_str().and_then(|reference| reference.strip_prefix('#')) {
+        Some(pointer) => spec
+            .pointer(pointer)
+            .map(|target| resolve(spec, target))
+            .unwrap_or(value),
+        None => value,
+    }
+}
+
+// Returns the schema of an operation's JSON response. Path parameters can have any name, and the
+// status can also be matched by a range (i.e, `4XX`) or the `default` response.
+fn response_schema<'a>(spec: &'a Value, path: &str, status: &str) -> Option<&'a Value> {
+    let normalize = |path: &str| {
+        path.trim_end_matches('/')
+            .split('/')
+            .map(|segment| if segment.starts_with('{') { "{}" } else { segment })
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let (_, item) = spec["paths"]
+        .as_object()?
+        .iter()
+        .find(|(candidate, _)| normalize(candidate) == normalize(path))?;
+
+    let responses = &resolve(spec, item)["get"]["responses"];
+    let range = format!("{}XX", &status[..1]);
+    let response = [status, range.as_str(), "default"]
+        .into_iter()
+        .map(|status| &responses[status])
+        .find(|response| !response.is_null())?;
+
+    resolve(spec, response)["content"]
+        .as_object()?
+        .iter()
+        .find(|(content_type, _)| content_type.starts_with("application/json"))
+        .map(|(_, media)| &media["schema"])
+}
+
+// Compiles a schema from the OpenAPI document, which can reference the document's schemas
+fn compile(schema: Value) -> JSONSchema {
+    let spec = spec();
+    let composed = composed_schemas(&spec);
+
+    let mut root = to_json_schema(&json!({ "allOf": [schema] }), true);
+    root["components"] = to_json_schema(&spec["components"], true);
+    for (name, schema) in spec["components"]["schemas"].as_object().into_iter().flatten() {
+        root["components"]["schemas"][name] = to_json_schema(schema, !composed.contains(name));
+    }
+
+    JSONSchema::options()
+        .with_draft(jsonschema::Draft::Draft7)
+        .compile(&root)
+        .unwrap()
+}
+
+// Returns the schemas that are a part of an `allOf` with other schemas, which can't be closed since
+// each part of the `allOf` only has some of the properties
+fn composed_schemas(value: &Value) -> BTreeSet<String> {
+    let mut composed = BTreeSet::new();
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(parts)) = object
+                .get("allOf")
+                .filter(|parts| parts.as_array().map_or(0, Vec::len) > 1)
+            {
+                composed.extend(
+                    parts
+                        .iter()
+                        .filter_map(|part| part["$ref"].as_str())
+                        .filter_map(|reference| reference.strip_prefix("#/components/schemas/"))
+                        .map(str::to_owned),
+                );
+            }
+
+            object
+                .values()
+                .for_each(|value| composed.extend(composed_schemas(value)));
+        }
+
+        Value::Array(values) => values.iter().for_each(|value| composed.extend(composed_schemas(value))),
+        _ => {}
+    }
+
+    composed
+}
+
+// OpenAPI 3.0 schemas aren't quite JSON Schema, so `nullable` is replaced with a `null` type. Objects
+// are also closed (unless they're a part of an `allOf`), so that models can't send properties that the
+// server doesn't know about.
+fn to_json_schema(value: &Value, close: bool) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut schema = object
+                .iter()
+                .filter(|(key, _)| key.as_str() != "nullable")
+                .map(|(key, value)| match (key.as_str(), value) {
+                    ("allOf", Value::Array(parts)) if parts.len() > 1 => (
+                        key.clone(),
+                        Value::Array(parts.iter().map(|part| to_json_schema(part, false)).collect()),
+                    ),
+
+                    _ => (key.clone(), to_json_schema(value, true)),
+                })
+                .collect::<Map<_, _>>();
+
+            if close && schema.contains_key("properties") && !schema.contains_key("additionalProperties") {
+                schema.insert("additionalProperties".into(), false.into());
+            }
+
+            if object.get("nullable") != Some(&Value::Bool(true)) {
+                return Value::Object(schema);
+            }
+
+            match schema.get("type").cloned() {
+                Some(Value::String(ty)) => {
+                    schema.insert("type".into(), json!([ty, "null"]));
+                    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+                        values.push(Value::Null);
+                    }
+
+                    Value::Object(schema)
+                }
+
+                _ => json!({ "anyOf": [schema, { "type": "null" }] }),
+            }
+        }
+
+        Value::Array(values) => Value::Array(values.iter().map(|value| to_json_schema(value, close)).collect()),
+        value => value.clone(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Example {
+    Required,
+    Full,
+    Nulls,
+}
+
+fn examples(spec: &Value, schema: &Value) -> Vec<Value> {
+    let mut examples = [Example::Required, Example::Full, Example::Nulls]
+        .into_iter()
+        .map(|kind| example(spec, schema, kind))
+        .collect::<Vec<_>>();
+
+    // every value of an enum has to be accepted
+    if let Some(values) = schema["enum"].as_array() {
+        examples.extend(values.iter().cloned());
+    }
+
+    examples.dedup();
+    examples
+}
+
+fn example(spec: &Value, schema: &Value, kind: Example) -> Value {
+    let schema = resolve(spec, schema);
+    match schema["allOf"].as_array().map(Vec::as_slice) {
+        Some([inner]) => return example(spec, inner, kind),
+
+        // every part of the `allOf` is an object that has some of the properties
+        Some(parts) => {
+            let mut object = Map::new();
+            for part in parts {
+                if let Value::Object(properties) = example(spec, part, kind) {
+                    object.extend(properties);
+                }
+            }
+
+            return Value::Object(object);
+        }
+
+        None => {}
+    }
+
+    if let Some(variant) = schema["oneOf"]
+        .as_array()
+        .or_else(|| schema["anyOf"].as_array())
+        .and_then(|variants| variants.iter().find(|variant| variant["type"] != "null"))
+    {
+        return example(spec, variant, kind);
+    }
+
+    if let Some(value) = schema["enum"].as_array().and_then(|values| values.first()) {
+        return value.clone();
+    }
+
+    // objects don't always have a `type` if they have properties
+    let ty = schema["type"]
+        .as_str()
+        .or_else(|| schema["properties"].is_object().then_some("object"));
+
+    match (ty, schema["format"].as_str()) {
+        (Some("string"), Some("date-time")) => json!("2023-03-14T18:32:18Z"),
+        (Some("string"), _) => json!("string"),
+        (Some("integer"), _) => json!(1),
+        (Some("number"), _) => json!(1.5),
+        (Some("boolean"), _) => json!(true),
+        (Some("array"), _) if kind == Example::Required => json!([]),
+        (Some("array"), _) => json!([example(spec, &schema["items"], kind)]),
+        (Some("object"), _) => {
+            let required = schema["required"].as_array().cloned().unwrap_or_default();
+            let mut object = Map::new();
+            for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+                let is_required = required.contains(&Value::String(name.clone()));
+                let value = match kind {
+                    Example::Required if !is_required => continue,
+                    Example::Nulls if property["nullable"] == true => Value::Null,
+                    _ => example(spec, property, kind),
+                };
+
+                object.insert(name.clone(), value);
+            }
+
+            if schema["additionalProperties"].is_object() && kind != Example::Required {
+                object.insert("key".into(), example(spec, &schema["additionalProperties"], kind));
+            }
+
+            Value::Object(object)
+        }
+
+        _ => panic!("unable to generate an example of {schema}"),
+    }
+}
@@ -0,0 +1,412 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for resolving a chart's dependencies with a [`DependencyResolver`] against the fake server.
+
+#![cfg(all(feature = "testing", feature = "indexes"))]
+
+use charted::{
+    chart::{DependencyError, DependencyResolver},
+    models::{ChartDependency, ChartIndexSpec, ChartIndexYaml, ChartLock, ChartYaml},
+    testing::{fixtures, FakeServer},
+    Error,
+};
+
+/// Starts a server where `noel` has the `hello` and `common` charts, and `noelware` has `postgresql`
+/// (which depends on `noel/common`).
+fn server() -> (FakeServer, String, String) {
+    let server = FakeServer::start();
+    let indexes = server.client().indexes();
+    let (noel, noelware) = (indexes.repository_url("noel"), indexes.repository_url("noelware"));
+
+    let spec = |name: &str, version: &str, dependencies: &[(&str, &str, &str)]| {
+        let mut builder = ChartIndexSpec::builder(name, version);
+        for (name, version, repository) in dependencies {
+            builder.dependency(
+                ChartDependency::builder(*name)
+                    .version(*version)
+                    .repository(*repository)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        builder.build().unwrap()
+    };
+
+    server.set_index(
+        "noel",
+        index([
+            spec("hello", "0.1.0", &[]),
+            spec("hello", "0.2.0", &[]),
+            spec("hello", "1.0.0-rc.1", &[]),
+            spec("common", "1.4.0", &[]),
+            spec("common", "2.1.0", &[]),
+            spec("common", "2.2.0", &[]),
+        ]),
+    );
+
+    server.set_index(
+        "noelware",
+        index([
+            spec("postgresql", "12.1.0", &[("common", "~2.1", &noel)]),
+            spec("loop", "1.0.0", &[("cycle", "*", &noelware)]),
+            spec("cycle", "1.0.0", &[("loop", "*", &noelware)]),
+            spec("self", "1.0.0", &[("self", "2.x", &noelware)]),
+            spec("self", "2.0.0", &[]),
+        ]),
+    );
+
+    (server, noel, noelware)
+}
+
+fn index<I: IntoIterator<Item = ChartIndexSpec>>(specs: I) -> ChartIndexYaml {
+    let mut index = ChartIndexYaml {
+        api_version: "v1".into(),
+        generated: fixtures::timestamp(),
+        ..Default::default()
+    };
+
+    for spec in specs {
+        index.entries.entry(spec.name.clone()).or_default().push(spec);
+    }
+
+    index
+}
+
+fn chart(dependencies: Vec<ChartDependency>) -> ChartYaml {
+    ChartYaml {
+        name: "mychart".into(),
+        version: "0.1.0".into(),
+        dependencies,
+        ..Default::default()
+    }
+}
+
+fn dependency(name: &str, version: &str, repository: &str) -> ChartDependency {
+    ChartDependency::builder(name)
+        .version(version)
+        .repository(repository)
+        .build()
+        .unwrap()
+}
+
+fn dependency_error(result: charted::Result<impl std::fmt::Debug>) -> DependencyError {
+    match result {
+        Err(Error::Dependency(error)) => error,
+        other => panic!("expected a dependency error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn resolves_the_newest_matching_versions() {
+    let (server, noel, noelware) = server();
+    let chart = chart(vec![
+        dependency("hello", "^0.1", &noel),
+        // the caret keeps the minor version below 1.0.0, so 0.2.0 is not a candidate
+        dependency("common", ">=1, <3", &noel),
+        dependency("postgresql", "12.x", &noelware),
+    ]);
+
+    let resolution = DependencyResolver::new(server.client().indexes())
+        .resolve(&chart)
+        .await
+        .unwrap();
+
+    let locked = resolution
+        .lock
+        .dependencies
+        .iter()
+        .map(|d| {
+            (
+                d.name.as_str(),
+                d.version.as_deref().unwrap(),
+                d.repository.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        locked,
+        [
+            ("hello", "0.1.0", noel.as_str()),
+            ("common", "2.2.0", noel.as_str()),
+            ("postgresql", "12.1.0", noelware.as_str())
+        ]
+    );
+
+    let postgresql = &resolution.graph[2];
+    assert_eq!(postgresql.dependencies.len(), 1);
+    assert_eq!(postgresql.dependencies[0].version, "2.1.0");
+    assert_eq!(postgresql.dependencies[0].constraint, "~2.1");
+
+    // `common` is resolved to 2.1.0 for postgresql and to 2.2.0 for the chart
+    assert_eq!(resolution.conflicts.len(), 1);
+    assert_eq!(resolution.conflicts[0].name, "common");
+    assert_eq!(resolution.conflicts[0].versions, ["2.1.0", "2.2.0"]);
+
+    assert!(resolution.lock.digest.starts_with("sha256:"));
+
+    // every index is only fetched once
+    assert_eq!(server.requests(), ["GET /indexes/noel", "GET /indexes/noelware"]);
+}
+
+#[tokio::test]
+async fn unsatisfiable_and_missing_charts() {
+    let (server, noel, _) = server();
+    let indexes = server.client().indexes();
+
+    let error = dependency_error(
+        DependencyResolver::new(indexes.clone())
+            .resolve(&chart(vec![dependency("hello", ">=3", &noel)]))
+            .await,
+    );
+
+    assert!(matches!(error, DependencyError::Unsatisfiable { name, .. } if name == "hello"));
+
+    let error = dependency_error(
+        DependencyResolver::new(indexes.clone())
+            .resolve(&chart(vec![dependency("hello", "*", &indexes.repository_url("ice"))]))
+            .await,
+    );
+
+    assert!(matches!(error, DependencyError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn duplicate_names() {
+    let (server, noel, _) = server();
+    let mut aliased = dependency("common", "2.x", &noel);
+    aliased.alias = Some("hello".into());
+
+    let error = dependency_error(
+        DependencyResolver::new(server.client().indexes())
+            .resolve(&chart(vec![dependency("hello", "*", &noel), aliased]))
+            .await,
+    );
+
+    assert!(matches!(error, DependencyError::DuplicateName(name) if name == "hello"));
+}
+
+#[tokio::test]
+async fn cycles_are_detected_across_versions() {
+    let (server, _, noelware) = server();
+    let indexes = server.client().indexes();
+
+    let error = dependency_error(
+        DependencyResolver::new(indexes.clone())
+            .resolve(&chart(vec![dependency("loop", "1.0.0", &noelware)]))
+            .await,
+    );
+
+    match error {
+        DependencyError::Cycle(cycle) => assert_eq!(
+            cycle,
+            [
+                "mychart@0.1.0".to_owned(),
+                format!("{noelware}/loop@1.0.0"),
+                format!("{noelware}/cycle@1.0.0"),
+                format!("{noelware}/loop@1.0.0"),
+            ]
+        ),
+
+        other => panic!("expected a cycle, got {other:?}"),
+    }
+
+    // self@1.0.0 depends on self@2.0.0
+    let error = dependency_error(
+        DependencyResolver::new(indexes.clone())
+            .resolve(&chart(vec![dependency("self", "1.0.0", &noelware)]))
+            .await,
+    );
+
+    assert!(matches!(error, DependencyError::Cycle(cycle) if cycle.len() == 3));
+
+    // a dependency on the chart that is being resolved
+    let mut chart = chart(vec![dependency("postgresql", "*", &noelware)]);
+    chart.name = "common".into();
+
+    let error = dependency_error(DependencyResolver::new(indexes).resolve(&chart).await);
+    assert!(matches!(error, DependencyError::Cycle(_)));
+}
+
+#[tokio::test]
+async fn repository_aliases() {
+    let (server, noel, _) = server();
+    let chart = chart(vec![
+        dependency("hello", "0.1.x", "@noel"),
+        dependency("common", "1.x", "alias:noel"),
+    ]);
+
+    let mut resolver = DependencyResolver::new(server.client().indexes());
+    let error = dependency_error(resolver.resolve(&chart).await);
+    assert!(matches!(error, DependencyError::UnsupportedRepository(repository) if repository == "@noel"));
+
+    let resolution = resolver.repository("noel", &noel).resolve(&chart).await.unwrap();
+    assert_eq!(resolution.graph[0].version, "0.1.0");
+    assert_eq!(resolution.graph[0].repository, noel);
+    assert_eq!(resolution.graph[1].version, "1.4.0");
+
+    // like Helm, the lock file has the repository's URL, which the digest is computed with
+    assert_eq!(
+        resolution.lock.dependencies[0].repository.as_deref(),
+        Some(noel.as_str())
+    );
+    assert_eq!(
+        resolution.lock.dependencies[1].repository.as_deref(),
+        Some(noel.as_str())
+    );
+
+    let resolved = vec![dependency("hello", "0.1.x", &noel), dependency("common", "1.x", &noel)];
+
+    assert_eq!(
+        resolution.lock.digest,
+        ChartLock::compute_digest(&resolved, &resolution.lock.dependencies)
+    );
+
+    assert!(!resolver.drift(&resolution.lock, &chart).unwrap().is_stale());
+    assert!(resolution.lock.drift(&chart).is_stale());
+}
+
+#[test]
+fn helm_digest() {
+    // the SHA-256 of `json.Marshal([2][]*chart.Dependency{requested, locked})`, which is how Helm's
+    // `resolver.HashReq` computes the digest; `@bitnami` was resolved to its URL in both lists
+    let bitnami = "https://charts.bitnami.com/bitnami";
+
+    let mut postgresql = dependency("postgresql", ">=12, <13", bitnami);
+    postgresql.condition = Some("postgresql.enabled".into());
+
+    let mut common = dependency("common", "2.x.x", bitnami);
+    common.tags = vec!["bitnami-common".into()];
+    common.alias = Some("lib".into());
+
+    let lock = ChartLock::from_yaml(
+        "dependencies:
+- name: postgresql
+  repository: https://charts.bitnami.com/bitnami
+  version: 12.12.10
+- name: common
+  repository: https://charts.bitnami.com/bitnami
+  version: 2.13.3
+digest: sha256:e8d0770f952dfc79bb2216ad97c4081f284efd9dd20c8a3cd116f3538dcd67ca
+generated: \"2023-10-03T17:43:23.811294Z\"
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ChartLock::compute_digest(&[postgresql, common], &lock.dependencies),
+        lock.digest
+    );
+}
+
+#[tokio::test]
+async fn unsupported_repositories() {
+    let (server, _, _) = server();
+    for repository in [
+        "https://charts.bitnami.com/bitnami",
+        "oci://registry-1.docker.io/bitnamicharts",
+        &format!("{}/users/noel", server.url()),
+    ] {
+        let error = dependency_error(
+            DependencyResolver::new(server.client().indexes())
+                .resolve(&chart(vec![dependency("common", "*", repository)]))
+                .await,
+        );
+
+        assert!(
+            matches!(error, DependencyError::UnsupportedRepository(_)),
+            "{repository}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn local_charts() {
+    let (server, _, _) = server();
+    let dir = tempfile::tempdir().unwrap();
+
+    let shared = dir.path().join("shared");
+    std::fs::create_dir_all(&shared).unwrap();
+    std::fs::write(
+        shared.join("Chart.yaml"),
+        "apiVersion: v2\nname: shared\nversion: 1.2.3\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("chart/charts/vendored")).unwrap();
+
+    let chart = chart(vec![
+        dependency("shared", "^1.2", "file://../shared"),
+        dependency("vendored", "~0.3", ""),
+    ]);
+
+    // without the chart's directory, only exact versions can be locked
+    let mut resolver = DependencyResolver::new(server.client().indexes());
+    let error = dependency_error(resolver.resolve(&chart).await);
+    assert!(matches!(error, DependencyError::MissingChartDir(name) if name == "shared"));
+
+    let exact = self::chart(vec![dependency("shared", "1.2.3", "file://../shared")]);
+    assert_eq!(resolver.resolve(&exact).await.unwrap().graph[0].version, "1.2.3");
+
+    let resolution = resolver
+        .chart_dir(dir.path().join("chart"))
+        .resolve(&chart)
+        .await
+        .unwrap();
+
+    let locked = resolution
+        .lock
+        .dependencies
+        .iter()
+        .map(|d| {
+            (
+                d.name.as_str(),
+                d.version.as_deref().unwrap(),
+                d.repository.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        locked,
+        [("shared", "1.2.3", "file://../shared"), ("vendored", "~0.3", "")]
+    );
+
+    let error = dependency_error(
+        resolver
+            .resolve(&self::chart(vec![dependency("shared", ">=2", "file://../shared")]))
+            .await,
+    );
+
+    assert!(matches!(error, DependencyError::Unsatisfiable { .. }));
+
+    for missing in [
+        dependency("missing", "*", "file://../missing"),
+        dependency("missing", "*", ""),
+    ] {
+        let error = dependency_error(resolver.resolve(&self::chart(vec![missing])).await);
+        assert!(matches!(error, DependencyError::NotFound { .. }));
+    }
+
+    // nothing was fetched from the server
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,181 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests the API client against the in-process fake server from the `testing` feature.
+
+#![cfg(all(feature = "testing", feature = "indexes"))]
+
+#[cfg(feature = "cache")]
+use charted::cache::MemoryStore;
+use charted::{
+    auth::ApiKeyStrategy,
+    testing::{fixtures, FakeServer},
+    APIClient, Error,
+};
+
+#[tokio::test]
+async fn meta() {
+    let server = FakeServer::start();
+    let client = server.client();
+
+    client.health().await.unwrap();
+    assert_eq!(client.main().await.unwrap().message, fixtures::main().message);
+    assert_eq!(client.info().await.unwrap().version, fixtures::info().version);
+    assert_eq!(server.requests(), ["GET /health", "GET /", "GET /info"]);
+}
+
+#[cfg(feature = "cache")]
+#[tokio::test]
+async fn indexes() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.set_index("noel", fixtures::chart_index());
+
+    let indexes = APIClient::builder()
+        .base_url(server.url())
+        .cache(MemoryStore::new())
+        .build()
+        .indexes();
+    let index = indexes.get("noel").await.unwrap().unwrap();
+    assert_eq!(index.entries["hello-world"].len(), 3);
+
+    // the second request is revalidated and the server replies with a 304
+    let cached = indexes.get_by_id(1).await.unwrap().unwrap();
+    assert_eq!(cached.entries.len(), index.entries.len());
+    assert!(indexes.get("noel").await.unwrap().is_some());
+    assert!(indexes.get("ice").await.unwrap().is_none());
+}
+
+#[cfg(feature = "chart-packaging")]
+#[tokio::test]
+async fn download() {
+    let server = FakeServer::start();
+    let package = fixtures::chart_package("hello-world", "0.1.0");
+    let spec = server.add_chart("noel", fixtures::chart_spec("hello-world", "0.1.0"), package.clone());
+
+    let indexes = server.client().indexes();
+    assert_eq!(indexes.download("noel", &spec).await.unwrap(), package);
+
+    let mut tampered = spec.clone();
+    tampered.digest = Some("0".repeat(64));
+    assert!(matches!(
+        indexes.download("noel", &tampered).await,
+        Err(Error::DigestMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "chart-packaging")]
+#[tokio::test]
+async fn download_without_digest() {
+    let server = FakeServer::start();
+    let mut spec = server.add_chart(
+        "noel",
+        fixtures::chart_spec("hello-world", "0.1.0"),
+        fixtures::chart_package("hello-world", "0.1.0"),
+    );
+
+    spec.digest = None;
+    let result = server.client().indexes().download("noel", &spec).await;
+    assert!(
+        matches!(&result, Err(Error::MissingDigest { name, version }) if name == "hello-world" && version == "0.1.0"),
+        "{result:?}"
+    );
+
+    // nothing should be downloaded if the digest is missing
+    assert!(server.requests().is_empty());
+
+    let indexes = server.client().indexes().allow_missing_digests(true);
+    assert!(indexes.download("noel", &spec).await.is_ok());
+}
+
+#[cfg(feature = "chart-packaging")]
+#[tokio::test]
+async fn credentials_are_only_sent_to_the_api_server() {
+    let server = FakeServer::start();
+    let elsewhere = FakeServer::start();
+    let local = server.add_chart(
+        "noel",
+        fixtures::chart_spec("hello-world", "0.1.0"),
+        fixtures::chart_package("hello-world", "0.1.0"),
+    );
+
+    let foreign = elsewhere.add_chart(
+        "noel",
+        fixtures::chart_spec("postgresql", "12.1.0"),
+        fixtures::chart_package("postgresql", "12.1.0"),
+    );
+
+    let client = APIClient::builder()
+        .base_url(server.url())
+        .auth_strategy(ApiKeyStrategy::new("s3cr3t"))
+        .build();
+
+    let indexes = client.indexes();
+    indexes.download("noel", &local).await.unwrap();
+    indexes.download("noel", &foreign).await.unwrap();
+
+    assert_eq!(server.authorizations(), [Some("ApiKey s3cr3t".to_owned())]);
+    assert_eq!(elsewhere.requests(), ["GET /charts/noel/postgresql-12.1.0.tgz"]);
+    assert_eq!(elsewhere.authorizations(), [None]);
+}
+
+#[cfg(feature = "chart-packaging")]
+#[tokio::test]
+async fn download_to_stays_in_the_directory() {
+    let server = FakeServer::start();
+    let spec = server.add_chart(
+        "noel",
+        fixtures::chart_spec("hello-world", "0.1.0"),
+        fixtures::chart_package("hello-world", "0.1.0"),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("mirror");
+    let indexes = server.client().indexes();
+    let path = indexes.download_to("noel", &spec, &target).await.unwrap();
+    assert_eq!(path, target.join("hello-world-0.1.0.tgz"));
+
+    for (name, version) in [
+        ("../hello-world", "0.1.0"),
+        ("hello-world", "0.1.0/../../evil"),
+        ("/etc/hello-world", "0.1.0"),
+        ("hello\\world", "0.1.0"),
+        ("..", "0.1.0"),
+        ("", "0.1.0"),
+    ] {
+        let mut evil = spec.clone();
+        evil.name = name.into();
+        evil.version = version.into();
+
+        assert!(
+            matches!(indexes.download_to("noel", &evil, &target).await, Err(Error::String(_))),
+            "{name} {version}"
+        );
+    }
+
+    let mut entries = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, ["mirror"]);
+    assert_eq!(std::fs::read_dir(&target).unwrap().count(), 1);
+}
@@ -0,0 +1,288 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for searching a [`ChartIndexYaml`] in-process with an [`IndexSearch`].
+
+#![cfg(feature = "yaml")]
+
+use charted::{
+    index::IndexSearch,
+    models::{ChartIndexSpec, ChartIndexYaml, ChartMaintainer, RepositoryType},
+};
+
+fn index<I: IntoIterator<Item = ChartIndexSpec>>(specs: I) -> ChartIndexYaml {
+    let mut index = ChartIndexYaml::default();
+    for spec in specs {
+        index.entries.entry(spec.name.clone()).or_default().push(spec);
+    }
+
+    index
+}
+
+fn names(index: &ChartIndexYaml, search: &IndexSearch) -> Vec<String> {
+    index
+        .search(search)
+        .into_iter()
+        .map(|hit| format!("{}@{}", hit.name, hit.spec.version))
+        .collect()
+}
+
+#[test]
+fn ranks_by_relevance() {
+    let index = index([
+        ChartIndexSpec::builder("mysql", "1.0.0")
+            .description("an alternative to postgres")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("pgadmin", "1.0.0")
+            .keyword("postgres")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("postgresql", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("postgres", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("post-gres-ui", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("redis", "1.0.0")
+            .description("a key-value store")
+            .build()
+            .unwrap(),
+    ]);
+
+    // exact name > name prefix > exact keyword > name subsequence > description substring, since
+    // matches on the name weigh three times as much as ones in the description
+    assert_eq!(
+        names(&index, &IndexSearch::new("Postgres")),
+        [
+            "postgres@1.0.0",
+            "postgresql@1.0.0",
+            "pgadmin@1.0.0",
+            "post-gres-ui@1.0.0",
+            "mysql@1.0.0"
+        ]
+    );
+
+    let hits = index.search(&IndexSearch::new("postgres"));
+    assert!(hits.windows(2).all(|w| w[0].score > w[1].score), "{hits:#?}");
+}
+
+#[test]
+fn descriptions_are_only_matched_by_substring() {
+    let index = index([
+        ChartIndexSpec::builder("grafana", "1.0.0")
+            .description("The leading tool for querying and visualizing time series and metrics")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("nginx", "1.0.0")
+            .description("NGINX Open Source is a web server that can also be used as a reverse proxy")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("pgsql", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("plugin-manager-for-server", "1.0.0")
+            .build()
+            .unwrap(),
+    ]);
+
+    // "pgs" is a subsequence of both descriptions, and of the last name with its characters
+    // spread over the whole name, so only the compact match on a name is returned
+    assert_eq!(names(&index, &IndexSearch::new("pgs")), ["pgsql@1.0.0"]);
+    assert_eq!(names(&index, &IndexSearch::new("reverse proxy")), ["nginx@1.0.0"]);
+}
+
+#[test]
+fn skips_removed_versions() {
+    let index = index([
+        ChartIndexSpec::builder("hello", "0.1.0").build().unwrap(),
+        ChartIndexSpec::builder("hello", "1.0.0").removed(true).build().unwrap(),
+        ChartIndexSpec::builder("gone", "1.0.0").removed(true).build().unwrap(),
+    ]);
+
+    assert_eq!(names(&index, &IndexSearch::new("hello")), ["hello@0.1.0"]);
+    assert_eq!(names(&index, IndexSearch::new("").all_versions(true)), ["hello@0.1.0"]);
+}
+
+#[test]
+fn ties_and_empty_queries_are_sorted_by_name() {
+    let index = index([
+        ChartIndexSpec::builder("zookeeper", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("apache", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("kafka", "1.0.0").build().unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, &IndexSearch::new("  ")),
+        ["apache@1.0.0", "kafka@1.0.0", "zookeeper@1.0.0"]
+    );
+
+    assert!(index.search(&IndexSearch::new("")).iter().all(|hit| hit.score == 0));
+    assert!(index.search(&IndexSearch::new("postgres")).is_empty());
+}
+
+#[test]
+fn returns_the_newest_version_unless_all_versions() {
+    let index = index([
+        ChartIndexSpec::builder("hello", "0.1.0").build().unwrap(),
+        ChartIndexSpec::builder("hello", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("hello", "0.2.0").build().unwrap(),
+    ]);
+
+    assert_eq!(names(&index, &IndexSearch::new("hello")), ["hello@1.0.0"]);
+    assert_eq!(
+        names(&index, IndexSearch::new("hello").all_versions(true)),
+        ["hello@1.0.0", "hello@0.2.0", "hello@0.1.0"]
+    );
+}
+
+#[test]
+fn keeps_versions_that_are_not_semver() {
+    let index = index([
+        ChartIndexSpec::builder("hello", "latest").build().unwrap(),
+        ChartIndexSpec::builder("hello", "0.1.0").build().unwrap(),
+        ChartIndexSpec::builder("nightly", "main").build().unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").all_versions(true)),
+        ["hello@0.1.0", "hello@latest", "nightly@main"]
+    );
+}
+
+#[test]
+fn filters_by_deprecation() {
+    let index = index([
+        ChartIndexSpec::builder("old", "1.0.0")
+            .deprecated(true)
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("new", "1.0.0")
+            .deprecated(false)
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("unset", "1.0.0").build().unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").deprecated(false)),
+        ["new@1.0.0", "unset@1.0.0"]
+    );
+
+    assert_eq!(names(&index, IndexSearch::new("").deprecated(true)), ["old@1.0.0"]);
+}
+
+#[test]
+fn filters_by_chart_type() {
+    let index = index([
+        ChartIndexSpec::builder("app", "1.0.0")
+            .chart_type(RepositoryType::Application)
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("common", "1.0.0")
+            .chart_type(RepositoryType::Library)
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("untyped", "1.0.0").build().unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").chart_type(RepositoryType::Application)),
+        ["app@1.0.0", "untyped@1.0.0"]
+    );
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").chart_type(RepositoryType::Library)),
+        ["common@1.0.0"]
+    );
+}
+
+#[test]
+fn filters_by_maintainer() {
+    let index = index([
+        ChartIndexSpec::builder("noel", "1.0.0")
+            .maintainer(ChartMaintainer::builder("Noel Towa").build().unwrap())
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("email", "1.0.0")
+            .maintainer(
+                ChartMaintainer::builder("Someone")
+                    .email("team@NOELWARE.org")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("other", "1.0.0")
+            .maintainer(ChartMaintainer::builder("Someone Else").build().unwrap())
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("nobody", "1.0.0").build().unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").maintainer("noel")),
+        ["email@1.0.0", "noel@1.0.0"]
+    );
+}
+
+#[test]
+fn filters_by_kube_version() {
+    let index = index([
+        ChartIndexSpec::builder("new", "1.0.0")
+            .kube_version(">=1.25.0-0")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("old", "1.0.0")
+            .kube_version("<1.20")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("any", "1.0.0").build().unwrap(),
+        ChartIndexSpec::builder("weird", "1.0.0")
+            .kube_version("not a constraint")
+            .build()
+            .unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").kube_version("v1.26.3")),
+        ["any@1.0.0", "new@1.0.0", "weird@1.0.0"]
+    );
+
+    assert_eq!(
+        names(&index, IndexSearch::new("").kube_version("1.19.0")),
+        ["any@1.0.0", "old@1.0.0", "weird@1.0.0"]
+    );
+}
+
+#[test]
+fn filters_only_consider_matching_versions() {
+    let index = index([
+        ChartIndexSpec::builder("hello", "2.0.0")
+            .kube_version(">=1.27")
+            .build()
+            .unwrap(),
+        ChartIndexSpec::builder("hello", "1.0.0")
+            .kube_version(">=1.20")
+            .build()
+            .unwrap(),
+    ]);
+
+    assert_eq!(
+        names(&index, IndexSearch::new("hello").kube_version("1.26.0")),
+        ["hello@1.0.0"]
+    );
+}
@@ -0,0 +1,198 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests that check that `index.yaml` files generated by Helm can be read and written back
+//! without losing anything, see `tests/fixtures/helm/README.md` for where the fixtures come from.
+
+#![cfg(feature = "yaml")]
+
+use charted::models::{ApiVersion, ChartIndexYaml, RepositoryType};
+use serde_yaml::Value;
+
+const FIXTURES: &[(&str, &str)] = &[
+    ("bitnami.yaml", include_str!("fixtures/helm/bitnami.yaml")),
+    ("local.yaml", include_str!("fixtures/helm/local.yaml")),
+];
+
+#[test]
+fn fixtures_round_trip() {
+    for (name, contents) in FIXTURES {
+        let index = ChartIndexYaml::from_yaml(contents).unwrap_or_else(|e| panic!("failed to parse {name}: {e}"));
+        let written = index.to_yaml().unwrap();
+
+        let expected: Value = serde_yaml::from_str(contents).unwrap();
+        let actual: Value = serde_yaml::from_str(&written).unwrap();
+        assert_eq!(expected, actual, "{name} didn't round-trip, wrote:\n{written}");
+    }
+}
+
+#[test]
+fn writes_keys_in_helm_order() {
+    let index = ChartIndexYaml::from_yaml(FIXTURES[1].1).unwrap();
+    let written = index.to_yaml().unwrap();
+    let top_level = written
+        .lines()
+        .filter(|line| !line.starts_with(' ') && !line.starts_with('-'))
+        .map(|line| line.split(':').next().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(top_level, ["apiVersion", "entries", "generated"]);
+
+    let entries = written
+        .lines()
+        .filter(|line| line.starts_with("  ") && !line.starts_with("   ") && line.ends_with(':'))
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries, ["  hello:", "  legacy:", "  mylib:"]);
+}
+
+#[test]
+fn writes_timestamps_like_helm() {
+    let contents = r#"apiVersion: v1
+entries:
+  hello:
+  - apiVersion: v2
+    created: "2023-03-14T18:32:18Z"
+    name: hello
+    version: 0.1.0
+generated: "2023-03-14T18:32:18.493813472Z"
+"#;
+
+    let index = ChartIndexYaml::from_yaml(contents).unwrap();
+    assert_eq!(
+        index.entries["hello"][0].created.unwrap().to_rfc3339(),
+        "2023-03-14T18:32:18+00:00"
+    );
+
+    let written = index.to_yaml().unwrap();
+    assert!(
+        written.contains("generated: 2023-03-14T18:32:18.493813472Z"),
+        "{written}"
+    );
+}
+
+#[test]
+fn parses_helm_fields() {
+    let index = ChartIndexYaml::from_yaml(FIXTURES[1].1).unwrap();
+    assert_eq!(index.api_version, "v1");
+
+    let legacy = &index.entries["legacy"][0];
+    assert_eq!(legacy.api_version, Some(ApiVersion::V1));
+    assert_eq!(legacy.deprecated, Some(true));
+    assert_eq!(legacy.condition.as_deref(), Some("legacy.enabled"));
+    assert_eq!(legacy.tags.as_deref(), Some("frontend,legacy"));
+    assert_eq!(legacy.urls, ["legacy-1.0.0.tgz"]);
+    assert!(legacy.created.is_some());
+    assert_eq!(legacy.maintainers[0].email.as_deref(), Some("noel@noelware.org"));
+
+    let hello = &index.entries["hello"][0];
+    assert_eq!(hello.kube_version.as_deref(), Some(">=1.20.0-0"));
+    assert_eq!(hello.app_version.as_deref(), Some("1.0"));
+    assert_eq!(hello.urls, ["hello-0.2.0-rc.1.tgz"]);
+    assert!(hello
+        .digest
+        .as_deref()
+        .is_some_and(|digest| digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit())));
+
+    assert_eq!(index.entries["mylib"][0].chart_type, Some(RepositoryType::Library));
+}
+
+#[test]
+fn preserves_unknown_fields() {
+    // `serverInfo` is written by ChartMuseum
+    let contents = r#"apiVersion: v1
+entries:
+  hello:
+  - apiVersion: v2
+    name: hello
+    version: 0.1.0
+generated: "2023-03-14T18:32:18Z"
+serverInfo:
+  contextPath: /v1/helm
+"#;
+
+    let index = ChartIndexYaml::from_yaml(contents).unwrap();
+    let server_info = index.extra.get("serverInfo").expect("serverInfo should be kept");
+    assert_eq!(server_info["contextPath"], Value::from("/v1/helm"));
+    assert!(index.to_yaml().unwrap().contains("serverInfo:"));
+
+    let mut entry = index.entries["hello"][0].clone();
+    entry.extra.insert("x-charted-owner".into(), Value::from("noel"));
+
+    let written = serde_yaml::to_string(&entry).unwrap();
+    assert!(written.contains("x-charted-owner: noel"));
+}
+
+#[test]
+fn dependencies_round_trip() {
+    let index = ChartIndexYaml::from_yaml(FIXTURES[0].1).unwrap();
+    let nginx = &index.entries["nginx"][0];
+    let common = &nginx.dependencies[0];
+
+    assert_eq!(common.name, "common");
+    assert_eq!(common.version.as_deref(), Some("2.x.x"));
+    assert_eq!(common.tags, ["bitnami-common"]);
+    assert!(common.import_values.is_empty());
+    assert_eq!(nginx.annotations["category"], "Infrastructure");
+}
+
+#[test]
+fn keeps_unknown_api_versions_and_types() {
+    let contents = r#"apiVersion: v1
+entries:
+  next:
+  - apiVersion: v3
+    name: next
+    type: plugin
+    version: 1.0.0
+generated: 2023-03-14T18:32:18Z
+"#;
+
+    let index = ChartIndexYaml::from_yaml(contents).unwrap();
+    let next = &index.entries["next"][0];
+
+    assert_eq!(next.api_version, Some(ApiVersion::Other("v3".into())));
+    assert_eq!(next.chart_type, Some(RepositoryType::Other("plugin".into())));
+
+    let written = index.to_yaml().unwrap();
+    assert!(written.contains("apiVersion: v3"));
+    assert!(written.contains("type: plugin"));
+
+    assert_eq!("v2".parse::<ApiVersion>().unwrap(), ApiVersion::V2);
+    assert_eq!(RepositoryType::Library.to_string(), "library");
+}
+
+#[test]
+fn keeps_a_missing_api_version() {
+    let contents = r#"apiVersion: v1
+entries:
+  old:
+  - name: old
+    version: 0.1.0
+generated: 2023-03-14T18:32:18Z
+"#;
+
+    let index = ChartIndexYaml::from_yaml(contents).unwrap();
+    assert_eq!(index.entries["old"][0].api_version, None);
+
+    let written = index.to_yaml().unwrap();
+    assert_eq!(written.matches("apiVersion").count(), 1, "{written}");
+}
@@ -0,0 +1,233 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for mirroring a chart repository from the fake server into a local directory.
+
+#![cfg(all(feature = "testing", feature = "indexes", feature = "chart-packaging"))]
+
+use charted::{
+    mirror::{ChangeKind, Mirror},
+    models::ChartIndexYaml,
+    testing::{fixtures, FakeServer},
+    Error,
+};
+
+fn server() -> FakeServer {
+    let server = FakeServer::start();
+    for (name, version) in [("hello", "0.1.0"), ("hello", "0.2.0"), ("postgresql", "12.1.0")] {
+        server.add_chart(
+            "noel",
+            fixtures::chart_spec(name, version),
+            fixtures::chart_package(name, version),
+        );
+    }
+
+    server
+}
+
+fn downloads(server: &FakeServer) -> Vec<String> {
+    server
+        .requests()
+        .into_iter()
+        .filter(|request| request.starts_with("GET /charts/"))
+        .collect()
+}
+
+fn mirrored_index(dir: &std::path::Path) -> ChartIndexYaml {
+    ChartIndexYaml::from_yaml(std::fs::read_to_string(dir.join("index.yaml")).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn sync_and_resume() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = Mirror::new(server.client().indexes());
+
+    let summary = mirror.sync("noel", dir.path()).await.unwrap();
+    assert!(summary.is_success());
+    assert_eq!(summary.up_to_date, 0);
+
+    let downloaded = summary
+        .downloaded
+        .iter()
+        .map(|chart| (chart.name.as_str(), chart.version.as_str(), chart.kind))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        downloaded,
+        [
+            ("hello", "0.1.0", ChangeKind::New),
+            ("hello", "0.2.0", ChangeKind::New),
+            ("postgresql", "12.1.0", ChangeKind::New)
+        ]
+    );
+
+    for chart in summary.downloaded.iter() {
+        assert_eq!(
+            std::fs::read(&chart.path).unwrap(),
+            fixtures::chart_package(&chart.name, &chart.version)
+        );
+    }
+
+    // the index points to the packages next to it
+    let index = mirrored_index(dir.path());
+    assert_eq!(index.entries["hello"].len(), 2);
+    assert_eq!(index.entries["postgresql"][0].urls, ["postgresql-12.1.0.tgz"]);
+
+    // only the index is requested when nothing changed
+    let before = downloads(&server).len();
+    let summary = mirror.sync("noel", dir.path()).await.unwrap();
+    assert!(summary.downloaded.is_empty());
+    assert_eq!(summary.up_to_date, 3);
+    assert_eq!(downloads(&server).len(), before);
+}
+
+#[tokio::test]
+async fn changed_and_interrupted_packages() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = Mirror::new(server.client().indexes());
+    mirror.sync("noel", dir.path()).await.unwrap();
+
+    // a package that was tampered with is downloaded again, and one that is missing
+    // (i.e, the previous sync was interrupted) is downloaded as new
+    std::fs::write(dir.path().join("hello-0.1.0.tgz"), b"tampered").unwrap();
+    std::fs::remove_file(dir.path().join("postgresql-12.1.0.tgz")).unwrap();
+
+    let summary = Mirror::new(server.client().indexes())
+        .concurrency(1)
+        .sync("noel", dir.path())
+        .await
+        .unwrap();
+
+    let downloaded = summary
+        .downloaded
+        .iter()
+        .map(|chart| (chart.name.as_str(), chart.kind))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        downloaded,
+        [("hello", ChangeKind::Changed), ("postgresql", ChangeKind::New)]
+    );
+    assert_eq!(summary.up_to_date, 1);
+    assert_eq!(
+        std::fs::read(dir.path().join("hello-0.1.0.tgz")).unwrap(),
+        fixtures::chart_package("hello", "0.1.0")
+    );
+}
+
+#[tokio::test]
+async fn failures_are_reported_and_left_out_of_the_index() {
+    let server = server();
+    let mut index = server.client().indexes().get("noel").await.unwrap().unwrap();
+    index.entries.get_mut("hello").unwrap()[0].digest = Some("0".repeat(64));
+    index.entries.get_mut("postgresql").unwrap()[0].urls = vec!["missing.tgz".into()];
+    server.set_index("noel", index);
+
+    let dir = tempfile::tempdir().unwrap();
+    let summary = Mirror::new(server.client().indexes())
+        .sync("noel", dir.path())
+        .await
+        .unwrap();
+
+    assert!(!summary.is_success());
+    assert_eq!(summary.downloaded.len(), 1);
+
+    let mut failed = summary.failed.iter().collect::<Vec<_>>();
+    failed.sort_by_key(|failure| failure.name.clone());
+    assert!(matches!(failed[0].error, Error::DigestMismatch { .. }));
+    assert_eq!(failed[1].name, "postgresql");
+
+    // nothing but the verified package was written
+    let mut files = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+
+    files.sort();
+    assert_eq!(files, ["hello-0.1.0.tgz", "index.yaml"]);
+
+    let index = mirrored_index(dir.path());
+    assert_eq!(index.entries.keys().collect::<Vec<_>>(), ["hello"]);
+    assert_eq!(index.entries["hello"][0].version, "0.1.0");
+}
+
+#[tokio::test]
+async fn repositories_that_do_not_exist() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let error = Mirror::new(server.client().indexes())
+        .sync("ice", dir.path())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::String(_)));
+}
+
+#[tokio::test]
+async fn plan_against_another_server() {
+    let source = server();
+    let target = FakeServer::start();
+
+    // the target doesn't have a repository yet, so everything is new
+    let mirror = Mirror::new(source.client().indexes());
+    let plan = mirror
+        .plan_against("noel", &target.client().indexes(), "noel")
+        .await
+        .unwrap();
+    assert_eq!(plan.download.len(), 3);
+    assert!(plan.download.iter().all(|planned| planned.kind == ChangeKind::New));
+
+    target.add_chart(
+        "noelware",
+        fixtures::chart_spec("hello", "0.1.0"),
+        fixtures::chart_package("hello", "0.1.0"),
+    );
+
+    target.add_chart(
+        "noelware",
+        fixtures::chart_spec("hello", "0.2.0"),
+        fixtures::chart_package("hello", "0.3.0"),
+    );
+
+    let plan = mirror
+        .plan_against("noel", &target.client().indexes(), "noelware")
+        .await
+        .unwrap();
+
+    let planned = plan
+        .download
+        .iter()
+        .map(|planned| (planned.spec.name.as_str(), planned.spec.version.as_str(), planned.kind))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        planned,
+        [
+            ("hello", "0.2.0", ChangeKind::Changed),
+            ("postgresql", "12.1.0", ChangeKind::New)
+        ]
+    );
+
+    assert_eq!(plan.up_to_date.len(), 1);
+    assert!(downloads(&source).is_empty() && downloads(&target).is_empty());
+}
@@ -0,0 +1,243 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests for signing and verifying provenance files with a [`Keyring`]. These need `gpg` to
+//! be on the `PATH`, and fail if it isn't.
+
+#![cfg(all(feature = "testing", feature = "gpg"))]
+
+use std::process::{Command, Stdio};
+
+use charted::{
+    chart::{Keyring, Provenance, ProvenanceError},
+    testing::fixtures,
+    Error,
+};
+use tempfile::TempDir;
+
+const USER_ID: &str = "Noel <cutie@floofy.dev>";
+const PASSPHRASE: &str = "hunter2 with spaces";
+
+/// The secret and public keyrings of a freshly generated key.
+struct Keys {
+    dir: TempDir,
+}
+
+impl Keys {
+    fn generate(user_id: &str) -> Keys {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("home");
+        std::fs::create_dir(&home).unwrap();
+
+        let gpg = |args: &[&str], output: Option<&str>| {
+            let result = Command::new("gpg")
+                .arg("--homedir")
+                .arg(&home)
+                .args(["--batch", "--pinentry-mode", "loopback", "--passphrase", PASSPHRASE])
+                .args(args)
+                .stdin(Stdio::null())
+                .output()
+                .expect("`gpg` must be installed to run the provenance tests");
+
+            assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+            if let Some(output) = output {
+                std::fs::write(dir.path().join(output), result.stdout).unwrap();
+            }
+        };
+
+        gpg(&["--quick-gen-key", user_id, "ed25519", "sign", "never"], None);
+        gpg(&["--export-secret-keys"], Some("secring.gpg"));
+        gpg(&["--export"], Some("pubring.gpg"));
+
+        let _ = Command::new("gpgconf")
+            .arg("--homedir")
+            .arg(&home)
+            .args(["--kill", "all"])
+            .status();
+
+        Keys { dir }
+    }
+
+    fn secret(&self) -> Keyring {
+        Keyring::new(self.dir.path().join("secring.gpg")).unwrap()
+    }
+
+    fn public(&self) -> Keyring {
+        Keyring::new(self.dir.path().join("pubring.gpg")).unwrap()
+    }
+}
+
+fn signed_package(keys: &Keys) -> (Vec<u8>, String) {
+    let package = fixtures::chart_package("hello", "0.1.0");
+    let provenance = Provenance::for_package("hello-0.1.0.tgz", &package).unwrap();
+    let signed = keys
+        .secret()
+        .sign(&provenance, "cutie@floofy.dev", Some(PASSPHRASE))
+        .unwrap();
+
+    (package, signed)
+}
+
+fn assert_invalid_signature(result: charted::Result<impl std::fmt::Debug>) {
+    match result {
+        Err(Error::Provenance(ProvenanceError::InvalidSignature(_))) => {}
+        other => panic!("expected an invalid signature, got {other:?}"),
+    }
+}
+
+#[test]
+fn sign_and_verify() {
+    let keys = Keys::generate(USER_ID);
+
+    let (package, signed) = signed_package(&keys);
+    assert!(signed.starts_with("-----BEGIN PGP SIGNED MESSAGE-----"));
+    assert!(signed.contains("Hash: SHA512"));
+
+    let verification = keys
+        .public()
+        .verify_chart("hello-0.1.0.tgz", &package, &signed)
+        .unwrap();
+    assert_eq!(verification.signer.user_id, USER_ID);
+    assert_eq!(verification.signer.fingerprint.len(), 40);
+    assert_eq!(verification.provenance.chart.name, "hello");
+    assert_eq!(verification.digest, charted::chart::chart_digest(&package));
+    assert_eq!(
+        verification.provenance.files["hello-0.1.0.tgz"],
+        format!("sha256:{}", verification.digest)
+    );
+
+    // the secret keyring can be used to verify too
+    keys.secret().verify(&signed).unwrap();
+}
+
+#[test]
+fn wrong_passphrase() {
+    let keys = Keys::generate(USER_ID);
+
+    let package = fixtures::chart_package("hello", "0.1.0");
+    let provenance = Provenance::for_package("hello-0.1.0.tgz", &package).unwrap();
+    for passphrase in [Some("nope"), None] {
+        assert!(matches!(
+            keys.secret().sign(&provenance, "cutie@floofy.dev", passphrase),
+            Err(Error::Provenance(ProvenanceError::Gpg(_)))
+        ));
+    }
+}
+
+#[test]
+fn tampered_package() {
+    let keys = Keys::generate(USER_ID);
+
+    let (_, signed) = signed_package(&keys);
+    let other = fixtures::chart_package("hello", "0.1.1");
+
+    assert!(matches!(
+        keys.public().verify_chart("hello-0.1.0.tgz", &other, &signed),
+        Err(Error::Provenance(ProvenanceError::DigestMismatch { .. }))
+    ));
+
+    assert!(matches!(
+        keys.public().verify_chart("hello-0.1.1.tgz", &other, &signed),
+        Err(Error::Provenance(ProvenanceError::FileNotListed(_)))
+    ));
+}
+
+#[test]
+fn tampered_provenance() {
+    let keys = Keys::generate(USER_ID);
+
+    let (package, signed) = signed_package(&keys);
+    let tampered = signed.replace("name: hello", "name: evil");
+    assert_ne!(signed, tampered);
+    assert_invalid_signature(keys.public().verify_chart("hello-0.1.0.tgz", &package, &tampered));
+
+    let unsigned = signed.split("-----BEGIN PGP SIGNATURE-----").next().unwrap();
+    assert!(matches!(
+        keys.public().verify(unsigned),
+        Err(Error::Provenance(ProvenanceError::MissingSignature))
+    ));
+}
+
+#[test]
+fn text_around_the_signed_block() {
+    let keys = Keys::generate(USER_ID);
+
+    let (package, signed) = signed_package(&keys);
+    let other = fixtures::chart_package("hello", "0.1.1");
+    let unsigned = Provenance::for_package("hello-0.1.1.tgz", &other)
+        .unwrap()
+        .message()
+        .unwrap();
+
+    // gpg only verifies the signed block, so the unsigned digests before or after it must be rejected
+    // instead of being parsed
+    for tampered in [
+        format!("{unsigned}\n{signed}"),
+        format!("{signed}\n{unsigned}"),
+        format!("{signed}\n{signed}"),
+    ] {
+        assert!(matches!(
+            keys.public().verify_chart("hello-0.1.1.tgz", &other, &tampered),
+            Err(Error::Provenance(ProvenanceError::Malformed(_)))
+        ));
+    }
+
+    // whitespace around the block is fine
+    keys.public()
+        .verify_chart("hello-0.1.0.tgz", &package, format!("\n{signed}\n\n"))
+        .unwrap();
+}
+
+#[test]
+fn unknown_signer() {
+    let keys = Keys::generate(USER_ID);
+    let other = Keys::generate("Someone <someone@example.com>");
+
+    let (package, signed) = signed_package(&keys);
+    assert_invalid_signature(other.public().verify_chart("hello-0.1.0.tgz", &package, &signed));
+}
+
+#[test]
+fn large_documents_do_not_deadlock() {
+    let keys = Keys::generate(USER_ID);
+
+    // bigger than a pipe's buffer, in both directions
+    let package = fixtures::chart_package("hello", "0.1.0");
+    let mut provenance = Provenance::for_package("hello-0.1.0.tgz", &package).unwrap();
+    provenance.chart.description = Some("a very long description.\n".repeat(40_000));
+
+    let signed = keys
+        .secret()
+        .sign(&provenance, "cutie@floofy.dev", Some(PASSPHRASE))
+        .unwrap();
+    assert!(signed.len() > 1_000_000);
+    keys.public().verify(&signed).unwrap();
+
+    let parsed = Provenance::parse(&signed).unwrap();
+    assert_eq!(parsed.chart.description, provenance.chart.description);
+
+    // gpg would silently truncate this line
+    provenance.chart.description = Some("a".repeat(20_000));
+    assert!(matches!(
+        keys.secret().sign(&provenance, "cutie@floofy.dev", Some(PASSPHRASE)),
+        Err(Error::Provenance(ProvenanceError::Malformed(_)))
+    ));
+}
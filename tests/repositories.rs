@@ -0,0 +1,99 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests the Repositories API container against the in-process fake server from the `testing` feature.
+
+#![cfg(all(feature = "testing", feature = "repositories"))]
+
+use charted::{
+    api::RepositoriesApi,
+    testing::{fixtures, FakeServer},
+    Error,
+};
+
+#[tokio::test]
+async fn get() {
+    let server = FakeServer::start();
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+
+    let repositories = server.client().repositories();
+    assert_eq!(repositories.get(3).await.unwrap().unwrap().name, "postgresql");
+    assert!(repositories.get(4).await.unwrap().is_none());
+    assert_eq!(server.requests(), ["GET /repositories/3", "GET /repositories/4"]);
+}
+
+#[tokio::test]
+async fn list_for_user() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_user(fixtures::user(2, "ice"));
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+    server.add_repository(fixtures::repository(4, 1, "hello-world"));
+    server.add_repository(fixtures::repository(5, 2, "redis"));
+
+    let repositories = server.client().repositories();
+    let names = repositories
+        .list_for_user("noel")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|repository| repository.name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["postgresql", "hello-world"]);
+    assert_eq!(repositories.list_for_user("2").await.unwrap()[0].name, "redis");
+}
+
+#[tokio::test]
+async fn list_for_unknown_user() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_organization(fixtures::organization(2, "noelware", fixtures::user(1, "noel")));
+    server.add_repository(fixtures::repository(3, 2, "charted"));
+
+    // organizations aren't users, even though they can own repositories
+    let repositories = server.client().repositories();
+    for name in ["nobody", "3", "noelware"] {
+        let result = repositories.list_for_user(name).await;
+        assert!(
+            matches!(&result, Err(Error::APIServer { errors }) if errors[0].code == "UNKNOWN_USER"),
+            "{name}: {result:?}"
+        );
+    }
+
+    assert_eq!(
+        server.requests(),
+        [
+            "GET /users/nobody/repositories",
+            "GET /users/3/repositories",
+            "GET /users/noelware/repositories",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn trait_objects() {
+    let server = FakeServer::start();
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+
+    let api: Box<dyn RepositoriesApi> = Box::new(server.client().repositories());
+    assert_eq!(api.get(3).await.unwrap().unwrap().id, 3);
+}
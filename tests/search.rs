@@ -0,0 +1,231 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Tests the Search API container and the query strings that it sends.
+
+#![cfg(all(feature = "testing", feature = "search"))]
+
+use charted::{
+    models::{Repository, RepositoryType, SearchQuery, SearchResult, SearchSortBy, SortOrder},
+    testing::{fixtures, Cassette, FakeServer},
+    APIClient, Error,
+};
+
+#[test]
+fn query_strings() {
+    assert_eq!(SearchQuery::new("postgres").to_query_string().unwrap(), "q=postgres");
+    assert_eq!(
+        SearchQuery::new("hello world").to_query_string().unwrap(),
+        "q=hello+world",
+        "queries should be URL-encoded"
+    );
+
+    let mut query = SearchQuery::new("db");
+    query
+        .owner("noel")
+        .repository_type(RepositoryType::Library)
+        .keyword("sql")
+        .keywords(["postgres", "mysql"])
+        .deprecated(false)
+        .sort_by(SearchSortBy::CreatedAt)
+        .sort_order(SortOrder::Descending)
+        .limit(5)
+        .offset(10);
+
+    assert_eq!(
+        query.to_query_string().unwrap(),
+        "q=db&owner=noel&type=library&keywords=sql%2Cpostgres%2Cmysql&deprecated=false&sort=created_at&order=desc&limit=5&offset=10"
+    );
+}
+
+#[tokio::test]
+async fn request_paths() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_organization(fixtures::organization(2, "noelware", fixtures::user(1, "noel")));
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+    server.add_repository(fixtures::repository(4, 1, "hello-world"));
+
+    let search = server.client().search();
+    let repositories = search
+        .repositories(SearchQuery::new("postgres").limit(1))
+        .await
+        .unwrap();
+
+    assert_eq!(repositories.total_hits, 1);
+    assert_eq!(repositories.hits[0].data.name, "postgresql");
+
+    assert_eq!(search.users(&SearchQuery::new("noel")).await.unwrap().hits.len(), 1);
+    assert_eq!(
+        search.organizations(&SearchQuery::new("ware")).await.unwrap().hits[0]
+            .data
+            .name,
+        "noelware"
+    );
+
+    assert_eq!(
+        server.requests(),
+        [
+            "GET /search/repositories?q=postgres&limit=1",
+            "GET /search/users?q=noel",
+            "GET /search/organizations?q=ware",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn fake_server_filters() {
+    let server = FakeServer::start();
+    server.add_user(fixtures::user(1, "noel"));
+    server.add_organization(fixtures::organization(2, "noelware", fixtures::user(1, "noel")));
+    server.add_repository(fixtures::repository(3, 1, "postgresql"));
+    server.add_repository(fixtures::repository(4, 2, "postgres-operator"));
+    server.add_repository(Repository {
+        deprecated: true,
+        repository_type: RepositoryType::Library,
+        ..fixtures::repository(5, 1, "postgres-common")
+    });
+
+    let mut index = fixtures::chart_index();
+    index.entries.get_mut("postgresql").unwrap()[0].keywords = vec!["database".into(), "sql".into()];
+    server.set_index("noel", index);
+
+    let search = server.client().search();
+    let names = |result: SearchResult<Repository>| result.hits.into_iter().map(|hit| hit.data.name).collect::<Vec<_>>();
+
+    assert_eq!(
+        names(
+            search
+                .repositories(SearchQuery::new("postgres").owner("noelware"))
+                .await
+                .unwrap()
+        ),
+        ["postgres-operator"]
+    );
+    assert_eq!(
+        names(
+            search
+                .repositories(SearchQuery::new("postgres").repository_type(RepositoryType::Library))
+                .await
+                .unwrap()
+        ),
+        ["postgres-common"]
+    );
+    assert_eq!(
+        names(
+            search
+                .repositories(SearchQuery::new("postgres").deprecated(false))
+                .await
+                .unwrap()
+        ),
+        ["postgresql", "postgres-operator"]
+    );
+    assert_eq!(
+        names(
+            search
+                .repositories(SearchQuery::new("postgres").keywords(["sql", "database"]))
+                .await
+                .unwrap()
+        ),
+        ["postgresql"]
+    );
+    assert!(search
+        .repositories(SearchQuery::new("postgres").keywords(["sql", "mysql"]))
+        .await
+        .unwrap()
+        .hits
+        .is_empty());
+
+    assert_eq!(
+        names(
+            search
+                .repositories(
+                    SearchQuery::new("postgres")
+                        .sort_by(SearchSortBy::Name)
+                        .sort_order(SortOrder::Descending)
+                        .offset(1)
+                )
+                .await
+                .unwrap()
+        ),
+        ["postgres-operator", "postgres-common"]
+    );
+
+    // repository filters are ignored for users and organizations
+    assert_eq!(
+        search
+            .users(SearchQuery::new("noel").deprecated(true))
+            .await
+            .unwrap()
+            .total_hits,
+        1
+    );
+}
+
+#[tokio::test]
+async fn fake_server_rejects_unknown_parameters() {
+    let server = FakeServer::start();
+    let search = |query: &str| {
+        let url = format!("{}/search/repositories?{query}", server.url());
+        async move { reqwest::get(url).await.unwrap().status() }
+    };
+
+    assert_eq!(search("q=postgres&deprecated=false").await, 200);
+    assert_eq!(search("q=postgres&tags=sql").await, 400);
+    assert_eq!(search("q=postgres&type=chart").await, 400);
+    assert_eq!(search("q=postgres&sort=stars").await, 400);
+    assert_eq!(search("q=postgres&limit=-1").await, 400);
+}
+
+#[tokio::test]
+async fn unsuccessful_responses() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("search.yaml");
+    std::fs::write(
+        &path,
+        r#"interactions:
+  - request: { method: GET, path: /search/users?q=noel }
+    response:
+      status: 400
+      body: '{"success":false,"errors":[{"code":"INVALID_QUERY","message":"query is invalid"}]}'
+  - request: { method: GET, path: /search/users?q=ice }
+    response: { status: 500, body: '{"success":false}' }
+"#,
+    )
+    .unwrap();
+
+    let search = APIClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .cassette(Cassette::replay(&path).unwrap())
+        .build()
+        .search();
+
+    match search.users(&SearchQuery::new("noel")).await {
+        Err(Error::APIServer { errors }) => assert_eq!(errors[0].code, "INVALID_QUERY"),
+        other => panic!("expected an API server error, got {other:?}"),
+    }
+
+    // a response without any errors is an error too, instead of a panic
+    assert!(matches!(
+        search.users(&SearchQuery::new("ice")).await,
+        Err(Error::String(_))
+    ));
+}
@@ -0,0 +1,293 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Checks that the hand-written models in `src/models` match their schemas in the OpenAPI document, which
+//! catches properties that were added or removed in charted-server but not in this crate.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use syn::{
+    braced,
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    Attribute, Fields, Ident, Item, ItemMacro, LitStr, Token, Type,
+};
+
+use crate::{
+    codegen::GENERATED_SCHEMAS,
+    spec::{self, Spec},
+    Result,
+};
+
+/// Schemas that aren't modelled by a type with the same name. The error envelope is modelled by the
+/// generic `APIResponse` type.
+const UNMODELLED_SCHEMAS: &[&str] = &["ApiErrorResponse"];
+
+/// The operations that the hand-written containers send requests to. Their methods aren't generated, since they
+/// do more than send a request (i.e, caching indexes, verifying digests, or returning `None` if something doesn't
+/// exist), so they are only checked to still exist in the OpenAPI document.
+const CONTAINER_OPERATIONS: &[(&str, &str)] = &[
+    ("get", "/indexes/{idOrName}"),
+    ("get", "/repositories/{id}"),
+    ("get", "/users/{idOrName}/repositories"),
+    ("get", "/search/{index}"),
+];
+
+/// The serde attributes of a struct, field, enum or variant that the checks need.
+#[derive(Default)]
+struct Serde {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    default: bool,
+    skip: bool,
+}
+
+/// Returns every problem that was found with the hand-written models.
+pub fn models(root: &Path, spec: &Spec) -> Result<Vec<String>> {
+    let mut types = BTreeMap::new();
+    let mut string_enums = BTreeMap::new();
+    for entry in fs::read_dir(root.join("src/models"))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "rs") || path.ends_with("generated.rs") {
+            continue;
+        }
+
+        let file = syn::parse_file(&fs::read_to_string(&path)?)
+            .map_err(|e| format!("unable to parse {}: {e}", path.display()))?;
+
+        let relative = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+        for item in file.items {
+            let name = match &item {
+                Item::Struct(item) => item.ident.to_string(),
+                Item::Enum(item) => item.ident.to_string(),
+                Item::Macro(item) if item.mac.path.is_ident("string_enum") => {
+                    let (name, values) = string_enum(item)
+                        .map_err(|e| format!("unable to parse `string_enum!` in {}: {e}", path.display()))?;
+
+                    string_enums.insert(name, values);
+                    continue;
+                }
+
+                _ => continue,
+            };
+
+            types.insert(name, (relative.clone(), item));
+        }
+    }
+
+    let mut problems = vec![];
+    for (name, schema) in spec.schemas() {
+        let schema = spec.resolve(schema);
+        if GENERATED_SCHEMAS.contains(&name) || UNMODELLED_SCHEMAS.contains(&name) {
+            continue;
+        }
+
+        // the SDK doesn't cover every part of the API, so this isn't a problem by itself
+        let Some((file, item)) = types.get(name) else {
+            eprintln!("warning: schema `{name}` isn't modelled by a type in src/models");
+            continue;
+        };
+
+        match (item, spec::enum_values(schema)) {
+            (Item::Enum(item), Some(values)) => {
+                // enums with an `Other` variant implement serde with `string_enum!` instead of deriving it
+                let variants = match string_enums.get(name) {
+                    Some(variants) => variants.clone(),
+                    None => {
+                        let container = serde(&item.attrs)?;
+                        let mut variants = vec![];
+                        for variant in &item.variants {
+                            let attrs = serde(&variant.attrs)?;
+                            if !attrs.skip && matches!(variant.fields, Fields::Unit) {
+                                variants.push(serialized_name(&variant.ident.to_string(), &attrs, &container));
+                            }
+                        }
+
+                        variants
+                    }
+                };
+
+                for value in &values {
+                    if !variants.iter().any(|variant| variant == value) {
+                        problems.push(format!("{file}: `{name}` doesn't have a variant for `{value}`"));
+                    }
+                }
+
+                for variant in &variants {
+                    if !values.contains(&variant.as_str()) {
+                        problems.push(format!(
+                            "{file}: `{name}` has the `{variant}` variant, which isn't in the `{name}` schema"
+                        ));
+                    }
+                }
+            }
+
+            (Item::Struct(item), None) if spec.is_object(schema) => {
+                let container = serde(&item.attrs)?;
+                let mut fields = BTreeMap::new();
+                for field in &item.fields {
+                    let attrs = serde(&field.attrs)?;
+                    let Some(ident) = &field.ident else { continue };
+                    if attrs.skip {
+                        continue;
+                    }
+
+                    let optional = is_option(&field.ty) || attrs.default || container.default;
+                    fields.insert(serialized_name(&ident.to_string(), &attrs, &container), optional);
+                }
+
+                let properties = spec.properties(schema);
+                for property in &properties {
+                    match fields.get(property.name) {
+                        None => problems.push(format!("{file}: `{name}` doesn't have the `{}` property", property.name)),
+                        Some(false) if !property.required => problems.push(format!(
+                            "{file}: the `{}` property of `{name}` isn't required, but it isn't an `Option` and has no `#[serde(default)]`",
+                            property.name
+                        )),
+
+                        Some(_) => {}
+                    }
+                }
+
+                for field in fields.keys() {
+                    if !properties.iter().any(|property| property.name == field) {
+                        problems.push(format!(
+                            "{file}: `{name}` has the `{field}` field, which isn't in the `{name}` schema"
+                        ));
+                    }
+                }
+            }
+
+            // i.e. `oneOf` schemas or aliases of primitive types, which can't be compared with a model
+            (_, None) if !spec.is_object(schema) => {
+                eprintln!(
+                    "warning: schema `{name}` isn't an object or a string enum, so {file} isn't checked against it"
+                );
+            }
+
+            _ => problems.push(format!(
+                "{file}: `{name}` should be a struct if its schema is an object, or an enum if it's a string enum"
+            )),
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Returns every operation that a hand-written container uses, but that isn't in the OpenAPI document.
+pub fn operations(spec: &Spec) -> Vec<String> {
+    CONTAINER_OPERATIONS
+        .iter()
+        .filter(|(method, path)| spec.operation(method, path).is_none())
+        .map(|(method, path)| {
+            format!(
+                "src/containers: `{} {path}` isn't an operation in the OpenAPI document",
+                method.to_uppercase()
+            )
+        })
+        .collect()
+}
+
+// Returns the name of the enum and the strings of its variants from a `string_enum!` invocation, which
+// looks like `string_enum!(Name { Variant => "value", ... })`
+fn string_enum(item: &ItemMacro) -> syn::Result<(String, Vec<String>)> {
+    let parser = |input: ParseStream| {
+        let name = input.parse::<Ident>()?;
+        let content;
+        braced!(content in input);
+
+        let variants = Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(&content, |input| {
+            let variant = input.parse::<Ident>()?;
+            input.parse::<Token![=>]>()?;
+            Ok((variant, input.parse::<LitStr>()?))
+        })?;
+
+        Ok((
+            name.to_string(),
+            variants.into_iter().map(|(_, value)| value.value()).collect(),
+        ))
+    };
+
+    parser.parse2(item.mac.tokens.clone())
+}
+
+fn serde(attrs: &[Attribute]) -> Result<Serde> {
+    let mut serde = Serde::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            let value = if meta.input.peek(syn::Token![=]) {
+                Some(meta.value()?.parse::<LitStr>()?.value())
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(syn::Token![=]) {
+                        nested.value()?.parse::<LitStr>()?;
+                    }
+
+                    Ok(())
+                })?;
+
+                None
+            } else {
+                None
+            };
+
+            let name = meta.path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+            match name.as_str() {
+                "rename" => serde.rename = value,
+                "rename_all" => serde.rename_all = value,
+                "default" => serde.default = true,
+                "skip" | "skip_deserializing" | "flatten" => serde.skip = true,
+                _ => {}
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(serde)
+}
+
+// Returns the name that serde uses for a field or variant
+fn serialized_name(ident: &str, attrs: &Serde, container: &Serde) -> String {
+    let ident = ident.trim_start_matches("r#");
+    if let Some(rename) = &attrs.rename {
+        return rename.clone();
+    }
+
+    match container.rename_all.as_deref() {
+        Some("lowercase") => ident.to_lowercase(),
+        Some("UPPERCASE") => ident.to_uppercase(),
+        Some("snake_case") => spec::snake_case(ident),
+        _ => ident.to_owned(),
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+
+        _ => false,
+    }
+}
@@ -0,0 +1,379 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Generates Rust code from the OpenAPI document. The output goes through `rustfmt` with the repository's
+//! configuration, so the generated files are formatted like the rest of the crate.
+
+use std::{
+    collections::BTreeSet,
+    fmt::Write,
+    fs,
+    io::Write as _,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use serde_json::Value;
+
+use crate::{
+    check,
+    spec::{self, Spec, SPEC_PATH},
+    Result,
+};
+
+/// Schemas that are generated into `src/models/generated.rs`. Every other schema is modelled by hand
+/// (i.e, because it needs custom serde logic or builders), and is only checked against the OpenAPI document.
+pub const GENERATED_SCHEMAS: &[&str] = &["MainResponse", "InfoResponse", "FeaturesResponse"];
+
+/// The `GET` operations that are generated as methods of `APIClient` into `src/endpoints.rs`, as the name
+/// of the method and the path of the operation.
+pub const GENERATED_OPERATIONS: &[(&str, &str)] = &[("main", "/"), ("info", "/info"), ("features", "/features")];
+
+const MODELS_PATH: &str = "src/models/generated.rs";
+const ENDPOINTS_PATH: &str = "src/endpoints.rs";
+
+/// Generates every file, or only checks that they are up to date. Returns `false` if the check failed.
+pub fn run(root: &Path, check: bool) -> Result<bool> {
+    let spec = Spec::load(root)?;
+    if spec.is_placeholder() && !check {
+        eprintln!(
+            "warning: {SPEC_PATH} is a hand-written placeholder, run `./scripts/generate-openapi.sh` to replace it"
+        );
+    }
+
+    let header = header(root)?;
+    let files = [
+        (MODELS_PATH, models(&spec, &header)?),
+        (ENDPOINTS_PATH, endpoints(&spec, &header)?),
+    ];
+
+    let mut ok = true;
+    for (path, contents) in files {
+        let contents = rustfmt(root, &contents)?;
+        let existing = fs::read_to_string(root.join(path)).unwrap_or_default();
+        if existing == contents {
+            continue;
+        }
+
+        if check {
+            eprintln!("error: {path} is out of date, run `cargo xtask codegen` to regenerate it");
+            ok = false;
+        } else {
+            fs::write(root.join(path), contents)?;
+            println!("generated {path}");
+        }
+    }
+
+    if check {
+        let mut problems = check::models(root, &spec)?;
+        problems.extend(check::operations(&spec));
+        if spec.is_placeholder() {
+            problems.push(format!(
+                "{SPEC_PATH} is a hand-written placeholder that was written from the models, so it can't catch drift from charted-server"
+            ));
+        }
+
+        for problem in &problems {
+            eprintln!("error: {problem}");
+        }
+
+        ok &= problems.is_empty();
+        if ok {
+            println!("everything is up to date with {SPEC_PATH}");
+        }
+    }
+
+    Ok(ok)
+}
+
+// Returns the license header of `src/lib.rs` and a notice that the file is generated
+fn header(root: &Path) -> Result<String> {
+    let lib = fs::read_to_string(root.join("src/lib.rs"))?;
+    let mut header = String::new();
+    for line in lib
+        .lines()
+        .take_while(|line| line.starts_with("//") && !line.starts_with("//!"))
+    {
+        writeln!(header, "{line}")?;
+    }
+
+    writeln!(header)?;
+    writeln!(
+        header,
+        "// This file is generated by `cargo xtask codegen` from `{SPEC_PATH}`, do not edit it by hand."
+    )?;
+
+    Ok(header)
+}
+
+fn models(spec: &Spec, header: &str) -> Result<String> {
+    let mut imports = Imports::default();
+    let mut body = String::new();
+    for name in GENERATED_SCHEMAS {
+        let schema = spec
+            .schema(name)
+            .ok_or_else(|| format!("schema `{name}` doesn't exist in {SPEC_PATH}"))?;
+
+        writeln!(body)?;
+        doc(&mut body, "", spec::description(schema))?;
+        writeln!(
+            body,
+            "#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]"
+        )?;
+        writeln!(body, "pub struct {name} {{")?;
+
+        for (i, property) in spec.properties(schema).into_iter().enumerate() {
+            let mut ty = rust_type(property.schema, &mut imports)
+                .map_err(|e| format!("property `{}` of schema `{name}`: {e}", property.name))?;
+
+            if i != 0 {
+                writeln!(body)?;
+            }
+
+            doc(&mut body, "    ", spec::description(property.schema))?;
+            let field = field_name(property.name);
+            if field != property.name {
+                writeln!(body, "    #[serde(rename = \"{}\")]", property.name)?;
+            }
+
+            if !property.required || spec::is_nullable(property.schema) {
+                ty = format!("Option<{ty}>");
+                writeln!(body, "    #[serde(skip_serializing_if = \"Option::is_none\")]")?;
+            }
+
+            writeln!(body, "    pub {field}: {ty},")?;
+        }
+
+        writeln!(body, "}}")?;
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{header}")?;
+    writeln!(
+        out,
+        "//! The models that are generated from the schemas in charted-server's OpenAPI document."
+    )?;
+
+    writeln!(out)?;
+    if imports.hash_map {
+        writeln!(out, "use std::collections::HashMap;")?;
+        writeln!(out)?;
+    }
+
+    if imports.chrono {
+        writeln!(out, "use chrono::{{DateTime, Utc}};")?;
+        writeln!(out)?;
+    }
+
+    let models = imports
+        .models
+        .iter()
+        .filter(|name| !GENERATED_SCHEMAS.contains(&name.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !models.is_empty() {
+        writeln!(out, "use super::{{{}}};", models.join(", "))?;
+    }
+
+    out.push_str(&body);
+    Ok(out)
+}
+
+fn endpoints(spec: &Spec, header: &str) -> Result<String> {
+    let mut models = BTreeSet::new();
+    let mut body = String::new();
+    for &(id, path) in GENERATED_OPERATIONS {
+        let operation = spec
+            .operation("get", path)
+            .ok_or_else(|| format!("operation `GET {path}` doesn't exist in {SPEC_PATH}"))?;
+
+        let model = spec
+            .response_data(operation)
+            .and_then(spec::reference)
+            .ok_or_else(|| format!("operation `GET {path}` doesn't respond with a model in the `data` property"))?;
+
+        models.insert(model.to_owned());
+
+        // path parameters become arguments of the method
+        let mut args = String::new();
+        let mut endpoint = path.to_owned();
+        for parameter in operation["parameters"].as_array().into_iter().flatten() {
+            let parameter = spec.resolve(parameter);
+            if parameter["in"] != "path" {
+                // optional query and header parameters are left out of the generated method
+                if parameter["required"].as_bool().unwrap_or(false) {
+                    return Err(format!(
+                        "operation `GET {path}` has a required non-path parameter, which can't be generated"
+                    )
+                    .into());
+                }
+
+                continue;
+            }
+
+            let name = parameter["name"].as_str().unwrap_or_default();
+            let arg = spec::snake_case(name);
+            let ty = match parameter["schema"]["type"].as_str() {
+                Some("integer") => "u64",
+                _ => "&str",
+            };
+
+            write!(args, ", {arg}: {ty}")?;
+            endpoint = endpoint.replace(&format!("{{{name}}}"), &format!("{{{arg}}}"));
+        }
+
+        let (endpoint, endpoint_ty) = if args.is_empty() {
+            (format!("\"{endpoint}\""), "&str")
+        } else {
+            (format!("format!(\"{endpoint}\")"), "String")
+        };
+
+        writeln!(body)?;
+        doc(&mut body, "    ", spec::description(operation))?;
+        writeln!(body, "    pub async fn {id}(&self{args}) -> Result<{model}> {{")?;
+
+        writeln!(
+            body,
+            "        self.request_json::<APIResponse<{model}>, Body, {endpoint_ty}>({endpoint}, Method::GET, None, None).await?.into_result()"
+        )?;
+
+        writeln!(body, "    }}")?;
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{header}")?;
+    writeln!(
+        out,
+        "//! The methods of [`APIClient`] that are generated from the operations in charted-server's OpenAPI document."
+    )?;
+
+    writeln!(out)?;
+    writeln!(out, "use reqwest::{{Body, Method}};")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "use crate::{{models::{{APIResponse, {}}}, APIClient, Result}};",
+        models.into_iter().collect::<Vec<_>>().join(", ")
+    )?;
+
+    writeln!(out)?;
+    writeln!(out, "impl APIClient {{")?;
+    out.push_str(body.trim_start_matches('\n'));
+    writeln!(out, "}}")?;
+
+    Ok(out)
+}
+
+#[derive(Default)]
+struct Imports {
+    hash_map: bool,
+    chrono: bool,
+    models: BTreeSet<String>,
+}
+
+fn rust_type(schema: &Value, imports: &mut Imports) -> Result<String> {
+    if let Some(name) = spec::reference(schema) {
+        imports.models.insert(name.to_owned());
+        return Ok(name.to_owned());
+    }
+
+    let unsigned = schema["minimum"].as_f64().map(|min| min >= 0.0).unwrap_or(false);
+    let ty = match (spec::ty(schema), schema["format"].as_str()) {
+        (Some("string"), Some("date-time")) => {
+            imports.chrono = true;
+            "DateTime<Utc>".to_owned()
+        }
+
+        (Some("string"), _) => "String".to_owned(),
+        (Some("boolean"), _) => "bool".to_owned(),
+        (Some("integer"), Some("int32")) if unsigned => "u32".to_owned(),
+        (Some("integer"), Some("int32")) => "i32".to_owned(),
+        (Some("integer"), _) if unsigned => "u64".to_owned(),
+        (Some("integer"), _) => "i64".to_owned(),
+        (Some("number"), _) => "f64".to_owned(),
+        (Some("array"), _) => format!("Vec<{}>", rust_type(&schema["items"], imports)?),
+        (Some("object"), _) if schema["additionalProperties"].is_object() => {
+            imports.hash_map = true;
+            format!(
+                "HashMap<String, {}>",
+                rust_type(&schema["additionalProperties"], imports)?
+            )
+        }
+
+        _ => return Err(format!("unsupported schema: {schema}").into()),
+    };
+
+    Ok(ty)
+}
+
+fn field_name(property: &str) -> String {
+    let name = spec::snake_case(property);
+    match name.as_str() {
+        "type" | "ref" | "match" | "move" | "use" | "impl" | "struct" | "enum" => format!("r#{name}"),
+        _ => name,
+    }
+}
+
+// Writes a description as a doc comment that is wrapped at 120 characters
+fn doc(out: &mut String, indent: &str, description: Option<&str>) -> Result<()> {
+    let Some(description) = description else {
+        return Ok(());
+    };
+
+    let width = 120 - indent.len() - "/// ".len();
+    let mut line = String::new();
+    for word in description.split_whitespace() {
+        if !line.is_empty() && line.len() + word.len() + 1 > width {
+            writeln!(out, "{indent}/// {line}")?;
+            line.clear();
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+
+        line.push_str(word);
+    }
+
+    writeln!(out, "{indent}/// {line}")?;
+    Ok(())
+}
+
+fn rustfmt(root: &Path, contents: &str) -> Result<String> {
+    let mut child = Command::new(std::env::var("RUSTFMT").unwrap_or_else(|_| "rustfmt".into()))
+        .arg("--config-path")
+        .arg(root.join("rustfmt.toml"))
+        .arg("--emit")
+        .arg("stdout")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("unable to run rustfmt: {e}"))?;
+
+    child.stdin.take().unwrap().write_all(contents.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err("rustfmt failed to format the generated code".into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
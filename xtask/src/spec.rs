@@ -0,0 +1,375 @@
+// 🐻‍❄️📦 charted_sdk: Rust SDK library for Noelware's Charts Platform
+// Copyright (c) 2022-2023 Noelware, LLC. <team@noelware.org>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Helpers to read the parts of an OpenAPI 3.0 document that the code generator and checks use. The
+//! helpers follow `$ref`s and merge `allOf` schemas, and also understand the `type: [.., "null"]` form
+//! of OpenAPI 3.1, so they don't depend on how the document was generated.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use serde_json::{Map, Value};
+
+use crate::Result;
+
+/// Where the OpenAPI document is, relative to the repository.
+pub const SPEC_PATH: &str = "openapi/charted.json";
+
+/// Represents the OpenAPI document of charted-server.
+pub struct Spec {
+    document: Value,
+}
+
+/// Represents a property of an object schema.
+pub struct Property<'a> {
+    pub name: &'a str,
+    pub schema: &'a Value,
+    pub required: bool,
+}
+
+impl Spec {
+    pub fn load(root: &Path) -> Result<Spec> {
+        let contents = fs::read_to_string(root.join(SPEC_PATH))?;
+        let document = serde_json::from_str(&contents).map_err(|e| format!("{SPEC_PATH} is invalid: {e}"))?;
+
+        Ok(Spec { document })
+    }
+
+    /// Returns `true` if the document is the hand-written placeholder, which is marked with the
+    /// `x-charted-placeholder` extension, instead of one that was generated by charted-server.
+    pub fn is_placeholder(&self) -> bool {
+        self.document["info"]["x-charted-placeholder"]
+            .as_bool()
+            .unwrap_or(false)
+    }
+
+    /// Returns every schema in `components.schemas`, sorted by their name.
+    pub fn schemas(&self) -> Vec<(&str, &Value)> {
+        self.document["components"]["schemas"]
+            .as_object()
+            .map(|schemas| schemas.iter().map(|(name, schema)| (name.as_str(), schema)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn schema(&self, name: &str) -> Option<&Value> {
+        self.document["components"]["schemas"].get(name)
+    }
+
+    /// Returns the operation of the given method and path. Operations are looked up by their path
+    /// since not every generator gives them an `operationId`, and the names of path parameters are
+    /// ignored, so `/users/{id}` finds `/users/{idOrName}`.
+    pub fn operation(&self, method: &str, path: &str) -> Option<&Value> {
+        let paths = self.document["paths"].as_object()?;
+        let (_, item) = paths
+            .iter()
+            .find(|(candidate, _)| path_template(candidate) == path_template(path))?;
+
+        self.resolve(item).get(method)
+    }
+
+    /// Follows a `$ref` to the component it points to, or returns the schema itself if it isn't one.
+    pub fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        // a limit on how many references are followed, in case they form a cycle
+        for _ in 0..16 {
+            let Some(pointer) = value["$ref"].as_str().and_then(|reference| reference.strip_prefix('#')) else {
+                break;
+            };
+
+            match self.document.pointer(pointer) {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+
+        value
+    }
+
+    /// Returns the properties of an object schema, sorted by their name. The properties of every schema
+    /// in an `allOf` are merged.
+    pub fn properties<'a>(&'a self, schema: &'a Value) -> Vec<Property<'a>> {
+        let mut properties = BTreeMap::new();
+        let mut required = BTreeSet::new();
+        self.collect_properties(schema, &mut properties, &mut required);
+
+        properties
+            .into_iter()
+            .map(|(name, schema)| Property {
+                name,
+                schema,
+                required: required.contains(name),
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the schema describes an object, i.e. it has properties or is an `allOf`.
+    pub fn is_object(&self, schema: &Value) -> bool {
+        let schema = self.resolve(schema);
+        ty(schema) == Some("object") || schema["properties"].is_object() || schema["allOf"].is_array()
+    }
+
+    /// Returns the schema of the `data` property that a successful response of an operation is
+    /// wrapped in, which is how charted-server responds to every REST handler.
+    pub fn response_data<'a>(&'a self, operation: &'a Value) -> Option<&'a Value> {
+        let responses = operation["responses"].as_object()?;
+        let (_, response) = responses
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .min_by_key(|(status, _)| status.as_str())?;
+
+        let content = self.resolve(response)["content"].as_object()?;
+        let (_, media) = content
+            .iter()
+            .find(|(content_type, _)| content_type.starts_with("application/json"))?;
+
+        self.properties(&media["schema"])
+            .into_iter()
+            .find(|property| property.name == "data")
+            .map(|property| property.schema)
+    }
+
+    fn collect_properties<'a>(
+        &'a self,
+        schema: &'a Value,
+        properties: &mut BTreeMap<&'a str, &'a Value>,
+        required: &mut BTreeSet<&'a str>,
+    ) {
+        let schema = self.resolve(schema);
+        for inner in schema["allOf"].as_array().into_iter().flatten() {
+            self.collect_properties(inner, properties, required);
+        }
+
+        required.extend(
+            schema["required"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str),
+        );
+        properties.extend(
+            schema["properties"]
+                .as_object()
+                .into_iter()
+                .flat_map(Map::iter)
+                .map(|(name, schema)| (name.as_str(), schema)),
+        );
+    }
+}
+
+// Returns a path without its trailing slash and the names of its parameters
+fn path_template(path: &str) -> String {
+    let mut template = String::new();
+    let mut in_parameter = false;
+    for ch in path.trim_end_matches('/').chars() {
+        match ch {
+            '{' => {
+                in_parameter = true;
+                template.push_str("{}");
+            }
+
+            '}' => in_parameter = false,
+            _ if in_parameter => {}
+            _ => template.push(ch),
+        }
+    }
+
+    template
+}
+
+/// Returns the name of the schema that a `$ref` points to. An `allOf` with a single `$ref`, and an
+/// `anyOf` or `oneOf` of a `$ref` and `null` (how nullable references are written), point to it too.
+pub fn reference(schema: &Value) -> Option<&str> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference.strip_prefix("#/components/schemas/");
+    }
+
+    if let Some([inner]) = schema["allOf"].as_array().map(Vec::as_slice) {
+        return reference(inner);
+    }
+
+    let variants = schema["anyOf"].as_array().or_else(|| schema["oneOf"].as_array())?;
+    match variants
+        .iter()
+        .filter(|variant| !is_null(variant))
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [inner] => reference(inner),
+        _ => None,
+    }
+}
+
+/// Returns the type of a schema, ignoring `null` if the type is a list like `["string", "null"]`.
+pub fn ty(schema: &Value) -> Option<&str> {
+    match &schema["type"] {
+        Value::String(ty) => Some(ty),
+        Value::Array(types) => types.iter().filter_map(Value::as_str).find(|ty| *ty != "null"),
+        _ => None,
+    }
+}
+
+/// Returns the values of a string enum schema.
+pub fn enum_values(schema: &Value) -> Option<Vec<&str>> {
+    schema["enum"]
+        .as_array()
+        .map(|values| values.iter().filter_map(Value::as_str).collect())
+}
+
+pub fn description(schema: &Value) -> Option<&str> {
+    schema["description"].as_str()
+}
+
+pub fn is_nullable(schema: &Value) -> bool {
+    let variants = schema["anyOf"].as_array().or_else(|| schema["oneOf"].as_array());
+    schema["nullable"].as_bool().unwrap_or(false)
+        || schema["type"]
+            .as_array()
+            .is_some_and(|types| types.iter().any(|ty| ty == "null"))
+        || variants.is_some_and(|variants| variants.iter().any(is_null))
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema["type"] == "null"
+}
+
+/// Converts a `camelCase` or `PascalCase` identifier into `snake_case`.
+pub fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // A document that is shaped like the ones that generators emit, with wrapper schemas, `allOf`,
+    // shared responses and 3.1-style nullable types
+    fn spec() -> Spec {
+        Spec {
+            document: json!({
+                "paths": {
+                    "/users/{idOrName}": {
+                        "get": { "responses": {} }
+                    },
+                    "/info/": {
+                        "get": {
+                            "responses": {
+                                "200": { "$ref": "#/components/responses/InfoResponse" },
+                                "500": { "description": "Internal server error" }
+                            }
+                        }
+                    }
+                },
+                "components": {
+                    "responses": {
+                        "InfoResponse": {
+                            "content": {
+                                "application/json; charset=utf-8": {
+                                    "schema": { "$ref": "#/components/schemas/ApiInfoResponse" }
+                                }
+                            }
+                        }
+                    },
+                    "schemas": {
+                        "ApiResponse": {
+                            "type": "object",
+                            "required": ["success"],
+                            "properties": { "success": { "type": "boolean" } }
+                        },
+                        "ApiInfoResponse": {
+                            "allOf": [
+                                { "$ref": "#/components/schemas/ApiResponse" },
+                                { "properties": { "data": { "$ref": "#/components/schemas/InfoResponse" } } }
+                            ]
+                        },
+                        "InfoResponse": {
+                            "type": "object",
+                            "required": ["version"],
+                            "properties": {
+                                "version": { "type": "string" },
+                                "commit_sha": { "type": ["string", "null"] }
+                            }
+                        }
+                    }
+                }
+            }),
+        }
+    }
+
+    #[test]
+    fn operations_are_found_by_their_path() {
+        let spec = spec();
+        assert!(spec.operation("get", "/info").is_some());
+        assert!(spec.operation("post", "/info").is_none());
+        assert!(spec.operation("get", "/features").is_none());
+        assert!(spec.operation("get", "/users/{id}").is_some());
+        assert!(spec.operation("get", "/users/{id}/repositories").is_none());
+    }
+
+    #[test]
+    fn response_data_follows_references() {
+        let spec = spec();
+        let operation = spec.operation("get", "/info").unwrap();
+        assert_eq!(spec.response_data(operation).and_then(reference), Some("InfoResponse"));
+    }
+
+    #[test]
+    fn properties_are_merged() {
+        let spec = spec();
+        let properties = spec.properties(spec.schema("ApiInfoResponse").unwrap());
+        let names = properties
+            .iter()
+            .map(|property| (property.name, property.required))
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, [("data", false), ("success", true)]);
+        assert!(spec.is_object(spec.schema("ApiInfoResponse").unwrap()));
+    }
+
+    #[test]
+    fn nullable_types() {
+        let spec = spec();
+        let properties = spec.properties(spec.schema("InfoResponse").unwrap());
+        assert_eq!(ty(properties[0].schema), Some("string"));
+        assert!(is_nullable(properties[0].schema));
+        assert!(!is_nullable(properties[1].schema));
+
+        let reference = json!({ "anyOf": [{ "$ref": "#/components/schemas/InfoResponse" }, { "type": "null" }] });
+        assert_eq!(super::reference(&reference), Some("InfoResponse"));
+        assert!(is_nullable(&reference));
+    }
+}